@@ -0,0 +1,443 @@
+use quote::__private::{Span, TokenStream};
+use quote::quote;
+use serde_json::Value;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitStr, Token};
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true",
+    "type", "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final",
+    "macro", "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
+];
+
+struct ContractInput {
+    name: Ident,
+    path: LitStr,
+}
+
+impl Parse for ContractInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let path = input.parse()?;
+        let _ = input.parse::<Option<Token![,]>>()?;
+        Ok(Self { name, path })
+    }
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut result = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() {
+            if prev_lower {
+                result.push('_');
+            }
+            result.extend(c.to_lowercase());
+            prev_lower = false;
+        } else {
+            result.push(c);
+            prev_lower = c.is_lowercase() || c.is_numeric();
+        }
+    }
+    if KEYWORDS.contains(&result.as_str()) {
+        result.push('_');
+    }
+    result
+}
+
+fn to_camel_case(name: &str) -> String {
+    name.split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+fn str_field<'a>(value: &'a Value, name: &str) -> &'a str {
+    value[name].as_str().unwrap_or("")
+}
+
+fn array_field<'a>(value: &'a Value, name: &str) -> &'a [Value] {
+    value[name].as_array().map(|vec| vec.as_slice()).unwrap_or(&[])
+}
+
+fn unwrap_type<'a>(param_type: &'a str, prefix: &str) -> Option<&'a str> {
+    if param_type.starts_with(prefix) && param_type.ends_with(')') {
+        Some(&param_type[prefix.len()..param_type.len() - 1])
+    } else {
+        None
+    }
+}
+
+/// Generates Rust structs for ABI params.
+struct Generator {
+    structs: Vec<TokenStream>,
+    /// Names of generated types with their docs.
+    types: Vec<(String, String)>,
+    /// The first type name clash.
+    error: Option<String>,
+}
+
+impl Generator {
+    fn new(contract: &Ident) -> Self {
+        Self {
+            structs: vec![],
+            types: vec![(contract.to_string(), "contract wrapper".to_string())],
+            error: None,
+        }
+    }
+
+    /// Returns Rust type for the ABI type. Tuple types are generated as structs
+    /// named with `struct_name`.
+    fn rust_type(&mut self, param_type: &str, components: &[Value], struct_name: &str) -> TokenStream {
+        if param_type.ends_with(']') {
+            let open = param_type.rfind('[').unwrap_or(0);
+            let item = self.rust_type(&param_type[..open], components, struct_name);
+            return quote! { Vec<#item> };
+        }
+        if let Some(inner) = unwrap_type(param_type, "optional(") {
+            let inner = self.rust_type(inner, components, struct_name);
+            return quote! { Option<#inner> };
+        }
+        if let Some(inner) = unwrap_type(param_type, "ref(") {
+            return self.rust_type(inner, components, struct_name);
+        }
+        if let Some(inner) = unwrap_type(param_type, "map(") {
+            let value_type = &inner[inner.find(',').map(|comma| comma + 1).unwrap_or(0)..];
+            let value = self.rust_type(value_type, components, struct_name);
+            return quote! { std::collections::BTreeMap<String, #value> };
+        }
+        match param_type {
+            "tuple" => {
+                self.add_struct(struct_name, components, "Tuple parameter");
+                let ident = Ident::new(struct_name, Span::call_site());
+                quote! { #ident }
+            }
+            "bool" => quote! { bool },
+            "pubkey" => quote! { Option<String> },
+            _ => quote! { String },
+        }
+    }
+
+    fn add_struct(&mut self, name: &str, params: &[Value], doc: &str) {
+        if let Some((_, first)) = self.types.iter().find(|(existing, _)| existing == name) {
+            if self.error.is_none() {
+                self.error = Some(format!(
+                    "type `{}` generated for \"{}\" clashes with the type of \"{}\"",
+                    name, doc, first
+                ));
+            }
+            return;
+        }
+        self.types.push((name.to_string(), doc.to_string()));
+        let ident = Ident::new(name, Span::call_site());
+        let mut fields = Vec::new();
+        for param in params {
+            let abi_name = str_field(param, "name");
+            let field = Ident::new(&to_snake_case(abi_name), Span::call_site());
+            let field_type = self.rust_type(
+                str_field(param, "type"),
+                array_field(param, "components"),
+                &format!("{}{}", name, to_camel_case(abi_name)),
+            );
+            let param_type = str_field(param, "type");
+            let field_doc = format!("`{}`: `{}`", abi_name, param_type);
+            fields.push(quote! {
+                #[doc = #field_doc]
+                #[serde(rename = #abi_name)]
+                pub #field: #field_type,
+            });
+        }
+        self.structs.push(quote! {
+            #[doc = #doc]
+            #[derive(
+                ::ton_client::abi::bindings::Serialize,
+                ::ton_client::abi::bindings::Deserialize,
+                Debug, Clone, Default, PartialEq,
+            )]
+            #[serde(crate = "::ton_client::abi::bindings::serde")]
+            pub struct #ident {
+                #(#fields)*
+            }
+        });
+    }
+}
+
+/// Methods of the contract wrapper which are not generated from ABI functions.
+const FIXED_METHODS: &[&str] = &["new", "abi", "encode_params"];
+
+/// Fields methods, generated if ABI has `fields`.
+const FIELDS_METHODS: &[&str] = &["decode_fields", "encode_fields"];
+
+/// Names of methods generated for the ABI function.
+fn function_method_names(function: &Value) -> [String; 4] {
+    let snake_name = to_snake_case(str_field(function, "name"));
+    let base = snake_name.trim_end_matches('_');
+    [
+        format!("encode_{}_params", base),
+        format!("encode_{}", base),
+        snake_name.clone(),
+        format!("run_{}", base),
+    ]
+}
+
+/// Checks that generated methods do not clash with each other and with fixed methods.
+fn check_method_names(abi: &Value) -> Result<(), String> {
+    let mut names: Vec<(String, String)> = FIXED_METHODS
+        .iter()
+        .map(|name| (name.to_string(), "contract wrapper".to_string()))
+        .collect();
+    if !array_field(abi, "fields").is_empty() {
+        names.extend(
+            FIELDS_METHODS
+                .iter()
+                .map(|name| (name.to_string(), "contract `fields`".to_string())),
+        );
+    }
+    for function in array_field(abi, "functions") {
+        let origin = format!("function `{}`", str_field(function, "name"));
+        for name in function_method_names(function).iter() {
+            if let Some((_, first)) = names.iter().find(|(existing, _)| existing == name) {
+                return Err(format!(
+                    "method `{}` generated for {} clashes with the method of {}",
+                    name, origin, first
+                ));
+            }
+            names.push((name.clone(), origin.clone()));
+        }
+    }
+    Ok(())
+}
+
+fn function_methods(function: &Value, input: &Ident, output: &Ident) -> TokenStream {
+    let abi_name = str_field(function, "name");
+    let [encode_params, encode, process, run] = function_method_names(function);
+    let encode_params = Ident::new(&encode_params, Span::call_site());
+    let encode = Ident::new(&encode, Span::call_site());
+    let process = Ident::new(&process, Span::call_site());
+    let run = Ident::new(&run, Span::call_site());
+    let doc_encode_params = format!("Builds `abi.encode_message` parameters for `{}` call", abi_name);
+    let doc_encode = format!("Encodes `{}` call message with `abi.encode_message`", abi_name);
+    let doc_process = format!("Calls `{}` on-chain with `processing.process_message`", abi_name);
+    let doc_run = format!("Runs `{}` locally on the account BOC with `tvm.run_tvm`", abi_name);
+    quote! {
+        #[doc = #doc_encode_params]
+        pub fn #encode_params(
+            &self,
+            input: &#input,
+            signer: ::ton_client::abi::Signer,
+        ) -> ::ton_client::error::ClientResult<::ton_client::abi::ParamsOfEncodeMessage> {
+            self.encode_params(#abi_name, ::ton_client::abi::bindings::to_input(#abi_name, input)?, signer)
+        }
+
+        #[doc = #doc_encode]
+        pub async fn #encode(
+            &self,
+            input: &#input,
+            signer: ::ton_client::abi::Signer,
+        ) -> ::ton_client::error::ClientResult<::ton_client::abi::ResultOfEncodeMessage> {
+            ::ton_client::abi::encode_message(
+                self.context.clone(),
+                self.#encode_params(input, signer)?,
+            ).await
+        }
+
+        #[doc = #doc_process]
+        pub async fn #process(
+            &self,
+            input: &#input,
+            signer: ::ton_client::abi::Signer,
+        ) -> ::ton_client::error::ClientResult<(#output, ::ton_client::processing::ResultOfProcessMessage)> {
+            let result = ::ton_client::processing::process_message(
+                self.context.clone(),
+                ::ton_client::processing::ParamsOfProcessMessage {
+                    message_encode_params: self.#encode_params(input, signer)?,
+                    send_events: false,
+                },
+                |_| async {},
+            ).await?;
+            let output = ::ton_client::abi::bindings::from_output(
+                result.decoded.as_ref().and_then(|decoded| decoded.output.clone()),
+            )?;
+            Ok((output, result))
+        }
+
+        #[doc = #doc_run]
+        pub async fn #run(
+            &self,
+            account: String,
+            input: &#input,
+        ) -> ::ton_client::error::ClientResult<#output> {
+            let message = self.#encode(input, ::ton_client::abi::Signer::None).await?.message;
+            let result = ::ton_client::tvm::run_tvm(
+                self.context.clone(),
+                ::ton_client::tvm::ParamsOfRunTvm {
+                    message,
+                    account,
+                    abi: Some(Self::abi()),
+                    ..Default::default()
+                },
+            ).await?;
+            ::ton_client::abi::bindings::from_output(
+                result.decoded.and_then(|decoded| decoded.output),
+            )
+        }
+    }
+}
+
+fn contract_tokens(name: &Ident, abi_path: &str, abi_json: &str) -> Result<TokenStream, String> {
+    let abi: Value =
+        serde_json::from_str(abi_json).map_err(|err| format!("invalid ABI JSON: {}", err))?;
+    check_method_names(&abi)?;
+    let mut generator = Generator::new(name);
+    let mut methods = Vec::new();
+
+    for function in array_field(&abi, "functions") {
+        let camel_name = to_camel_case(str_field(function, "name"));
+        let input = format!("{}Input", camel_name);
+        let output = format!("{}Output", camel_name);
+        generator.add_struct(
+            &input,
+            array_field(function, "inputs"),
+            &format!("Input of `{}` function", str_field(function, "name")),
+        );
+        generator.add_struct(
+            &output,
+            array_field(function, "outputs"),
+            &format!("Output of `{}` function", str_field(function, "name")),
+        );
+        methods.push(function_methods(
+            function,
+            &Ident::new(&input, Span::call_site()),
+            &Ident::new(&output, Span::call_site()),
+        ));
+    }
+
+    for event in array_field(&abi, "events") {
+        generator.add_struct(
+            &format!("{}Event", to_camel_case(str_field(event, "name"))),
+            array_field(event, "inputs"),
+            &format!("`{}` event", str_field(event, "name")),
+        );
+    }
+
+    if !array_field(&abi, "fields").is_empty() {
+        generator.add_struct("Fields", array_field(&abi, "fields"), "Contract account data");
+        methods.push(quote! {
+            /// Decodes account data with `abi.decode_account_data`
+            pub async fn decode_fields(
+                &self,
+                data: String,
+            ) -> ::ton_client::error::ClientResult<Fields> {
+                let result = ::ton_client::abi::decode_account_data(
+                    self.context.clone(),
+                    ::ton_client::abi::ParamsOfDecodeAccountData { abi: Self::abi(), data },
+                ).await?;
+                ::ton_client::abi::bindings::from_output(Some(result.data))
+            }
+
+            /// Encodes account data with `abi.encode_account_data`
+            pub async fn encode_fields(
+                &self,
+                fields: &Fields,
+            ) -> ::ton_client::error::ClientResult<String> {
+                let result = ::ton_client::abi::encode_account_data(
+                    self.context.clone(),
+                    ::ton_client::abi::ParamsOfEncodeAccountData {
+                        abi: Self::abi(),
+                        data: ::ton_client::abi::bindings::to_input("fields", fields)?,
+                        boc_cache: None,
+                    },
+                ).await?;
+                Ok(result.data)
+            }
+        });
+    }
+
+    if let Some(err) = generator.error {
+        return Err(err);
+    }
+
+    let module = Ident::new(&to_snake_case(&name.to_string()), Span::call_site());
+    let structs = generator.structs;
+    let doc = format!("Typed bindings for `{}` contract", name);
+    Ok(quote! {
+        #[doc = #doc]
+        pub mod #module {
+            // Rebuilds bindings when the ABI file changes
+            const _: &[u8] = include_bytes!(#abi_path);
+
+            #(#structs)*
+
+            #[doc = #doc]
+            #[derive(Clone)]
+            pub struct #name {
+                pub context: std::sync::Arc<::ton_client::ClientContext>,
+                pub address: String,
+            }
+
+            impl #name {
+                /// Contract ABI JSON
+                pub const ABI: &'static str = #abi_json;
+
+                pub fn new(
+                    context: std::sync::Arc<::ton_client::ClientContext>,
+                    address: impl Into<String>,
+                ) -> Self {
+                    Self { context, address: address.into() }
+                }
+
+                pub fn abi() -> ::ton_client::abi::Abi {
+                    ::ton_client::abi::Abi::Json(Self::ABI.to_string())
+                }
+
+                /// Builds `abi.encode_message` parameters for an arbitrary function call
+                pub fn encode_params(
+                    &self,
+                    function_name: &str,
+                    input: ::ton_client::abi::bindings::Value,
+                    signer: ::ton_client::abi::Signer,
+                ) -> ::ton_client::error::ClientResult<::ton_client::abi::ParamsOfEncodeMessage> {
+                    Ok(::ton_client::abi::ParamsOfEncodeMessage {
+                        abi: Self::abi(),
+                        address: Some(self.address.clone()),
+                        call_set: ::ton_client::abi::CallSet::some_with_function_and_input(
+                            function_name,
+                            input,
+                        ),
+                        signer,
+                        ..Default::default()
+                    })
+                }
+
+                #(#methods)*
+            }
+        }
+    })
+}
+
+pub fn impl_abigen(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = syn::parse_macro_input!(input as ContractInput);
+    let path = std::path::Path::new(
+        &std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".into()),
+    )
+    .join(input.path.value());
+    let tokens = std::fs::read_to_string(&path)
+        .map_err(|err| format!("can not read ABI file {}: {}", path.display(), err))
+        .and_then(|abi_json| contract_tokens(&input.name, &path.to_string_lossy(), &abi_json));
+    match tokens {
+        Ok(tokens) => tokens.into(),
+        Err(err) => syn::Error::new(input.path.span(), err)
+            .to_compile_error()
+            .into(),
+    }
+}
+
@@ -1,3 +1,4 @@
+mod derive_contract;
 mod derive_function;
 mod derive_module;
 mod derive_type;
@@ -23,6 +24,14 @@ pub fn api_function(attr: TokenStream, input: TokenStream) -> TokenStream {
     crate::derive_function::impl_api_function(attr, input)
 }
 
+/// Generates typed Rust bindings for the contract ABI file.
+///
+/// `abigen!(Wallet, "path/to/Wallet.abi.json")` - path is relative to the crate manifest.
+#[proc_macro]
+pub fn abigen(input: TokenStream) -> TokenStream {
+    crate::derive_contract::impl_abigen(input)
+}
+
 #[macro_use]
 extern crate quote;
 
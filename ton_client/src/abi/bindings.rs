@@ -0,0 +1,41 @@
+//! Support items for typed contract bindings generated with `abigen!` macro.
+//!
+//! ```ignore
+//! ton_client::abi::bindings::abigen!(Wallet, "contracts/Wallet.abi.json");
+//!
+//! let wallet = wallet::Wallet::new(context, address);
+//! let (output, _) = wallet.send_transaction(&wallet::SendTransactionInput {
+//!     dest: "0:...".into(),
+//!     value: "1000000000".into(),
+//!     bounce: false,
+//! }, signer).await?;
+//! ```
+//!
+//! The macro reads the ABI file relative to the crate manifest directory and generates a module
+//! named after the contract in snake case. The module contains input and output structs for each
+//! function, structs for events and account data `fields`, and a contract wrapper whose methods
+//! call `abi.encode_message`, `processing.process_message` and `tvm.run_tvm`.
+//!
+//! Integer, address, bytes and cell values are represented with `String` in the same encoding
+//! the SDK uses for JSON values.
+
+use crate::abi::Error;
+use crate::error::ClientResult;
+use serde::de::DeserializeOwned;
+
+pub use api_derive::abigen;
+pub use serde;
+pub use serde_derive::{Deserialize, Serialize};
+pub use serde_json::Value;
+
+/// Converts typed function input into `CallSet.input` JSON.
+pub fn to_input<T: serde::Serialize>(function: &str, input: &T) -> ClientResult<Value> {
+    serde_json::to_value(input).map_err(|err| Error::encode_run_message_failed(err, function))
+}
+
+/// Converts decoded function output into the typed output.
+/// Missing output is treated as an empty object.
+pub fn from_output<T: DeserializeOwned>(output: Option<Value>) -> ClientResult<T> {
+    serde_json::from_value(output.unwrap_or_else(|| json!({})))
+        .map_err(|err| Error::invalid_message_for_decode(err))
+}
@@ -0,0 +1,48 @@
+use crate::abi::internal::encode_storage_fields;
+use crate::abi::types::Abi;
+use crate::boc::internal::serialize_cell_to_boc;
+use crate::boc::BocCacheType;
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use serde_json::Value;
+use std::sync::Arc;
+
+//---------------------------------------------------------------------------- encode_account_data
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfEncodeAccountData {
+    /// Contract ABI
+    pub abi: Abi,
+
+    /// Data to encode as a JSON structure. Must contain values for all ABI `fields`.
+    pub data: Value,
+
+    /// Cache type to put the result.
+    /// The BOC itself returned if no cache type provided.
+    pub boc_cache: Option<BocCacheType>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfEncodeAccountData {
+    /// Encoded data BOC or BOC handle
+    pub data: String,
+}
+
+/// Encodes account data using provided data JSON and ABI `fields` section.
+///
+/// The result can be used as account data for `encode_account` or `tvm.run_executor`
+/// to emulate an arbitrary contract state without running a constructor.
+///
+/// Note: this feature requires ABI 2.1 or higher.
+#[api_function]
+pub async fn encode_account_data(
+    context: Arc<ClientContext>,
+    params: ParamsOfEncodeAccountData,
+) -> ClientResult<ResultOfEncodeAccountData> {
+    let abi = params.abi.abi()?;
+    let data = encode_storage_fields(&abi, &params.data)?;
+
+    Ok(ResultOfEncodeAccountData {
+        data: serialize_cell_to_boc(&context, data, "contract data", params.boc_cache).await?,
+    })
+}
@@ -3,6 +3,7 @@ use crate::abi::internal::{add_sign_to_message, add_sign_to_message_body, create
 use crate::abi::{Abi, Error, FunctionHeader, Signer};
 use crate::boc::internal::{get_boc_hash, deserialize_cell_from_boc};
 use crate::client::ClientContext;
+use crate::crypto::SigningBoxContext;
 use crate::encoding::{account_decode, account_encode, decode_abi_number, hex_decode};
 use crate::error::ClientResult;
 use serde_json::Value;
@@ -416,6 +417,9 @@ fn encode_run(
 /// without private key disclosure to SDK. For instance, in case of using a cold wallet or HSM,
 /// when application calls some API to sign data.
 ///
+/// `Signer::Multi` collects signatures from several signing boxes and passes them
+/// to the contract in the function input parameter.
+///
 /// There is an optional public key can be provided in deploy set in order to substitute one
 /// in TVM file.
 ///
@@ -428,6 +432,17 @@ fn encode_run(
 pub async fn encode_message(
     context: std::sync::Arc<ClientContext>,
     params: ParamsOfEncodeMessage,
+) -> ClientResult<ResultOfEncodeMessage> {
+    if let Signer::Multi { .. } = params.signer {
+        encode_multi_signed_message(context, params).await
+    } else {
+        encode_single_signed_message(context, params).await
+    }
+}
+
+async fn encode_single_signed_message(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfEncodeMessage,
 ) -> ClientResult<ResultOfEncodeMessage> {
     let abi = params.abi.json_string()?;
 
@@ -472,8 +487,13 @@ pub async fn encode_message(
         return Err(abi::Error::missing_required_call_set_for_encode_message());
     };
 
+    let signing_context = SigningBoxContext {
+        address: Some(account_encode(&address)),
+        function_name: params.call_set.as_ref().map(|call_set| call_set.function_name.clone()),
+        input: params.call_set.as_ref().and_then(|call_set| call_set.input.clone()),
+    };
     let (message, data_to_sign) = try_to_sign_message(
-        context, &abi, message, data_to_sign, &params.signer
+        context, &abi, message, data_to_sign, &params.signer, &signing_context
     ).await?;
 
     Ok(ResultOfEncodeMessage {
@@ -692,6 +712,17 @@ pub struct ResultOfEncodeMessageBody {
 pub async fn encode_message_body(
     context: std::sync::Arc<ClientContext>,
     params: ParamsOfEncodeMessageBody,
+) -> ClientResult<ResultOfEncodeMessageBody> {
+    if let Signer::Multi { .. } = params.signer {
+        encode_multi_signed_message_body(context, params).await
+    } else {
+        encode_single_signed_message_body(context, params).await
+    }
+}
+
+async fn encode_single_signed_message_body(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfEncodeMessageBody,
 ) -> ClientResult<ResultOfEncodeMessageBody> {
     let abi = params.abi.json_string()?;
 
@@ -745,7 +776,16 @@ pub async fn encode_message_body(
     )
     .map_err(|err| Error::encode_run_message_failed(err, &func))?;
     if let Some(unsigned) = &data_to_sign {
-        if let Some(signature) = params.signer.sign(context.clone(), unsigned).await? {
+        let signing_context = SigningBoxContext {
+            address: None,
+            function_name: Some(params.call_set.function_name.clone()),
+            input: params.call_set.input.clone(),
+        };
+        if let Some(signature) = params
+            .signer
+            .sign_with_context(context.clone(), unsigned, &signing_context)
+            .await?
+        {
             let pubkey = public
                 .map(|string| hex_decode(&string))
                 .transpose()?;
@@ -767,6 +807,144 @@ pub async fn encode_message_body(
     })
 }
 
+//--------------------------------------------------------------------------- multi signed message
+
+/// Fixes the function header, so the message can be encoded several times
+/// with the same data to sign.
+fn resolve_call_set_header(
+    context: &Arc<ClientContext>,
+    abi: &str,
+    call_set: &CallSet,
+    processing_try_index: Option<u8>,
+    internal: bool,
+) -> ClientResult<Option<FunctionHeader>> {
+    if internal {
+        return Ok(None);
+    }
+    let contract = Contract::load(abi.as_bytes()).map_err(|x| Error::invalid_json(x))?;
+    resolve_header(call_set.header.as_ref(), None, processing_try_index, context, &contract)
+}
+
+fn encode_unsigned_body(
+    context: &Arc<ClientContext>,
+    abi: &str,
+    call_set: &CallSet,
+    processing_try_index: Option<u8>,
+    internal: bool,
+) -> ClientResult<ton_types::Cell> {
+    let call = call_set.to_function_call_set(None, processing_try_index, context, abi, internal)?;
+    ton_abi::encode_function_call(
+        abi.to_string(),
+        call.func.clone(),
+        call.header,
+        call.input,
+        internal,
+        None,
+    )
+    .and_then(|body| body.into_cell())
+    .map_err(|err| Error::encode_run_message_failed(err, &call.func))
+}
+
+/// Returns call set with the collected signatures and data to sign if the signatures
+/// must be collected by the application.
+async fn sign_multi(
+    context: Arc<ClientContext>,
+    abi: &str,
+    mut call_set: CallSet,
+    signer: &Signer,
+    address: Option<String>,
+    processing_try_index: Option<u8>,
+    internal: bool,
+) -> ClientResult<(CallSet, Option<Vec<u8>>)> {
+    let (signing_boxes, signatures, layout) = match signer {
+        Signer::Multi { signing_boxes, signatures, layout } => (signing_boxes, signatures, layout),
+        _ => return Ok((call_set, None)),
+    };
+    call_set.header =
+        resolve_call_set_header(&context, abi, &call_set, processing_try_index, internal)?;
+
+    let unsigned_call_set = CallSet {
+        input: Some(layout.apply(call_set.input.as_ref(), &[])?),
+        ..call_set.clone()
+    };
+    let data_to_sign = encode_unsigned_body(
+        &context, abi, &unsigned_call_set, processing_try_index, internal
+    )?
+        .repr_hash()
+        .as_slice()
+        .to_vec();
+
+    let signing_context = SigningBoxContext {
+        address,
+        function_name: Some(call_set.function_name.clone()),
+        input: call_set.input.clone(),
+    };
+    let collected = crate::crypto::boxes::signing_box::collect_signatures_with_context(
+        context,
+        &data_to_sign,
+        signing_boxes,
+        signatures.clone().unwrap_or_default(),
+        Some(&signing_context),
+    ).await?;
+    match collected.failed.len() {
+        0 => (),
+        1 => return Err(collected.failed[0].error.clone()),
+        _ => return Err(crate::crypto::Error::signing_boxes_failed(&collected.failed)),
+    }
+
+    call_set.input = Some(layout.apply(call_set.input.as_ref(), &collected.signatures)?);
+    let data_to_sign = if collected.signatures.is_empty() { Some(data_to_sign) } else { None };
+    Ok((call_set, data_to_sign))
+}
+
+async fn encode_multi_signed_message(
+    context: std::sync::Arc<ClientContext>,
+    mut params: ParamsOfEncodeMessage,
+) -> ClientResult<ResultOfEncodeMessage> {
+    let abi = params.abi.json_string()?;
+    let call_set = params
+        .call_set
+        .take()
+        .ok_or_else(|| abi::Error::missing_required_call_set_for_encode_message())?;
+    let (call_set, data_to_sign) = sign_multi(
+        context.clone(),
+        &abi,
+        call_set,
+        &params.signer,
+        params.address.clone(),
+        params.processing_try_index,
+        false,
+    ).await?;
+
+    params.call_set = Some(call_set);
+    params.signer = Signer::None;
+    let mut result = encode_single_signed_message(context, params).await?;
+    result.data_to_sign = data_to_sign.map(|data| base64::encode(&data));
+    Ok(result)
+}
+
+async fn encode_multi_signed_message_body(
+    context: std::sync::Arc<ClientContext>,
+    mut params: ParamsOfEncodeMessageBody,
+) -> ClientResult<ResultOfEncodeMessageBody> {
+    let abi = params.abi.json_string()?;
+    let (call_set, data_to_sign) = sign_multi(
+        context.clone(),
+        &abi,
+        params.call_set.clone(),
+        &params.signer,
+        None,
+        params.processing_try_index,
+        params.is_internal,
+    ).await?;
+
+    params.call_set = call_set;
+    params.signer = Signer::None;
+    let mut result = encode_single_signed_message_body(context, params).await?;
+    result.data_to_sign = data_to_sign.map(|data| base64::encode(&data));
+    Ok(result)
+}
+
 //------------------------------------------------------------------------------- attach_signature
 
 #[derive(Serialize, Deserialize, ApiType, Default)]
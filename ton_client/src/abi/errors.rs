@@ -17,6 +17,7 @@ pub enum ErrorCode {
     InvalidFunctionId = 312,
     InvalidData = 313,
     EncodeInitialDataFailed = 314,
+    EncodeAccountDataFailed = 315,
 }
 
 pub struct Error;
@@ -118,4 +119,11 @@ impl Error {
             format!("Encode initial data failed: {}", err),
         )
     }
+
+    pub fn encode_account_data_failed<E: Display>(err: E) -> ClientError {
+        error(
+            ErrorCode::EncodeAccountDataFailed,
+            format!("Encode account data failed: {}", err),
+        )
+    }
 }
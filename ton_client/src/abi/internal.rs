@@ -2,10 +2,13 @@ use crate::ClientContext;
 use crate::boc::internal::deserialize_cell_from_boc;
 use crate::abi::{Error, Signer, DeploySet};
 use crate::crypto::internal::decode_public_key;
+use crate::crypto::SigningBoxContext;
 use crate::encoding::hex_decode;
 use crate::error::ClientResult;
 use std::sync::Arc;
 use serde_json::Value;
+use ton_abi::token::Tokenizer;
+use ton_abi::TokenValue;
 use ton_sdk::ContractImage;
 
 /// Combines `hex` encoded `signature` with `base64` encoded `unsigned_message`.
@@ -52,9 +55,13 @@ pub(crate) async fn try_to_sign_message(
     message: Vec<u8>,
     data_to_sign: Option<Vec<u8>>,
     signer: &Signer,
+    signing_context: &SigningBoxContext,
 ) -> ClientResult<(Vec<u8>, Option<Vec<u8>>)> {
     if let Some(unsigned) = &data_to_sign {
-        if let Some(signature) = signer.sign(context.clone(), unsigned).await? {
+        if let Some(signature) = signer
+            .sign_with_context(context.clone(), unsigned, signing_context)
+            .await?
+        {
             let pubkey = signer.resolve_public_key(context)
                 .await?
                 .map(|string| hex_decode(&string))
@@ -136,3 +143,15 @@ pub(crate) fn update_pubkey(
             .map(|public| hex::encode(&public)))
     }
 }
+
+/// Encodes `data` JSON object into account data cell according to the `fields` section of ABI.
+pub(crate) fn encode_storage_fields(
+    abi: &ton_abi::Contract,
+    data: &Value,
+) -> ClientResult<ton_types::Cell> {
+    let tokens = Tokenizer::tokenize_all_params(abi.fields(), data)
+        .map_err(|err| Error::encode_account_data_failed(err))?;
+    TokenValue::pack_values_into_chain(&tokens, vec![], abi.version())
+        .and_then(|builder| builder.into_cell())
+        .map_err(|err| Error::encode_account_data_failed(err))
+}
@@ -0,0 +1,243 @@
+use crate::abi::types::{unwrap_param_type, Abi, AbiParam};
+use crate::abi::validate::validate_contract;
+use crate::abi::Error;
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use serde_json::{Map, Value};
+use std::sync::Arc;
+
+//-------------------------------------------------------------------------------- get_json_schema
+
+const SCHEMA_DRAFT: &str = "http://json-schema.org/draft-07/schema#";
+const UINT_PATTERN: &str = "^(0x[0-9a-fA-F]+|[0-9]+)$";
+const INT_PATTERN: &str = "^-?(0x[0-9a-fA-F]+|[0-9]+)$";
+const ADDRESS_PATTERN: &str = "^-?[0-9]+:[0-9a-fA-F]{64}$";
+const HEX_PATTERN: &str = "^([0-9a-fA-F]{2})*$";
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfGetJsonSchema {
+    /// Contract ABI
+    pub abi: Abi,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct AbiFunctionSchema {
+    /// Function name
+    pub name: String,
+
+    /// JSON Schema of the `CallSet.input` object
+    pub inputs: Value,
+
+    /// JSON Schema of the decoded function output
+    pub outputs: Value,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone)]
+pub struct AbiEventSchema {
+    /// Event name
+    pub name: String,
+
+    /// JSON Schema of the decoded event
+    pub inputs: Value,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfGetJsonSchema {
+    /// Schemas of contract functions
+    pub functions: Vec<AbiFunctionSchema>,
+
+    /// Schemas of contract events
+    pub events: Vec<AbiEventSchema>,
+
+    /// Schema of the initial data (static variables) used in `update_initial_data`.
+    /// `None` if ABI has no `data` section.
+    pub data: Option<Value>,
+
+    /// Schema of the account data used in `decode_account_data` and `encode_account_data`.
+    /// `None` if ABI has no `fields` section.
+    pub fields: Option<Value>,
+}
+
+/// Input values are accepted by the SDK in a more relaxed form than it produces output values
+#[derive(Clone, Copy, PartialEq)]
+enum Direction {
+    Input,
+    Output,
+}
+
+fn string_schema(pattern: &str, description: String) -> Value {
+    json!({
+        "type": "string",
+        "pattern": pattern,
+        "description": description,
+    })
+}
+
+fn integer_schema(param_type: &str, signed: bool, direction: Direction) -> Value {
+    let pattern = if signed { INT_PATTERN } else { UINT_PATTERN };
+    let string = string_schema(
+        pattern,
+        format!("`{}` encoded as a decimal or `0x` prefixed hex string", param_type),
+    );
+    match direction {
+        Direction::Output => string,
+        Direction::Input => json!({ "anyOf": [{ "type": "integer" }, string] }),
+    }
+}
+
+fn type_schema(param_type: &str, components: &[AbiParam], direction: Direction) -> Value {
+    if param_type.ends_with(']') {
+        let open = param_type.rfind('[').unwrap_or(0);
+        let mut schema = json!({
+            "type": "array",
+            "items": type_schema(&param_type[..open], components, direction),
+        });
+        if let Ok(size) = param_type[open + 1..param_type.len() - 1].parse::<usize>() {
+            schema["minItems"] = json!(size);
+            schema["maxItems"] = json!(size);
+        }
+        return schema;
+    }
+    if let Some(inner) = unwrap_param_type(param_type, "optional(") {
+        return json!({
+            "anyOf": [type_schema(inner, components, direction), { "type": "null" }]
+        });
+    }
+    if let Some(inner) = unwrap_param_type(param_type, "ref(") {
+        return type_schema(inner, components, direction);
+    }
+    if let Some(inner) = unwrap_param_type(param_type, "map(") {
+        let comma = inner.find(',').unwrap_or(0);
+        let key = &inner[..comma];
+        let key_pattern = if key == "address" {
+            ADDRESS_PATTERN
+        } else if key.starts_with("int") {
+            INT_PATTERN
+        } else {
+            UINT_PATTERN
+        };
+        return json!({
+            "type": "object",
+            "propertyNames": { "pattern": key_pattern },
+            "additionalProperties": type_schema(&inner[comma + 1..], components, direction),
+        });
+    }
+    if param_type == "tuple" {
+        return object_schema(components, direction);
+    }
+    match param_type {
+        "bool" => json!({ "type": "boolean" }),
+        "string" => json!({ "type": "string" }),
+        "address" => string_schema(ADDRESS_PATTERN, "Address in raw format `workchain:hex`".into()),
+        "bytes" => string_schema(HEX_PATTERN, "Bytes encoded in `hex`".into()),
+        "cell" => json!({
+            "type": "string",
+            "contentEncoding": "base64",
+            "description": "Cell BOC encoded in `base64`",
+        }),
+        "pubkey" => match direction {
+            Direction::Output => json!({
+                "anyOf": [string_schema("^0x[0-9a-fA-F]{64}$", "Public key".into()), { "type": "null" }]
+            }),
+            Direction::Input => json!({
+                "anyOf": [string_schema("^(0x)?[0-9a-fA-F]{64}$", "Public key".into()), { "type": "null" }]
+            }),
+        },
+        "gram" | "token" | "time" | "expire" => integer_schema(param_type, false, direction),
+        _ if param_type.starts_with("fixedbytes") => {
+            let size = param_type["fixedbytes".len()..].parse::<usize>().unwrap_or(0);
+            string_schema(
+                &format!("^[0-9a-fA-F]{{{}}}$", size * 2),
+                format!("{} bytes encoded in `hex`", size),
+            )
+        }
+        _ if param_type.starts_with("varint") || param_type.starts_with("int") => {
+            integer_schema(param_type, true, direction)
+        }
+        _ => integer_schema(param_type, false, direction),
+    }
+}
+
+fn object_schema(params: &[AbiParam], direction: Direction) -> Value {
+    let mut properties = Map::new();
+    for param in params {
+        properties.insert(
+            param.name.clone(),
+            type_schema(&param.param_type, &param.components, direction),
+        );
+    }
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": params.iter().map(|param| param.name.clone()).collect::<Vec<String>>(),
+        "additionalProperties": false,
+    })
+}
+
+fn document(title: String, params: &[AbiParam], direction: Direction) -> Value {
+    let mut schema = object_schema(params, direction);
+    schema["$schema"] = json!(SCHEMA_DRAFT);
+    schema["title"] = json!(title);
+    schema
+}
+
+/// Generates JSON Schema documents for the contract ABI.
+///
+/// Produces a schema for inputs and outputs of every function, for every event,
+/// for the initial data and for the account data `fields`. Schemas describe the encodings
+/// the SDK accepts and produces: big integers as decimal or hex strings, addresses in raw format,
+/// bytes in `hex`, cells in `base64`, `optional(...)` values as nullable, `map(...)` as objects
+/// and tuples as nested objects.
+#[api_function]
+pub async fn get_json_schema(
+    _context: Arc<ClientContext>,
+    params: ParamsOfGetJsonSchema,
+) -> ClientResult<ResultOfGetJsonSchema> {
+    let abi = params.abi.abi_contract()?;
+    if let Some(error) = validate_contract(&abi).first() {
+        return Err(Error::invalid_abi(format!("{}: {}", error.path, error.message)));
+    }
+
+    let functions = abi
+        .functions
+        .iter()
+        .map(|function| AbiFunctionSchema {
+            name: function.name.clone(),
+            inputs: document(format!("{}.inputs", function.name), &function.inputs, Direction::Input),
+            outputs: document(
+                format!("{}.outputs", function.name),
+                &function.outputs,
+                Direction::Output,
+            ),
+        })
+        .collect();
+
+    let events = abi
+        .events
+        .iter()
+        .map(|event| AbiEventSchema {
+            name: event.name.clone(),
+            inputs: document(event.name.clone(), &event.inputs, Direction::Output),
+        })
+        .collect();
+
+    let data = if abi.data.is_empty() {
+        None
+    } else {
+        let params: Vec<AbiParam> = abi.data.iter().map(AbiParam::from).collect();
+        Some(document("data".into(), &params, Direction::Input))
+    };
+
+    let fields = if abi.fields.is_empty() {
+        None
+    } else {
+        Some(document("fields".into(), &abi.fields, Direction::Output))
+    };
+
+    Ok(ResultOfGetJsonSchema {
+        functions,
+        events,
+        data,
+        fields,
+    })
+}
@@ -0,0 +1,164 @@
+use crate::abi::internal::encode_storage_fields;
+use crate::abi::types::Abi;
+use crate::abi::Error;
+use crate::boc::internal::{deserialize_object_from_boc, serialize_cell_to_boc};
+use crate::boc::BocCacheType;
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use serde_json::{Map, Value};
+use std::sync::Arc;
+use ton_abi::token::Detokenizer;
+use ton_abi::Param;
+
+//--------------------------------------------------------------------------- migrate_account_data
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfMigrateAccountData {
+    /// Contract ABI describing the current layout of the account data
+    pub old_abi: Abi,
+
+    /// Contract ABI describing the new layout of the account data
+    pub new_abi: Abi,
+
+    /// Account BOC or BOC handle
+    pub account: String,
+
+    /// Values for the fields that are added or retyped in the new layout.
+    /// Also can override values of unchanged fields.
+    /// If specified, account data is re-encoded according to the new layout.
+    pub defaults: Option<Value>,
+
+    /// Cache type to put the result.
+    /// The BOC itself returned if no cache type provided.
+    pub boc_cache: Option<BocCacheType>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct AbiFieldChange {
+    /// Field name
+    pub name: String,
+
+    /// Field type in the old layout. `None` for added fields.
+    pub old_type: Option<String>,
+
+    /// Field type in the new layout. `None` for removed fields.
+    pub new_type: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfMigrateAccountData {
+    /// Account data decoded with the old layout
+    pub data: Value,
+
+    /// Fields that exist in the new layout only
+    pub added: Vec<AbiFieldChange>,
+
+    /// Fields that exist in the old layout only
+    pub removed: Vec<AbiFieldChange>,
+
+    /// Fields that exist in both layouts with different types
+    pub retyped: Vec<AbiFieldChange>,
+
+    /// `true` if both layouts are identical, i.e. the new code can read
+    /// the existing data without re-encoding.
+    pub compatible: bool,
+
+    /// Data BOC or BOC handle encoded with the new layout.
+    /// Returned only if `defaults` are specified.
+    pub new_data: Option<String>,
+}
+
+fn find_field<'a>(fields: &'a [Param], name: &str) -> Option<&'a Param> {
+    fields.iter().find(|field| field.name == name)
+}
+
+fn field_change(old: Option<&Param>, new: Option<&Param>) -> AbiFieldChange {
+    AbiFieldChange {
+        name: old.or(new).map(|field| field.name.clone()).unwrap_or_default(),
+        old_type: old.map(|field| field.kind.type_signature()),
+        new_type: new.map(|field| field.kind.type_signature()),
+    }
+}
+
+/// Compares account data layouts of two contract ABIs and migrates account data
+/// from the old layout to the new one.
+///
+/// Decodes account data using the old ABI, reports fields added, removed and retyped
+/// in the new ABI. If `defaults` are provided, encodes the data according to the new ABI:
+/// values of unchanged fields are taken from the decoded data, values of added and retyped
+/// fields must be provided in `defaults`.
+///
+/// Note: this feature requires ABI 2.1 or higher.
+#[api_function]
+pub async fn migrate_account_data(
+    context: Arc<ClientContext>,
+    params: ParamsOfMigrateAccountData,
+) -> ClientResult<ResultOfMigrateAccountData> {
+    let account =
+        deserialize_object_from_boc::<ton_block::Account>(&context, &params.account, "account")
+            .await?
+            .object;
+    let data = account
+        .get_data()
+        .ok_or_else(|| Error::invalid_data_for_decode("account has no data"))?;
+
+    let old_abi = params.old_abi.abi()?;
+    let new_abi = params.new_abi.abi()?;
+    let old_fields = old_abi.fields();
+    let new_fields = new_abi.fields();
+
+    let tokens = old_abi
+        .decode_storage_fields(data.into())
+        .map_err(|e| Error::invalid_data_for_decode(e))?;
+    let decoded = Detokenizer::detokenize_to_json_value(&tokens)
+        .map_err(|e| Error::invalid_data_for_decode(e))?;
+
+    let mut added = Vec::new();
+    let mut retyped = Vec::new();
+    for new_field in new_fields {
+        match find_field(old_fields, &new_field.name) {
+            None => added.push(field_change(None, Some(new_field))),
+            Some(old_field) if old_field.kind != new_field.kind => {
+                retyped.push(field_change(Some(old_field), Some(new_field)))
+            }
+            _ => (),
+        }
+    }
+    let removed = old_fields
+        .iter()
+        .filter(|old_field| find_field(new_fields, &old_field.name).is_none())
+        .map(|old_field| field_change(Some(old_field), None))
+        .collect();
+
+    let compatible = old_fields == new_fields;
+
+    let new_data = if let Some(defaults) = params.defaults {
+        let defaults = defaults.as_object().ok_or_else(|| {
+            Error::encode_account_data_failed("`defaults` must be a JSON object")
+        })?;
+        let mut values = Map::new();
+        for new_field in new_fields {
+            let value = defaults.get(&new_field.name).or_else(|| {
+                find_field(old_fields, &new_field.name)
+                    .filter(|old_field| old_field.kind == new_field.kind)
+                    .and_then(|_| decoded.get(&new_field.name))
+            });
+            if let Some(value) = value {
+                values.insert(new_field.name.clone(), value.clone());
+            }
+        }
+        let cell = encode_storage_fields(&new_abi, &Value::Object(values))?;
+        Some(serialize_cell_to_boc(&context, cell, "contract data", params.boc_cache).await?)
+    } else {
+        None
+    };
+
+    Ok(ResultOfMigrateAccountData {
+        data: decoded,
+        added,
+        removed,
+        retyped,
+        compatible,
+        new_data,
+    })
+}
@@ -14,11 +14,16 @@
 #[cfg(test)]
 mod tests;
 
+pub mod bindings;
 pub(crate) mod decode_data;
 pub(crate) mod decode_message;
 pub(crate) mod encode_account;
+pub(crate) mod encode_data;
 pub(crate) mod encode_message;
 pub(crate) mod init_data;
+pub(crate) mod json_schema;
+pub(crate) mod migrate_data;
+pub(crate) mod validate;
 
 mod errors;
 mod internal;
@@ -35,6 +40,7 @@ pub use decode_message::{
 pub use encode_account::{
     encode_account, ParamsOfEncodeAccount, ResultOfEncodeAccount, StateInitParams, StateInitSource,
 };
+pub use encode_data::{encode_account_data, ParamsOfEncodeAccountData, ResultOfEncodeAccountData};
 pub use encode_message::{
     attach_signature, attach_signature_to_message_body, encode_internal_message, encode_message,
     encode_message_body, CallSet, DeploySet, ParamsOfAttachSignature,
@@ -47,11 +53,19 @@ pub use init_data::{
     decode_initial_data, update_initial_data, ParamsOfDecodeInitialData, ParamsOfUpdateInitialData,
     ResultOfDecodeInitialData, ResultOfUpdateInitialData,
 };
-pub use signing::Signer;
+pub use json_schema::{
+    get_json_schema, AbiEventSchema, AbiFunctionSchema, ParamsOfGetJsonSchema,
+    ResultOfGetJsonSchema,
+};
+pub use migrate_data::{
+    migrate_account_data, AbiFieldChange, ParamsOfMigrateAccountData, ResultOfMigrateAccountData,
+};
+pub use signing::{MultiSignatureLayout, Signer};
 pub use types::{
     Abi, AbiContract, AbiData, AbiEvent, AbiFunction, AbiHandle, AbiParam, FunctionHeader,
     MessageSource,
 };
+pub use validate::{validate_abi, AbiValidationError, ParamsOfValidateAbi, ResultOfValidateAbi};
 
 pub fn default_workchain() -> i32 {
     0
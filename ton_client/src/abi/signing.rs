@@ -1,5 +1,5 @@
 use crate::ClientContext;
-use crate::crypto::{KeyPair, SigningBoxHandle};
+use crate::crypto::{CollectedSignature, KeyPair, SigningBoxContext, SigningBoxHandle};
 use crate::error::ClientResult;
 use std::sync::Arc;
 
@@ -16,6 +16,90 @@ pub enum Signer {
     /// Signing Box interface is provided for signing, allows Dapps to sign messages using external APIs,
     /// such as HSM, cold wallet, etc.
     SigningBox { handle: SigningBoxHandle },
+    /// Several signing boxes sign the same data. Used for contracts with co-signers.
+    ///
+    /// The message is encoded without the standard ABI signature. Data to sign is the
+    /// representation hash of the function call body encoded without signatures
+    /// (the `layout` parameter is empty). Collected signatures are passed to the contract
+    /// in the function input parameter according to `layout`.
+    ///
+    /// If no signatures are collected (both `signing_boxes` and `signatures` are empty),
+    /// `data_to_sign` is returned, so the signatures can be collected later with
+    /// `crypto.collect_signatures` and passed in `signatures`. Such a signer can not be
+    /// used in `processing.process_message`.
+    ///
+    /// If several signing boxes fail, `SigningBoxesFailed` error is returned with
+    /// all failures in `data.failed`.
+    Multi {
+        /// Signing boxes to collect signatures from.
+        signing_boxes: Vec<SigningBoxHandle>,
+        /// Signatures collected before.
+        signatures: Option<Vec<CollectedSignature>>,
+        /// Layout of the signatures in the function call.
+        layout: MultiSignatureLayout,
+    },
+}
+
+/// Layout of the signatures collected by `Signer::Multi`.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, PartialEq)]
+#[serde(tag = "type")]
+pub enum MultiSignatureLayout {
+    /// Signatures are passed in the function input parameter of `bytes[]` type
+    /// in the order of collection.
+    Array {
+        /// Name of the function input parameter.
+        param: String,
+    },
+    /// Signatures are passed in the function input parameter of `mapping(uint256 => bytes)`
+    /// type with signer public keys as keys.
+    Map {
+        /// Name of the function input parameter.
+        param: String,
+    },
+}
+
+impl Default for MultiSignatureLayout {
+    fn default() -> Self {
+        MultiSignatureLayout::Array { param: String::new() }
+    }
+}
+
+impl MultiSignatureLayout {
+    /// Returns function input with `signatures` placed according to the layout.
+    pub(crate) fn apply(
+        &self,
+        input: Option<&serde_json::Value>,
+        signatures: &[CollectedSignature],
+    ) -> ClientResult<serde_json::Value> {
+        let mut input = input.cloned().unwrap_or_else(|| json!({}));
+        let (param, value) = match self {
+            MultiSignatureLayout::Array { param } => (
+                param,
+                signatures
+                    .iter()
+                    .map(|collected| collected.signature.clone().into())
+                    .collect::<Vec<serde_json::Value>>()
+                    .into(),
+            ),
+            MultiSignatureLayout::Map { param } => (
+                param,
+                signatures
+                    .iter()
+                    .map(|collected| {
+                        (format!("0x{}", collected.public_key), collected.signature.clone().into())
+                    })
+                    .collect::<serde_json::Map<String, serde_json::Value>>()
+                    .into(),
+            ),
+        };
+        input
+            .as_object_mut()
+            .ok_or_else(|| crate::abi::Error::invalid_signer(
+                "Function input must be an object to attach multiple signatures".into()
+            ))?
+            .insert(param.clone(), value);
+        Ok(input)
+    }
 }
 
 impl Default for Signer {
@@ -32,6 +116,17 @@ impl Signer {
             false
         }
     }
+
+    /// Returns `true` for `Multi` signer without signing boxes and signatures,
+    /// which returns `data_to_sign` instead of a signed message.
+    pub(crate) fn is_unsigned_multi(&self) -> bool {
+        match self {
+            Signer::Multi { signing_boxes, signatures, .. } => {
+                signing_boxes.is_empty() && signatures.as_ref().map_or(true, Vec::is_empty)
+            }
+            _ => false,
+        }
+    }
 }
 
 impl Signer {
@@ -42,7 +137,7 @@ impl Signer {
                 crate::crypto::internal::sign_using_keys(data_to_sign, &keys.decode()?)
                     .map(|(_, sign)| Some(sign))
             },
-            Signer::External { .. } => Ok(None),
+            Signer::External { .. } | Signer::Multi { .. } => Ok(None),
             Signer::SigningBox { handle } => {
                 let result = crate::crypto::signing_box_sign(
                     context,
@@ -58,11 +153,34 @@ impl Signer {
         }
     }
 
+    /// Signs message data. Signing box receives `signing_context` describing the message.
+    pub(crate) async fn sign_with_context(
+        &self,
+        context: Arc<ClientContext>,
+        data_to_sign: &[u8],
+        signing_context: &SigningBoxContext,
+    ) -> ClientResult<Option<Vec<u8>>> {
+        match self {
+            Signer::SigningBox { handle } => {
+                crate::crypto::boxes::signing_box::signing_box_sign_with_context(
+                    context,
+                    handle,
+                    data_to_sign,
+                    signing_context,
+                )
+                    .await
+                    .map(Some)
+            },
+            _ => self.sign(context, data_to_sign).await,
+        }
+    }
+
     pub async fn resolve_public_key(&self, context: Arc<ClientContext>) -> ClientResult<Option<String>> {
         match self {
             Signer::None => Ok(None),
             Signer::Keys { keys } => Ok(Some(keys.public.clone())),
             Signer::External { public_key } => Ok(Some(public_key.clone())),
+            Signer::Multi { .. } => Ok(None),
             Signer::SigningBox { handle } => {
                 crate::crypto::signing_box_get_public_key(
                     context,
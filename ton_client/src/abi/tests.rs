@@ -11,6 +11,7 @@ use crate::boc::internal::{
 };
 use crate::boc::{ParamsOfDecodeTvc, ParamsOfGetCodeFromTvc, ParamsOfParse, ResultOfDecodeTvc, ResultOfGetCodeFromTvc};
 use crate::crypto::KeyPair;
+use crate::json_interface::crypto::{ParamsOfAppSigningBox, ResultOfAppSigningBox};
 use crate::encoding::account_decode;
 use crate::tests::{EVENTS, HELLO, TestClient};
 use crate::utils::conversion::abi_uint;
@@ -20,6 +21,7 @@ use crate::{
     ClientContext,
 };
 
+use serde_json::Value;
 use std::io::Cursor;
 use ton_abi::Contract;
 use ton_block::{CurrencyCollection, Deserializable, InternalMessageHeader, Message, Serializable};
@@ -290,6 +292,341 @@ fn encode_v2() {
     assert_eq!(no_pubkey_body.body, extract_body(no_pubkey.message));
 }
 
+#[tokio::test(core_threads = 2)]
+async fn test_signing_box_context() {
+    let client = Arc::new(TestClient::new());
+    let client_copy = client.clone();
+    let (abi, _) = TestClient::package(EVENTS, Some(2));
+    let keys = client.generate_sign_keys();
+    let keys_copy = keys.clone();
+    let address = "0:05beb555e942fa744fd96f45a9ea9d0a8248208ca12421947c06e59bc997d309";
+
+    let callback = move |request: crate::client::ParamsOfAppRequest, _: u32| {
+        let client = client_copy.clone();
+        let keys = keys_copy.clone();
+        tokio::spawn(async move {
+            let result = match serde_json::from_value(request.request_data).unwrap() {
+                ParamsOfAppSigningBox::GetPublicKey => {
+                    ResultOfAppSigningBox::GetPublicKey { public_key: keys.public }
+                },
+                ParamsOfAppSigningBox::SignWithContext { unsigned, context } => {
+                    assert_eq!(context.address.as_deref(), Some(address));
+                    assert_eq!(context.function_name.as_deref(), Some("returnValue"));
+                    match context.input.as_ref().and_then(|input| input["id"].as_str()) {
+                        Some("0") => ResultOfAppSigningBox::Reject { reason: "zero id".into() },
+                        Some("2") => {
+                            // application fails the request
+                            let _: () = client.request_async(
+                                "client.resolve_app_request",
+                                crate::client::ParamsOfResolveAppRequest {
+                                    app_request_id: request.app_request_id,
+                                    result: crate::client::AppRequestResult::Error {
+                                        text: "signing failed".into(),
+                                    },
+                                },
+                            ).await.unwrap();
+                            return;
+                        },
+                        Some("3") => {
+                            // answer of the application which does not support the request
+                            client.resolve_app_request(
+                                request.app_request_id,
+                                json!({ "type": "SignWithContext" }),
+                            ).await;
+                            return;
+                        },
+                        _ => ResultOfAppSigningBox::Sign {
+                            signature: client.sign_detached(&unsigned, &keys),
+                        },
+                    }
+                },
+                ParamsOfAppSigningBox::Sign { unsigned } => {
+                    ResultOfAppSigningBox::Sign {
+                        signature: client.sign_detached(&unsigned, &keys),
+                    }
+                },
+                request => panic!("unexpected signing box request {:?}", request),
+            };
+            client.resolve_app_request(request.app_request_id, result).await;
+        });
+        futures::future::ready(())
+    };
+
+    let signing_box: crate::crypto::RegisteredSigningBox = client
+        .request_async_callback("crypto.register_signing_box", (), callback)
+        .await
+        .unwrap();
+
+    let run_params = |id: &str| ParamsOfEncodeMessage {
+        abi: abi.clone(),
+        address: Some(address.into()),
+        deploy_set: None,
+        call_set: CallSet::some_with_function_and_input("returnValue", json!({ "id": id })),
+        signer: Signer::SigningBox {
+            handle: signing_box.handle.clone(),
+        },
+        processing_try_index: None,
+    };
+
+    let signed: ResultOfEncodeMessage = client
+        .request_async("abi.encode_message", run_params("1"))
+        .await
+        .unwrap();
+    assert_eq!(signed.data_to_sign, None);
+
+    let error = client
+        .request_async::<_, ResultOfEncodeMessage>("abi.encode_message", run_params("0"))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, crate::crypto::ErrorCode::SigningBoxRejected as u32);
+    assert_eq!(error.data["reason"], "zero id");
+
+    let error = client
+        .request_async::<_, ResultOfEncodeMessage>("abi.encode_message", run_params("2"))
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, crate::client::ErrorCode::AppRequestError as u32);
+
+    let signed: ResultOfEncodeMessage = client
+        .request_async("abi.encode_message", run_params("3"))
+        .await
+        .unwrap();
+    assert_eq!(signed.data_to_sign, None);
+}
+
+#[tokio::test(core_threads = 2)]
+async fn test_multi_signer() {
+    let client = Arc::new(TestClient::new());
+    let abi = Abi::Json(json!({
+        "ABI version": 2,
+        "header": ["time", "expire"],
+        "functions": [{
+            "name": "submit",
+            "inputs": [
+                { "name": "value", "type": "uint64" },
+                { "name": "signatures", "type": "bytes[]" }
+            ],
+            "outputs": []
+        }, {
+            "name": "confirm",
+            "inputs": [
+                { "name": "signatures", "type": "map(uint256,bytes)" }
+            ],
+            "outputs": []
+        }],
+        "events": [],
+        "data": []
+    }).to_string());
+    let address = "0:05beb555e942fa744fd96f45a9ea9d0a8248208ca12421947c06e59bc997d309";
+    let keys = vec![client.generate_sign_keys(), client.generate_sign_keys()];
+    let mut signing_boxes = Vec::new();
+    for key_pair in &keys {
+        let signing_box: crate::crypto::RegisteredSigningBox = client
+            .request_async("crypto.get_signing_box", key_pair.clone())
+            .await
+            .unwrap();
+        signing_boxes.push(signing_box.handle);
+    }
+
+    let encode_params = |function: &str, input: Value, signer: Signer| ParamsOfEncodeMessage {
+        abi: abi.clone(),
+        address: Some(address.into()),
+        deploy_set: None,
+        call_set: Some(CallSet {
+            function_name: function.into(),
+            header: Some(FunctionHeader {
+                time: Some(1599458364291),
+                expire: Some(1599458404),
+                pubkey: None,
+            }),
+            input: Some(input),
+        }),
+        signer,
+        processing_try_index: None,
+    };
+    async fn decode(client: &TestClient, abi: &Abi, message: String) -> Value {
+        client
+            .request_async::<_, DecodedMessageBody>(
+                "abi.decode_message",
+                ParamsOfDecodeMessage { abi: abi.clone(), message },
+            )
+            .await
+            .unwrap()
+            .value
+            .unwrap()
+    }
+
+    // data to sign for later collection
+    let unsigned: ResultOfEncodeMessage = client
+        .request_async(
+            "abi.encode_message",
+            encode_params("submit", json!({ "value": 1 }), Signer::Multi {
+                signing_boxes: vec![],
+                signatures: None,
+                layout: MultiSignatureLayout::Array { param: "signatures".into() },
+            }),
+        )
+        .await
+        .unwrap();
+    let data_to_sign = unsigned.data_to_sign.unwrap();
+    assert_eq!(decode(&client, &abi, unsigned.message).await["signatures"], json!([]));
+
+    // signing boxes sign the same data
+    let signed: ResultOfEncodeMessage = client
+        .request_async(
+            "abi.encode_message",
+            encode_params("submit", json!({ "value": 1 }), Signer::Multi {
+                signing_boxes: signing_boxes.clone(),
+                signatures: None,
+                layout: MultiSignatureLayout::Array { param: "signatures".into() },
+            }),
+        )
+        .await
+        .unwrap();
+    assert_eq!(signed.data_to_sign, None);
+    let decoded = decode(&client, &abi, signed.message).await;
+    assert_eq!(decoded["value"], "1");
+    let signatures = decoded["signatures"].as_array().unwrap();
+    assert_eq!(signatures.len(), 2);
+    for (key_pair, signature) in keys.iter().zip(signatures) {
+        let verified: crate::crypto::ResultOfNaclSignDetachedVerify = client
+            .request_async(
+                "crypto.nacl_sign_detached_verify",
+                crate::crypto::ParamsOfNaclSignDetachedVerify {
+                    unsigned: data_to_sign.clone(),
+                    signature: signature.as_str().unwrap().to_owned(),
+                    public: key_pair.public.clone(),
+                },
+            )
+            .await
+            .unwrap();
+        assert!(verified.succeeded);
+    }
+
+    // partial collection is resumed
+    let unsigned: ResultOfEncodeMessage = client
+        .request_async(
+            "abi.encode_message",
+            encode_params("confirm", json!({}), Signer::Multi {
+                signing_boxes: vec![],
+                signatures: None,
+                layout: MultiSignatureLayout::Map { param: "signatures".into() },
+            }),
+        )
+        .await
+        .unwrap();
+    let partial: crate::crypto::ResultOfCollectSignatures = client
+        .request_async(
+            "crypto.collect_signatures",
+            crate::crypto::ParamsOfCollectSignatures {
+                unsigned: unsigned.data_to_sign.clone().unwrap(),
+                signing_boxes: vec![signing_boxes[0].clone()],
+                signatures: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(partial.signatures.len(), 1);
+    assert!(partial.failed.is_empty());
+    let resumed: crate::crypto::ResultOfCollectSignatures = client
+        .request_async(
+            "crypto.collect_signatures",
+            crate::crypto::ParamsOfCollectSignatures {
+                unsigned: unsigned.data_to_sign.unwrap(),
+                signing_boxes: signing_boxes.clone(),
+                signatures: Some(partial.signatures.clone()),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(resumed.signatures.len(), 2);
+    assert_eq!(resumed.signatures[0], partial.signatures[0]);
+    assert_eq!(resumed.signatures[1].public_key, keys[1].public);
+
+    let signed: ResultOfEncodeMessage = client
+        .request_async(
+            "abi.encode_message",
+            encode_params("confirm", json!({}), Signer::Multi {
+                signing_boxes: vec![],
+                signatures: Some(resumed.signatures.clone()),
+                layout: MultiSignatureLayout::Map { param: "signatures".into() },
+            }),
+        )
+        .await
+        .unwrap();
+    assert_eq!(signed.data_to_sign, None);
+    let signatures = decode(&client, &abi, signed.message).await["signatures"].clone();
+    let signatures = signatures.as_object().unwrap();
+    assert_eq!(signatures.len(), 2);
+    for collected in &resumed.signatures {
+        assert!(signatures.values().any(|signature| *signature == collected.signature));
+    }
+
+    // all failed signing boxes are reported
+    let mut rejecting_boxes = Vec::new();
+    for _ in 0..2 {
+        let client_copy = client.clone();
+        let public_key = client.generate_sign_keys().public;
+        let callback = move |request: crate::client::ParamsOfAppRequest, _: u32| {
+            let client = client_copy.clone();
+            let public_key = public_key.clone();
+            tokio::spawn(async move {
+                let result = match serde_json::from_value(request.request_data).unwrap() {
+                    ParamsOfAppSigningBox::GetPublicKey => {
+                        ResultOfAppSigningBox::GetPublicKey { public_key }
+                    },
+                    _ => ResultOfAppSigningBox::Reject { reason: "not a co-signer".into() },
+                };
+                client.resolve_app_request(request.app_request_id, result).await;
+            });
+            futures::future::ready(())
+        };
+        let signing_box: crate::crypto::RegisteredSigningBox = client
+            .request_async_callback("crypto.register_signing_box", (), callback)
+            .await
+            .unwrap();
+        rejecting_boxes.push(signing_box.handle);
+    }
+    let error = client
+        .request_async::<_, ResultOfEncodeMessage>(
+            "abi.encode_message",
+            encode_params("submit", json!({ "value": 1 }), Signer::Multi {
+                signing_boxes: vec![
+                    signing_boxes[0].clone(),
+                    rejecting_boxes[0].clone(),
+                    rejecting_boxes[1].clone(),
+                ],
+                signatures: None,
+                layout: MultiSignatureLayout::Array { param: "signatures".into() },
+            }),
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, crate::crypto::ErrorCode::SigningBoxesFailed as u32);
+    let failed = error.data["failed"].as_array().unwrap();
+    assert_eq!(failed.len(), 2);
+    assert_eq!(failed[0]["handle"], rejecting_boxes[0].0);
+    assert_eq!(failed[1]["handle"], rejecting_boxes[1].0);
+    assert_eq!(failed[1]["error"]["data"]["reason"], "not a co-signer");
+
+    // signer without signatures can not be used for processing
+    let error = client
+        .net_process_message(
+            crate::processing::ParamsOfProcessMessage {
+                message_encode_params: encode_params("submit", json!({ "value": 1 }), Signer::Multi {
+                    signing_boxes: vec![],
+                    signatures: None,
+                    layout: MultiSignatureLayout::Array { param: "signatures".into() },
+                }),
+                send_events: false,
+            },
+            |_: crate::processing::ProcessingEvent, _: crate::processing::ProcessingResponseType| async {},
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, crate::processing::ErrorCode::ExternalSignerMustNotBeUsed as u32);
+}
+
 #[test]
 fn decode_v2() {
     TestClient::init_log();
@@ -942,6 +1279,201 @@ async fn test_decode_account_data() {
     );
 }
 
+#[tokio::test]
+async fn test_encode_account_data() {
+    let abi = Abi::Json(ACCOUNT_ABI.to_owned());
+    let state = deserialize_object_from_base64::<ton_block::StateInit>(ACCOUNT_STATE, "state").unwrap();
+    let data = serialize_cell_to_base64(&state.object.data.unwrap(), "data").unwrap();
+
+    let context = Arc::new(ClientContext::new(Default::default()).unwrap());
+    let decoded = decode_account_data(
+        context.clone(),
+        ParamsOfDecodeAccountData { data: data.clone(), abi: abi.clone() },
+    )
+    .await
+    .unwrap()
+    .data;
+
+    let encoded = encode_account_data(
+        context.clone(),
+        ParamsOfEncodeAccountData { abi: abi.clone(), data: decoded.clone(), boc_cache: None },
+    )
+    .await
+    .unwrap()
+    .data;
+    let redecoded = decode_account_data(
+        context.clone(),
+        ParamsOfDecodeAccountData { data: encoded, abi: abi.clone() },
+    )
+    .await
+    .unwrap()
+    .data;
+    assert_eq!(redecoded, decoded);
+
+    let encoded = encode_account_data(
+        context.clone(),
+        ParamsOfEncodeAccountData {
+            abi: abi.clone(),
+            data: json!({
+                "__pubkey": format!("0x{}", "22".repeat(32)),
+                "__timestamp": "1",
+                "fun": "100",
+                "opt": null,
+                "big": null,
+                "a": "",
+                "b": "0102",
+                "length": "0x01",
+            }),
+            boc_cache: None,
+        },
+    )
+    .await
+    .unwrap()
+    .data;
+
+    let decoded = decode_account_data(
+        context.clone(),
+        ParamsOfDecodeAccountData { data: encoded, abi: abi.clone() },
+    )
+    .await
+    .unwrap()
+    .data;
+    assert_eq!(decoded["__pubkey"], json!(format!("0x{}", "22".repeat(32))));
+    assert_eq!(decoded["fun"], json!("100"));
+    assert_eq!(decoded["opt"], json!(null));
+    assert_eq!(decoded["b"], json!("0102"));
+
+    let error = encode_account_data(
+        context,
+        ParamsOfEncodeAccountData { abi, data: json!({ "fun": "1" }), boc_cache: None },
+    )
+    .await
+    .unwrap_err();
+    assert_eq!(error.code, ErrorCode::EncodeAccountDataFailed as u32);
+}
+
+#[tokio::test]
+async fn test_migrate_account_data() {
+    let state = deserialize_object_from_base64::<ton_block::StateInit>(ACCOUNT_STATE, "state").unwrap();
+    let context = Arc::new(ClientContext::new(Default::default()).unwrap());
+    let account = encode_account(
+        context.clone(),
+        ParamsOfEncodeAccount {
+            state_init: StateInitSource::StateInit {
+                code: serialize_cell_to_base64(&state.object.code.unwrap(), "code").unwrap(),
+                data: serialize_cell_to_base64(&state.object.data.unwrap(), "data").unwrap(),
+                library: None,
+            },
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap()
+    .account;
+
+    let mut new_abi: AbiContract = serde_json::from_str(ACCOUNT_ABI).unwrap();
+    new_abi.fields.retain(|field| field.name != "b");
+    new_abi.fields.iter_mut()
+        .find(|field| field.name == "fun")
+        .unwrap()
+        .param_type = "uint64".into();
+    new_abi.fields.push(AbiParam {
+        name: "owner".into(),
+        param_type: "address".into(),
+        components: vec![],
+    });
+    let old_abi = Abi::Json(ACCOUNT_ABI.to_owned());
+    let new_abi = Abi::Contract(new_abi);
+
+    let result = migrate_account_data(
+        context.clone(),
+        ParamsOfMigrateAccountData {
+            old_abi: old_abi.clone(),
+            new_abi: new_abi.clone(),
+            account: account.clone(),
+            defaults: None,
+            boc_cache: None,
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(result.data["fun"], json!("22"));
+    assert!(!result.compatible);
+    assert!(result.new_data.is_none());
+    assert_eq!(result.added, vec![AbiFieldChange {
+        name: "owner".into(),
+        old_type: None,
+        new_type: Some("address".into()),
+    }]);
+    assert_eq!(result.removed, vec![AbiFieldChange {
+        name: "b".into(),
+        old_type: Some("bytes".into()),
+        new_type: None,
+    }]);
+    assert_eq!(result.retyped, vec![AbiFieldChange {
+        name: "fun".into(),
+        old_type: Some("uint32".into()),
+        new_type: Some("uint64".into()),
+    }]);
+
+    let owner = "0:1111111111111111111111111111111111111111111111111111111111111111";
+    let new_data = migrate_account_data(
+        context.clone(),
+        ParamsOfMigrateAccountData {
+            old_abi: old_abi.clone(),
+            new_abi: new_abi.clone(),
+            account: account.clone(),
+            defaults: Some(json!({ "fun": "23", "owner": owner })),
+            boc_cache: None,
+        },
+    )
+    .await
+    .unwrap()
+    .new_data
+    .unwrap();
+
+    let decoded = decode_account_data(
+        context.clone(),
+        ParamsOfDecodeAccountData { data: new_data, abi: new_abi },
+    )
+    .await
+    .unwrap()
+    .data;
+
+    assert_eq!(decoded["fun"], json!("23"));
+    assert_eq!(decoded["owner"], json!(owner));
+    assert_eq!(decoded["a"], result.data["a"]);
+    assert_eq!(decoded["big"], result.data["big"]);
+    assert!(decoded.get("b").is_none());
+
+    // appended fields can not be read from the old data
+    let mut appended_abi: AbiContract = serde_json::from_str(ACCOUNT_ABI).unwrap();
+    appended_abi.fields.push(AbiParam {
+        name: "owner".into(),
+        param_type: "address".into(),
+        components: vec![],
+    });
+    for (new_abi, compatible) in vec![
+        (Abi::Contract(appended_abi), false),
+        (old_abi.clone(), true),
+    ] {
+        let result = migrate_account_data(
+            context.clone(),
+            ParamsOfMigrateAccountData {
+                old_abi: old_abi.clone(),
+                new_abi,
+                account: account.clone(),
+                defaults: None,
+                boc_cache: None,
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(result.compatible, compatible);
+    }
+}
+
 #[test]
 fn test_init_data() {
     let client = TestClient::new();
@@ -1002,3 +1534,199 @@ fn test_init_data() {
     assert_eq!(result.initial_data, Some(initial_data));
     assert_eq!(result.initial_pubkey, hex::encode(&[0x22u8; 32]));
 }
+
+#[test]
+fn test_validate_abi() {
+    let client = TestClient::new();
+    let (events_abi, _) = TestClient::package(EVENTS, Some(2));
+
+    let result: ResultOfValidateAbi = client
+        .request("abi.validate_abi", ParamsOfValidateAbi { abi: events_abi })
+        .unwrap();
+    assert_eq!(result.errors, vec![]);
+
+    let abi = Abi::Json(r#"{
+        "ABI version": 2,
+        "header": ["time", "nonce"],
+        "functions": [
+            {
+                "name": "transfer",
+                "inputs": [
+                    {"name":"dest","type":"address"},
+                    {"name":"value","type":"uint512"}
+                ],
+                "outputs": []
+            },
+            {
+                "name": "getInfo",
+                "inputs": [],
+                "outputs": [{"name":"info","type":"tuple[]"}],
+                "id": "0x00000001"
+            },
+            {
+                "name": "setOwner",
+                "inputs": [{"name":"owner","type":"map(bytes,uint8)"}],
+                "outputs": [],
+                "id": "0x00000001"
+            }
+        ],
+        "events": [
+            {"name": "transfer", "inputs": []}
+        ],
+        "data": [
+            {"key":0,"name":"owner","type":"uint256"},
+            {"key":1,"name":"seqno","type":"uint32"},
+            {"key":1,"name":"nonce","type":"uint32"}
+        ],
+        "fields": [
+            {"name":"seqno","type":"uint32"}
+        ]
+    }"#.to_owned());
+
+    let result: ResultOfValidateAbi = client
+        .request("abi.validate_abi", ParamsOfValidateAbi { abi })
+        .unwrap();
+    let paths: Vec<&str> = result.errors.iter().map(|error| error.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec![
+            "$.header[1]",
+            "$.functions[0].inputs[1].type",
+            "$.functions[1].outputs[0].type",
+            "$.functions[2].inputs[0].type",
+            "$.functions[2]",
+            "$.events[0].name",
+            "$.data[0].key",
+            "$.data[2].key",
+            "$.fields",
+        ]
+    );
+}
+
+#[test]
+fn test_get_json_schema() {
+    let client = TestClient::new();
+    let (events_abi, _) = TestClient::package(EVENTS, Some(2));
+
+    let result: ResultOfGetJsonSchema = client
+        .request("abi.get_json_schema", ParamsOfGetJsonSchema { abi: events_abi })
+        .unwrap();
+    let function = result.functions.iter().find(|f| f.name == "sendAllMoney").unwrap();
+    assert_eq!(function.inputs["title"], json!("sendAllMoney.inputs"));
+    assert_eq!(function.inputs["required"], json!(["dest_addr"]));
+    assert_eq!(
+        function.inputs["properties"]["dest_addr"]["pattern"],
+        json!("^-?[0-9]+:[0-9a-fA-F]{64}$")
+    );
+    let function = result.functions.iter().find(|f| f.name == "returnValue").unwrap();
+    assert_eq!(function.outputs["properties"]["value0"]["type"], json!("string"));
+    assert_eq!(result.events[0].name, "EventThrown");
+    assert_eq!(result.data, None);
+    assert_eq!(result.fields, None);
+
+    let abi = Abi::Json(ACCOUNT_ABI.to_owned());
+    let result: ResultOfGetJsonSchema = client
+        .request("abi.get_json_schema", ParamsOfGetJsonSchema { abi })
+        .unwrap();
+    let fields = result.fields.unwrap();
+    assert_eq!(
+        fields["properties"]["opt"],
+        json!({
+            "anyOf": [
+                { "type": "string", "pattern": "^([0-9a-fA-F]{2})*$", "description": "Bytes encoded in `hex`" },
+                { "type": "null" }
+            ]
+        })
+    );
+    assert_eq!(fields["properties"]["big"]["anyOf"][0]["type"], json!("object"));
+    assert_eq!(
+        fields["properties"]["big"]["anyOf"][0]["required"],
+        json!(["value0", "value1", "value2", "value3"])
+    );
+}
+
+bindings::abigen!(EventsContract, "src/tests/contracts/abi_v2/Events.abi.json");
+
+#[tokio::test]
+async fn test_abigen_bindings() {
+    use events_contract::{EventsContract, ReturnValueInput, ReturnValueOutput, EventThrownEvent};
+
+    let context = Arc::new(ClientContext::new(Default::default()).unwrap());
+    let address = "0:1111111111111111111111111111111111111111111111111111111111111111";
+    let contract = EventsContract::new(context.clone(), address);
+
+    let params = contract
+        .encode_return_value_params(&ReturnValueInput { id: "0x1".into() }, Signer::None)
+        .unwrap();
+    assert_eq!(params.address.as_deref(), Some(address));
+    let call_set = params.call_set.unwrap();
+    assert_eq!(call_set.function_name, "returnValue");
+    assert_eq!(call_set.input, Some(json!({ "id": "0x1" })));
+
+    let typed = contract
+        .encode_return_value(&ReturnValueInput { id: "0x1".into() }, Signer::None)
+        .await
+        .unwrap();
+    let decoded = decode_message(
+        context,
+        ParamsOfDecodeMessage {
+            abi: EventsContract::abi(),
+            message: typed.message,
+        },
+    )
+    .await
+    .unwrap();
+    assert_eq!(decoded.name, "returnValue");
+    assert_eq!(
+        decoded.value,
+        Some(json!({ "id": "0x0000000000000000000000000000000000000000000000000000000000000001" }))
+    );
+
+    let output: ReturnValueOutput = bindings::from_output(Some(json!({ "value0": "0x01" }))).unwrap();
+    assert_eq!(output.value0, "0x01");
+    let event: EventThrownEvent = bindings::from_output(Some(json!({ "id": "0x02" }))).unwrap();
+    assert_eq!(event.id, "0x02");
+}
+
+bindings::abigen!(HelloContract, "src/tests/contracts/abi_v2/Hello.abi.json");
+
+#[tokio::test]
+async fn test_abigen_run_local() {
+    use hello_contract::{HelloContract, SayHelloInput};
+    use crate::tvm::{run_executor, AccountForExecutor, ParamsOfRunExecutor};
+
+    let context = Arc::new(ClientContext::new(Default::default()).unwrap());
+    let keys = crate::crypto::generate_random_sign_keys(context.clone()).unwrap();
+    let deploy = encode_message(
+        context.clone(),
+        ParamsOfEncodeMessage {
+            abi: HelloContract::abi(),
+            address: None,
+            deploy_set: DeploySet::some_with_tvc(TestClient::tvc(HELLO, Some(2))),
+            call_set: CallSet::some_with_function("constructor"),
+            signer: Signer::Keys { keys },
+            processing_try_index: None,
+        },
+    )
+    .await
+    .unwrap();
+    let deployed = run_executor(
+        context.clone(),
+        ParamsOfRunExecutor {
+            message: deploy.message,
+            account: AccountForExecutor::Uninit,
+            return_updated_account: Some(true),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let contract = HelloContract::new(context, deploy.address);
+    let output = contract
+        .run_say_hello(deployed.account, &SayHelloInput {})
+        .await
+        .unwrap();
+    let timestamp: u32 = crate::encoding::decode_abi_number(&output.value0).unwrap();
+    assert!(timestamp > 0);
+}
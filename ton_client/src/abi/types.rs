@@ -36,6 +36,16 @@ impl Abi {
         }
     }
 
+    pub(crate) fn abi_contract(&self) -> ClientResult<AbiContract> {
+        match self {
+            Self::Contract(abi) | Self::Serialized(abi) => Ok(abi.clone()),
+            Self::Json(abi) => serde_json::from_str(abi).map_err(|err| Error::invalid_json(err)),
+            _ => Err(crate::client::Error::not_implemented(
+                "ABI handles are not supported yet",
+            )),
+        }
+    }
+
     pub(crate) fn abi(&self) -> ClientResult<ton_abi::Contract> {
         ton_abi::Contract::load(self.json_string()?.as_bytes())
             .map_err(|x| Error::invalid_json(x))
@@ -102,6 +112,26 @@ pub struct AbiParam {
     pub components: Vec<AbiParam>,
 }
 
+impl From<&AbiData> for AbiParam {
+    fn from(data: &AbiData) -> Self {
+        Self {
+            name: data.name.clone(),
+            param_type: data.param_type.clone(),
+            components: data.components.clone(),
+        }
+    }
+}
+
+/// Returns the argument of the parameterized type, e.g. `uint8` for `optional(uint8)`
+/// and `optional(` prefix.
+pub(crate) fn unwrap_param_type<'a>(param_type: &'a str, prefix: &str) -> Option<&'a str> {
+    if param_type.starts_with(prefix) && param_type.ends_with(')') {
+        Some(&param_type[prefix.len()..param_type.len() - 1])
+    } else {
+        None
+    }
+}
+
 /// The ABI function header.
 ///
 /// Includes several hidden function parameters that contract
@@ -184,7 +214,7 @@ impl MessageSource {
     ) -> ClientResult<(String, Option<Abi>)> {
         Ok(match self {
             MessageSource::EncodingParams(params) => {
-                if params.signer.is_external() {
+                if params.signer.is_external() || params.signer.is_unsigned_multi() {
                     return Err(processing::Error::external_signer_must_not_be_used());
                 }
                 let abi = params.abi.clone();
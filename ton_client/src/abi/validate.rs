@@ -0,0 +1,346 @@
+use crate::abi::types::{unwrap_param_type, Abi, AbiContract, AbiParam};
+use crate::client::ClientContext;
+use crate::error::ClientResult;
+use sha2::Digest;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+//----------------------------------------------------------------------------------- validate_abi
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfValidateAbi {
+    /// Contract ABI
+    pub abi: Abi,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default, Debug, Clone, PartialEq)]
+pub struct AbiValidationError {
+    /// JSON path to the invalid ABI element, e.g. `$.functions[1].inputs[0].type`
+    pub path: String,
+
+    /// Problem description
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfValidateAbi {
+    /// All problems found in the ABI. Empty if the ABI is valid.
+    pub errors: Vec<AbiValidationError>,
+}
+
+const HEADERS_V2: [&str; 3] = ["time", "expire", "pubkey"];
+
+struct Validator {
+    errors: Vec<AbiValidationError>,
+}
+
+impl Validator {
+    fn report(&mut self, path: String, message: impl Into<String>) {
+        self.errors.push(AbiValidationError {
+            path,
+            message: message.into(),
+        })
+    }
+
+    /// Checks that `names` are unique and reports all duplicates found.
+    fn check_unique_names<'a>(
+        &mut self,
+        path: &str,
+        kind: &str,
+        names: impl Iterator<Item = &'a String>,
+    ) {
+        let mut found = HashMap::new();
+        for (index, name) in names.enumerate() {
+            if let Some(first) = found.insert(name, index) {
+                self.report(
+                    format!("{}[{}].name", path, index),
+                    format!("duplicate {} name `{}`, first declared at {}[{}]", kind, name, path, first),
+                );
+                found.insert(name, first);
+            }
+        }
+    }
+
+    /// Validates list of params and returns their type signatures
+    /// if all of them are valid.
+    fn check_params(&mut self, path: &str, params: &[AbiParam]) -> Option<Vec<String>> {
+        self.check_unique_names(path, "parameter", params.iter().map(|param| &param.name));
+        let mut signatures = Some(Vec::new());
+        for (index, param) in params.iter().enumerate() {
+            let signature = self.check_param(&format!("{}[{}]", path, index), param);
+            signatures = signatures.and_then(|mut vec| {
+                vec.push(signature?);
+                Some(vec)
+            });
+        }
+        signatures
+    }
+
+    fn check_param(&mut self, path: &str, param: &AbiParam) -> Option<String> {
+        if param.name.is_empty() {
+            self.report(format!("{}.name", path), "parameter name is empty");
+        }
+        let result = self.check_type(path, &param.param_type, &param.components);
+        if !param.components.is_empty() && !param.param_type.contains("tuple") {
+            self.report(
+                format!("{}.components", path),
+                format!("`components` are specified for non-tuple type `{}`", param.param_type),
+            );
+        }
+        match result {
+            Ok(signature) => signature,
+            Err(message) => {
+                self.report(format!("{}.type", path), message);
+                None
+            }
+        }
+    }
+
+    /// Checks the type string. Returns `Ok(None)` if the type itself is valid,
+    /// but its tuple components are not.
+    fn check_type(
+        &mut self,
+        path: &str,
+        param_type: &str,
+        components: &[AbiParam],
+    ) -> Result<Option<String>, String> {
+        if param_type.ends_with(']') {
+            let open = param_type
+                .rfind('[')
+                .ok_or_else(|| format!("invalid array type `{}`", param_type))?;
+            let size = &param_type[open + 1..param_type.len() - 1];
+            if !size.is_empty() && size.parse::<usize>().is_err() {
+                return Err(format!("invalid array size in type `{}`", param_type));
+            }
+            let item = self.check_type(path, &param_type[..open], components)?;
+            return Ok(item.map(|item| format!("{}[{}]", item, size)));
+        }
+        if let Some(inner) = unwrap_param_type(param_type, "optional(") {
+            let inner = self.check_type(path, inner, components)?;
+            return Ok(inner.map(|inner| format!("optional({})", inner)));
+        }
+        if let Some(inner) = unwrap_param_type(param_type, "ref(") {
+            let inner = self.check_type(path, inner, components)?;
+            return Ok(inner.map(|inner| format!("ref({})", inner)));
+        }
+        if let Some(inner) = unwrap_param_type(param_type, "map(") {
+            let comma = inner
+                .find(',')
+                .ok_or_else(|| format!("invalid map type `{}`", param_type))?;
+            let key = &inner[..comma];
+            if !key.starts_with("int") && !key.starts_with("uint") && key != "address" {
+                return Err(format!(
+                    "invalid map key type `{}`: only integers and addresses are allowed",
+                    key
+                ));
+            }
+            Self::check_simple_type(key)?;
+            let value = self.check_type(path, &inner[comma + 1..], components)?;
+            return Ok(value.map(|value| format!("map({},{})", key, value)));
+        }
+        if param_type == "tuple" {
+            if components.is_empty() {
+                return Err("`components` are required for `tuple` type".into());
+            }
+            let components = self.check_params(&format!("{}.components", path), components);
+            return Ok(components.map(|components| format!("({})", components.join(","))));
+        }
+        Self::check_simple_type(param_type)?;
+        Ok(Some(param_type.to_owned()))
+    }
+
+    fn check_simple_type(param_type: &str) -> Result<(), String> {
+        let size = |prefix: &str| {
+            param_type
+                .strip_prefix(prefix)
+                .and_then(|size| size.parse::<usize>().ok())
+        };
+        let valid = match param_type {
+            "bool" | "cell" | "address" | "bytes" | "string" | "gram" | "token" | "time"
+            | "expire" | "pubkey" => true,
+            _ => {
+                if let Some(size) = size("varuint").or_else(|| size("varint")) {
+                    size == 16 || size == 32
+                } else if let Some(size) = size("uint").or_else(|| size("int")) {
+                    size >= 1 && size <= 256
+                } else if let Some(size) = size("fixedbytes") {
+                    size >= 1 && size <= 32
+                } else {
+                    false
+                }
+            }
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(format!("unknown type `{}`", param_type))
+        }
+    }
+}
+
+fn calc_id(signature: &str) -> u32 {
+    let hash = sha2::Sha256::digest(signature.as_bytes());
+    u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]) & 0x7FFFFFFF
+}
+
+fn parse_id(id: &str) -> Option<u32> {
+    let hex = id.trim_start_matches("0x");
+    if hex.len() != id.len() {
+        u32::from_str_radix(hex, 16).ok()
+    } else {
+        id.parse().ok()
+    }
+}
+
+/// Returns `(major, minor)` ABI version declared in the contract.
+fn declared_version(abi: &AbiContract) -> (u32, u32) {
+    if let Some(version) = &abi.version {
+        let mut parts = version.split('.').map(|part| part.parse::<u32>().unwrap_or(0));
+        return (parts.next().unwrap_or(0), parts.next().unwrap_or(0));
+    }
+    if abi.obsolete_abi_version != abi.abi_version && abi.abi_version == 2 {
+        (abi.obsolete_abi_version, 0)
+    } else {
+        (abi.abi_version, 0)
+    }
+}
+
+pub(crate) fn validate_contract(abi: &AbiContract) -> Vec<AbiValidationError> {
+    let mut validator = Validator { errors: Vec::new() };
+    let (major, minor) = declared_version(abi);
+
+    if major != 1 && major != 2 {
+        validator.report("$.version".into(), format!("unsupported ABI version {}.{}", major, minor));
+    }
+    for (index, header) in abi.header.iter().enumerate() {
+        if major < 2 || !HEADERS_V2.contains(&header.as_str()) {
+            validator.report(
+                format!("$.header[{}]", index),
+                format!("header `{}` is not supported by ABI version {}.{}", header, major, minor),
+            );
+        }
+    }
+    validator.check_unique_names("$.header", "header", abi.header.iter());
+
+    let mut ids: HashMap<u32, usize> = HashMap::new();
+    validator.check_unique_names("$.functions", "function", abi.functions.iter().map(|f| &f.name));
+    for (index, function) in abi.functions.iter().enumerate() {
+        let path = format!("$.functions[{}]", index);
+        if function.name.is_empty() {
+            validator.report(format!("{}.name", path), "function name is empty");
+        }
+        let inputs = validator.check_params(&format!("{}.inputs", path), &function.inputs);
+        let outputs = validator.check_params(&format!("{}.outputs", path), &function.outputs);
+
+        let id = if let Some(id) = &function.id {
+            let parsed = parse_id(id);
+            if parsed.is_none() {
+                validator.report(format!("{}.id", path), format!("invalid function id `{}`", id));
+            }
+            parsed
+        } else {
+            inputs.zip(outputs).map(|(inputs, outputs)| {
+                let mut inputs = inputs;
+                if major == 1 {
+                    inputs.splice(0..0, abi.header.iter().cloned());
+                }
+                calc_id(&format!(
+                    "{}({})({})v{}",
+                    function.name,
+                    inputs.join(","),
+                    outputs.join(","),
+                    major
+                ))
+            })
+        };
+        if let Some(id) = id {
+            if let Some(first) = ids.insert(id, index) {
+                validator.report(
+                    path,
+                    format!(
+                        "duplicate function id 0x{:08x}, first declared at $.functions[{}]",
+                        id, first
+                    ),
+                );
+                ids.insert(id, first);
+            }
+        }
+    }
+
+    validator.check_unique_names("$.events", "event", abi.events.iter().map(|e| &e.name));
+    for (index, event) in abi.events.iter().enumerate() {
+        let path = format!("$.events[{}]", index);
+        if event.name.is_empty() {
+            validator.report(format!("{}.name", path), "event name is empty");
+        }
+        validator.check_params(&format!("{}.inputs", path), &event.inputs);
+        if let Some(id) = &event.id {
+            if parse_id(id).is_none() {
+                validator.report(format!("{}.id", path), format!("invalid event id `{}`", id));
+            }
+        }
+        if let Some(function) = abi.functions.iter().position(|f| f.name == event.name) {
+            validator.report(
+                format!("{}.name", path),
+                format!(
+                    "event name `{}` collides with function $.functions[{}]",
+                    event.name, function
+                ),
+            );
+        }
+    }
+
+    validator.check_unique_names("$.data", "data", abi.data.iter().map(|d| &d.name));
+    let mut keys = HashMap::new();
+    for (index, data) in abi.data.iter().enumerate() {
+        let path = format!("$.data[{}]", index);
+        if data.key == 0 {
+            validator.report(
+                format!("{}.key", path),
+                "data key 0 is reserved for the contract public key",
+            );
+        }
+        if let Some(first) = keys.insert(data.key, index) {
+            validator.report(
+                format!("{}.key", path),
+                format!("duplicate data key {}, first declared at $.data[{}]", data.key, first),
+            );
+            keys.insert(data.key, first);
+        }
+        validator.check_param(&path, &AbiParam::from(data));
+    }
+
+    if !abi.fields.is_empty() && (major, minor) < (2, 1) {
+        validator.report(
+            "$.fields".into(),
+            format!("`fields` are not supported by ABI version {}.{}", major, minor),
+        );
+    }
+    validator.check_params("$.fields", &abi.fields);
+
+    validator.errors
+}
+
+/// Validates contract ABI.
+///
+/// Checks ABI structure and reports all found problems at once, each with a JSON path
+/// to the invalid element: duplicate function ids and names, unknown parameter types,
+/// missing tuple `components`, invalid `data` keys, headers unsupported by the declared
+/// ABI version and name collisions between functions and events.
+#[api_function]
+pub async fn validate_abi(
+    _context: Arc<ClientContext>,
+    params: ParamsOfValidateAbi,
+) -> ClientResult<ResultOfValidateAbi> {
+    let contract = params.abi.abi_contract()?;
+    let mut errors = validate_contract(&contract);
+    if errors.is_empty() {
+        if let Err(err) = params.abi.abi() {
+            errors.push(AbiValidationError {
+                path: "$".into(),
+                message: err.message,
+            });
+        }
+    }
+    Ok(ResultOfValidateAbi { errors })
+}
@@ -23,6 +23,7 @@ use crate::abi::AbiConfig;
 use crate::boc::{BocConfig, cache::Bocs};
 use crate::crypto::CryptoConfig;
 use crate::crypto::boxes::{signing_box::SigningBox, encryption_box::EncryptionBox};
+use crate::crypto::boxes::encryption_box::stream::StreamSession;
 use crate::debot::DEngine;
 use crate::error::ClientResult;
 use crate::json_interface::interop::ResponseType;
@@ -40,6 +41,7 @@ use super::wasm_client_env::ClientEnv;
 pub struct Boxes {
     pub(crate) signing_boxes: LockfreeMap<u32, Box<dyn SigningBox + Send + Sync>>,
     pub(crate) encryption_boxes: LockfreeMap<u32, Box<dyn EncryptionBox + Send + Sync>>,
+    pub(crate) encryption_sessions: Mutex<HashMap<u32, Arc<Mutex<StreamSession>>>>,
 }
 
 pub struct NetworkContext {
@@ -15,12 +15,17 @@ use std::sync::Arc;
 
 use lockfree::map::ReadGuard;
 use serde_json::Value;
+use tokio::sync::Mutex;
+use zeroize::Zeroize;
 
 use crate::client::ClientContext;
 use crate::crypto::Error;
-use crate::error::ClientResult;
+use crate::encoding::base64_decode;
+use crate::error::{ClientError, ClientResult};
+use stream::{StreamDecryptor, StreamEncryptor, StreamSession};
 
 pub(crate) mod aes;
+pub(crate) mod stream;
 
 #[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
 pub struct EncryptionBoxHandle(pub u32);
@@ -181,6 +186,298 @@ pub async fn encryption_box_decrypt(
     })
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct EncryptionSessionHandle(pub u32);
+
+impl From<u32> for EncryptionSessionHandle {
+    fn from(handle: u32) -> Self {
+        Self(handle)
+    }
+}
+
+async fn register_encryption_session(
+    context: &Arc<ClientContext>,
+    session: StreamSession,
+) -> EncryptionSessionHandle {
+    let id = context.get_next_id();
+    context.boxes.encryption_sessions
+        .lock()
+        .await
+        .insert(id, Arc::new(Mutex::new(session)));
+    EncryptionSessionHandle(id)
+}
+
+async fn get_encryption_session(
+    context: &Arc<ClientContext>,
+    handle: &EncryptionSessionHandle,
+) -> ClientResult<Arc<Mutex<StreamSession>>> {
+    context.boxes.encryption_sessions
+        .lock()
+        .await
+        .get(&handle.0)
+        .cloned()
+        .ok_or(invalid_session_handle(handle))
+}
+
+async fn take_encryption_session(
+    context: &Arc<ClientContext>,
+    handle: &EncryptionSessionHandle,
+) -> ClientResult<Arc<Mutex<StreamSession>>> {
+    context.boxes.encryption_sessions
+        .lock()
+        .await
+        .remove(&handle.0)
+        .ok_or(invalid_session_handle(handle))
+}
+
+fn invalid_session_handle(handle: &EncryptionSessionHandle) -> ClientError {
+    crate::client::Error::invalid_handle(handle.0, "encryption session")
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ParamsOfEncryptionBoxEncryptInit {
+    /// Encryption box handle
+    pub encryption_box: EncryptionBoxHandle,
+    /// Size of plaintext chunks in bytes. Default is 65536, maximum is 16777216.
+    pub chunk_size: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ResultOfEncryptionBoxEncryptInit {
+    /// Encryption session handle
+    pub session: EncryptionSessionHandle,
+    /// Header of the encrypted container, encoded in Base64
+    pub data: String,
+}
+
+/// Starts streaming encryption using given encryption box
+///
+/// Data is encrypted with a random content key in chunks of the same size, each chunk
+/// is authenticated (STREAM construction over NaCl secret box). The content key is encrypted
+/// with the encryption box and stored in the container header, so an application
+/// implemented encryption box is called only once per session.
+///
+/// Encrypted container is the concatenation of `data` returned by `encryption_box_encrypt_init`,
+/// all `encryption_box_encrypt_update` calls and `encryption_box_encrypt_finish`.
+/// It can be decrypted with `encryption_box_decrypt_init`.
+#[api_function]
+pub async fn encryption_box_encrypt_init(
+    context: Arc<ClientContext>,
+    params: ParamsOfEncryptionBoxEncryptInit,
+) -> ClientResult<ResultOfEncryptionBoxEncryptInit> {
+    let mut content_key = stream::generate_content_key();
+    let mut encoded_key = base64::encode(&content_key);
+    let wrapped_key = get_registered_encryption_box(&context, &params.encryption_box)?
+        .val()
+        .encrypt(&encoded_key)
+        .await;
+    encoded_key.zeroize();
+    let encryptor = StreamEncryptor::new(
+        &content_key,
+        &base64_decode(&wrapped_key?)?,
+        params.chunk_size.unwrap_or(stream::DEFAULT_CHUNK_SIZE),
+    );
+    content_key.zeroize();
+    let (encryptor, header) = encryptor?;
+
+    Ok(ResultOfEncryptionBoxEncryptInit {
+        session: register_encryption_session(&context, StreamSession::Encrypt(encryptor)).await,
+        data: base64::encode(&header),
+    })
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ParamsOfEncryptionBoxEncryptUpdate {
+    /// Encryption session handle
+    pub session: EncryptionSessionHandle,
+    /// Next part of data to be encrypted, encoded in Base64
+    pub data: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ResultOfEncryptionBoxEncryptUpdate {
+    /// Encrypted chunks, encoded in Base64. Can be empty if there is not enough data for a chunk
+    pub data: String,
+}
+
+/// Encrypts next part of data in the streaming encryption session
+#[api_function]
+pub async fn encryption_box_encrypt_update(
+    context: Arc<ClientContext>,
+    params: ParamsOfEncryptionBoxEncryptUpdate,
+) -> ClientResult<ResultOfEncryptionBoxEncryptUpdate> {
+    let session = get_encryption_session(&context, &params.session).await?;
+    let mut session = session.lock().await;
+    match &mut *session {
+        StreamSession::Encrypt(encryptor) => Ok(ResultOfEncryptionBoxEncryptUpdate {
+            data: base64::encode(&encryptor.update(&base64_decode(&params.data)?)?),
+        }),
+        _ => Err(invalid_session_handle(&params.session)),
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ParamsOfEncryptionBoxEncryptFinish {
+    /// Encryption session handle
+    pub session: EncryptionSessionHandle,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ResultOfEncryptionBoxEncryptFinish {
+    /// Final encrypted chunk, encoded in Base64
+    pub data: String,
+}
+
+/// Finishes streaming encryption session
+///
+/// Encrypts the rest of the data as the final chunk and removes the session.
+#[api_function]
+pub async fn encryption_box_encrypt_finish(
+    context: Arc<ClientContext>,
+    params: ParamsOfEncryptionBoxEncryptFinish,
+) -> ClientResult<ResultOfEncryptionBoxEncryptFinish> {
+    let session = get_encryption_session(&context, &params.session).await?;
+    let mut session = session.lock().await;
+    let encryptor = match &mut *session {
+        StreamSession::Encrypt(encryptor) => encryptor,
+        _ => return Err(invalid_session_handle(&params.session)),
+    };
+    // Session is removed only after its kind is checked
+    take_encryption_session(&context, &params.session).await?;
+    Ok(ResultOfEncryptionBoxEncryptFinish {
+        data: base64::encode(&encryptor.finish()?),
+    })
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ParamsOfEncryptionBoxDecryptInit {
+    /// Encryption box handle
+    pub encryption_box: EncryptionBoxHandle,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ResultOfEncryptionBoxDecryptInit {
+    /// Decryption session handle
+    pub session: EncryptionSessionHandle,
+}
+
+/// Starts streaming decryption of the container created by `encryption_box_encrypt_init`
+///
+/// The content key is decrypted with the encryption box when the container header
+/// is passed to `encryption_box_decrypt_update`.
+#[api_function]
+pub async fn encryption_box_decrypt_init(
+    context: Arc<ClientContext>,
+    params: ParamsOfEncryptionBoxDecryptInit,
+) -> ClientResult<ResultOfEncryptionBoxDecryptInit> {
+    get_registered_encryption_box(&context, &params.encryption_box)?;
+    let session = StreamSession::Decrypt {
+        encryption_box: params.encryption_box,
+        decryptor: StreamDecryptor::new(),
+    };
+    Ok(ResultOfEncryptionBoxDecryptInit {
+        session: register_encryption_session(&context, session).await,
+    })
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ParamsOfEncryptionBoxDecryptUpdate {
+    /// Decryption session handle
+    pub session: EncryptionSessionHandle,
+    /// Next part of the encrypted container, encoded in Base64
+    pub data: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ResultOfEncryptionBoxDecryptUpdate {
+    /// Decrypted data, encoded in Base64. Can be empty if there is not enough data for a chunk
+    pub data: String,
+}
+
+/// Decrypts next part of the encrypted container in the streaming decryption session
+///
+/// Decrypted data is returned only after its chunk is authenticated.
+#[api_function]
+pub async fn encryption_box_decrypt_update(
+    context: Arc<ClientContext>,
+    params: ParamsOfEncryptionBoxDecryptUpdate,
+) -> ClientResult<ResultOfEncryptionBoxDecryptUpdate> {
+    let session = get_encryption_session(&context, &params.session).await?;
+    let mut session = session.lock().await;
+    let (encryption_box, decryptor) = match &mut *session {
+        StreamSession::Decrypt { encryption_box, decryptor } => (encryption_box, decryptor),
+        _ => return Err(invalid_session_handle(&params.session)),
+    };
+    if let Some(header) = decryptor.push(&base64_decode(&params.data)?)? {
+        let mut content_key = base64_decode(
+            &get_registered_encryption_box(&context, encryption_box)?
+                .val()
+                .decrypt(&base64::encode(&header.wrapped_key))
+                .await?
+        )?;
+        // Block ciphers can pad the decrypted key
+        let started = if content_key.len() >= stream::CONTENT_KEY_SIZE {
+            decryptor.start(header, &content_key[..stream::CONTENT_KEY_SIZE])
+        } else {
+            Err(Error::decrypt_data_error("invalid content key in the container header"))
+        };
+        content_key.zeroize();
+        started?;
+    }
+    Ok(ResultOfEncryptionBoxDecryptUpdate {
+        data: base64::encode(&decryptor.update()?),
+    })
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ParamsOfEncryptionBoxDecryptFinish {
+    /// Decryption session handle
+    pub session: EncryptionSessionHandle,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ResultOfEncryptionBoxDecryptFinish {
+    /// Decrypted final chunk, encoded in Base64
+    pub data: String,
+}
+
+/// Finishes streaming decryption session
+///
+/// Decrypts the final chunk and removes the session. Fails if the container is truncated.
+#[api_function]
+pub async fn encryption_box_decrypt_finish(
+    context: Arc<ClientContext>,
+    params: ParamsOfEncryptionBoxDecryptFinish,
+) -> ClientResult<ResultOfEncryptionBoxDecryptFinish> {
+    let session = get_encryption_session(&context, &params.session).await?;
+    let mut session = session.lock().await;
+    let decryptor = match &mut *session {
+        StreamSession::Decrypt { decryptor, .. } => decryptor,
+        _ => return Err(invalid_session_handle(&params.session)),
+    };
+    // Session is removed only after its kind is checked
+    take_encryption_session(&context, &params.session).await?;
+    Ok(ResultOfEncryptionBoxDecryptFinish {
+        data: base64::encode(&decryptor.finish()?),
+    })
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ParamsOfRemoveEncryptionSession {
+    /// Encryption or decryption session handle
+    pub session: EncryptionSessionHandle,
+}
+
+/// Removes streaming encryption or decryption session without finishing it
+#[api_function]
+pub async fn remove_encryption_session(
+    context: Arc<ClientContext>,
+    params: ParamsOfRemoveEncryptionSession,
+) -> ClientResult<()> {
+    take_encryption_session(&context, &params.session).await?;
+    Ok(())
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, ApiType)]
 pub enum CipherMode {
     CBC,
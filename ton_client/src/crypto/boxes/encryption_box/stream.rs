@@ -0,0 +1,333 @@
+/*
+* Copyright 2018-2021 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+//! Chunked encryption container used by the streaming encryption box functions.
+//!
+//! Data is encrypted with a random content key following the STREAM construction:
+//! it is split into chunks of the same size and each chunk is sealed with
+//! NaCl secret box (XSalsa20-Poly1305) under the nonce
+//! `nonce_prefix (19 bytes) || chunk_index (4 bytes, BE) || last_chunk_flag (1 byte)`.
+//! So reordered, duplicated or dropped chunks and truncated streams fail authentication.
+//!
+//! The content key is wrapped with the encryption box and stored in the container header:
+//!
+//! | Field          | Size     |
+//! |----------------|----------|
+//! | magic `TONS`   | 4        |
+//! | version        | 1        |
+//! | chunk size     | 4 (BE)   |
+//! | nonce prefix   | 19       |
+//! | wrapped key    | 2 (BE) + wrapped key size |
+//!
+//! Chunks are sealed with the key `HMAC-SHA256(content_key, header)`, so a modified header
+//! fails authentication of the first chunk.
+
+use hmac::{Hmac, Mac, NewMac};
+use rand::RngCore;
+use sha2::Sha256;
+use zeroize::Zeroize;
+
+use super::EncryptionBoxHandle;
+use crate::crypto::Error;
+use crate::error::ClientResult;
+
+const MAGIC: &[u8; 4] = b"TONS";
+const VERSION: u8 = 1;
+const NONCE_PREFIX_SIZE: usize = 19;
+const HEADER_FIXED_SIZE: usize = 4 + 1 + 4 + NONCE_PREFIX_SIZE + 2;
+const TAG_SIZE: usize = 16;
+
+pub(crate) const CONTENT_KEY_SIZE: usize = 32;
+pub(crate) const DEFAULT_CHUNK_SIZE: u32 = 64 * 1024;
+pub(crate) const MAX_CHUNK_SIZE: u32 = 16 * 1024 * 1024;
+
+struct StreamCipher {
+    key: [u8; 32],
+    nonce_prefix: [u8; NONCE_PREFIX_SIZE],
+    chunk_index: u32,
+    finished: bool,
+}
+
+impl StreamCipher {
+    fn new(content_key: &[u8], nonce_prefix: [u8; NONCE_PREFIX_SIZE], header: &[u8]) -> Self {
+        let mut hmac = Hmac::<Sha256>::new_from_slice(content_key).unwrap();
+        hmac.update(header);
+        let mut key = [0u8; 32];
+        key.copy_from_slice(&hmac.finalize().into_bytes());
+        Self {
+            key,
+            nonce_prefix,
+            chunk_index: 0,
+            finished: false,
+        }
+    }
+
+    fn next_nonce(&mut self, last: bool) -> ClientResult<[u8; 24]> {
+        if self.finished {
+            return Err(Error::encrypt_data_error("stream is already finished"));
+        }
+        let mut nonce = [0u8; 24];
+        nonce[..NONCE_PREFIX_SIZE].copy_from_slice(&self.nonce_prefix);
+        nonce[NONCE_PREFIX_SIZE..23].copy_from_slice(&self.chunk_index.to_be_bytes());
+        nonce[23] = last as u8;
+        self.chunk_index = self
+            .chunk_index
+            .checked_add(1)
+            .ok_or_else(|| Error::encrypt_data_error("too many chunks in the stream"))?;
+        self.finished = last;
+        Ok(nonce)
+    }
+
+    fn seal(&mut self, chunk: &[u8], last: bool) -> ClientResult<Vec<u8>> {
+        let nonce = self.next_nonce(last)?;
+        let mut input = vec![0u8; 32 + chunk.len()];
+        input[32..].copy_from_slice(chunk);
+        let mut output = vec![0u8; input.len()];
+        sodalite::secretbox(&mut output, &input, &nonce, &self.key)
+            .map_err(|_| Error::encrypt_data_error("secret box failed"))?;
+        input.zeroize();
+        output.drain(..TAG_SIZE);
+        Ok(output)
+    }
+
+    fn open(&mut self, chunk: &[u8], last: bool) -> ClientResult<Vec<u8>> {
+        let nonce = self.next_nonce(last)?;
+        let mut input = vec![0u8; TAG_SIZE + chunk.len()];
+        input[TAG_SIZE..].copy_from_slice(chunk);
+        let mut output = vec![0u8; input.len()];
+        sodalite::secretbox_open(&mut output, &input, &nonce, &self.key).map_err(|_| {
+            Error::decrypt_data_error(format!(
+                "chunk {} authentication failed",
+                self.chunk_index - 1
+            ))
+        })?;
+        output.drain(..32);
+        Ok(output)
+    }
+}
+
+impl Drop for StreamCipher {
+    fn drop(&mut self) {
+        self.key.zeroize();
+    }
+}
+
+fn check_chunk_size(chunk_size: u32) -> Result<usize, String> {
+    if chunk_size == 0 || chunk_size > MAX_CHUNK_SIZE {
+        return Err(format!(
+            "invalid chunk size {}, expected 1..{}",
+            chunk_size, MAX_CHUNK_SIZE
+        ));
+    }
+    Ok(chunk_size as usize)
+}
+
+pub(crate) fn generate_content_key() -> Vec<u8> {
+    let mut key = vec![0u8; CONTENT_KEY_SIZE];
+    rand::thread_rng().fill_bytes(&mut key);
+    key
+}
+
+pub(crate) struct StreamEncryptor {
+    cipher: StreamCipher,
+    chunk_size: usize,
+    buffer: Vec<u8>,
+}
+
+impl StreamEncryptor {
+    /// Creates encryptor and returns it with the container header
+    pub fn new(
+        content_key: &[u8],
+        wrapped_key: &[u8],
+        chunk_size: u32,
+    ) -> ClientResult<(Self, Vec<u8>)> {
+        let chunk_size =
+            check_chunk_size(chunk_size).map_err(Error::encrypt_data_error)?;
+        if wrapped_key.len() > u16::MAX as usize {
+            return Err(Error::encrypt_data_error(format!(
+                "wrapped content key is too long: {} bytes",
+                wrapped_key.len()
+            )));
+        }
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_SIZE];
+        rand::thread_rng().fill_bytes(&mut nonce_prefix);
+
+        let mut header = Vec::with_capacity(HEADER_FIXED_SIZE + wrapped_key.len());
+        header.extend_from_slice(MAGIC);
+        header.push(VERSION);
+        header.extend_from_slice(&(chunk_size as u32).to_be_bytes());
+        header.extend_from_slice(&nonce_prefix);
+        header.extend_from_slice(&(wrapped_key.len() as u16).to_be_bytes());
+        header.extend_from_slice(wrapped_key);
+
+        let encryptor = Self {
+            cipher: StreamCipher::new(content_key, nonce_prefix, &header),
+            chunk_size,
+            buffer: Vec::new(),
+        };
+        Ok((encryptor, header))
+    }
+
+    /// Encrypts all complete chunks except the last one, which can be the final chunk
+    pub fn update(&mut self, data: &[u8]) -> ClientResult<Vec<u8>> {
+        self.buffer.extend_from_slice(data);
+        let mut output = Vec::new();
+        let mut offset = 0;
+        while self.buffer.len() - offset > self.chunk_size {
+            output.extend(
+                self.cipher
+                    .seal(&self.buffer[offset..offset + self.chunk_size], false)?,
+            );
+            offset += self.chunk_size;
+        }
+        self.buffer.drain(..offset);
+        Ok(output)
+    }
+
+    /// Encrypts the rest of the data as the final chunk
+    pub fn finish(&mut self) -> ClientResult<Vec<u8>> {
+        let mut chunk = std::mem::take(&mut self.buffer);
+        let result = self.cipher.seal(&chunk, true);
+        chunk.zeroize();
+        result
+    }
+}
+
+impl Drop for StreamEncryptor {
+    fn drop(&mut self) {
+        self.buffer.zeroize();
+    }
+}
+
+/// Container header parsed from the beginning of the encrypted stream
+pub(crate) struct StreamHeader {
+    pub chunk_size: u32,
+    pub nonce_prefix: [u8; NONCE_PREFIX_SIZE],
+    pub wrapped_key: Vec<u8>,
+    pub size: usize,
+}
+
+impl StreamHeader {
+    /// Parses header from the data. Returns `None` if there is not enough data yet
+    pub fn parse(data: &[u8]) -> ClientResult<Option<Self>> {
+        if data.len() < HEADER_FIXED_SIZE {
+            if !MAGIC.starts_with(&data[..data.len().min(MAGIC.len())]) {
+                return Err(Error::decrypt_data_error("data is not an encrypted stream"));
+            }
+            return Ok(None);
+        }
+        if &data[..4] != MAGIC {
+            return Err(Error::decrypt_data_error("data is not an encrypted stream"));
+        }
+        if data[4] != VERSION {
+            return Err(Error::decrypt_data_error(format!(
+                "unsupported encrypted stream version {}",
+                data[4]
+            )));
+        }
+        let mut chunk_size = [0u8; 4];
+        chunk_size.copy_from_slice(&data[5..9]);
+        let chunk_size = u32::from_be_bytes(chunk_size);
+        check_chunk_size(chunk_size).map_err(Error::decrypt_data_error)?;
+        let mut nonce_prefix = [0u8; NONCE_PREFIX_SIZE];
+        nonce_prefix.copy_from_slice(&data[9..9 + NONCE_PREFIX_SIZE]);
+        let key_size = u16::from_be_bytes([data[HEADER_FIXED_SIZE - 2], data[HEADER_FIXED_SIZE - 1]]);
+        let size = HEADER_FIXED_SIZE + key_size as usize;
+        if data.len() < size {
+            return Ok(None);
+        }
+        Ok(Some(Self {
+            chunk_size,
+            nonce_prefix,
+            wrapped_key: data[HEADER_FIXED_SIZE..size].to_vec(),
+            size,
+        }))
+    }
+}
+
+pub(crate) struct StreamDecryptor {
+    cipher: Option<(StreamCipher, usize)>,
+    buffer: Vec<u8>,
+}
+
+impl StreamDecryptor {
+    pub fn new() -> Self {
+        Self {
+            cipher: None,
+            buffer: Vec::new(),
+        }
+    }
+
+    /// Appends data to the buffer. Returns the header if the decryptor
+    /// is not started yet and the header is complete
+    pub fn push(&mut self, data: &[u8]) -> ClientResult<Option<StreamHeader>> {
+        self.buffer.extend_from_slice(data);
+        if self.cipher.is_some() {
+            return Ok(None);
+        }
+        StreamHeader::parse(&self.buffer)
+    }
+
+    /// Starts decryption with the content key unwrapped from the header
+    pub fn start(&mut self, header: StreamHeader, content_key: &[u8]) -> ClientResult<()> {
+        let chunk_size =
+            check_chunk_size(header.chunk_size).map_err(Error::decrypt_data_error)?;
+        let cipher = StreamCipher::new(
+            content_key,
+            header.nonce_prefix,
+            &self.buffer[..header.size],
+        );
+        self.buffer.drain(..header.size);
+        self.cipher = Some((cipher, chunk_size + TAG_SIZE));
+        Ok(())
+    }
+
+    /// Decrypts all complete chunks except the last one, which can be the final chunk
+    pub fn update(&mut self) -> ClientResult<Vec<u8>> {
+        let (cipher, sealed_size) = match self.cipher.as_mut() {
+            Some(cipher) => cipher,
+            None => return Ok(Vec::new()),
+        };
+        let mut output = Vec::new();
+        let mut offset = 0;
+        while self.buffer.len() - offset > *sealed_size {
+            output.extend(cipher.open(&self.buffer[offset..offset + *sealed_size], false)?);
+            offset += *sealed_size;
+        }
+        self.buffer.drain(..offset);
+        Ok(output)
+    }
+
+    /// Decrypts the final chunk
+    pub fn finish(&mut self) -> ClientResult<Vec<u8>> {
+        let (cipher, _) = self.cipher.as_mut().ok_or_else(|| {
+            Error::decrypt_data_error("encrypted stream is truncated: header is incomplete")
+        })?;
+        if self.buffer.len() < TAG_SIZE {
+            return Err(Error::decrypt_data_error(
+                "encrypted stream is truncated: final chunk is missing",
+            ));
+        }
+        let chunk = std::mem::take(&mut self.buffer);
+        cipher.open(&chunk, true)
+    }
+}
+
+/// Streaming encryption session registered in the client context
+pub(crate) enum StreamSession {
+    Encrypt(StreamEncryptor),
+    Decrypt {
+        encryption_box: EncryptionBoxHandle,
+        decryptor: StreamDecryptor,
+    },
+}
@@ -14,9 +14,9 @@
 use std::sync::Arc;
 
 use crate::client::ClientContext;
-use crate::crypto::Error;
-use crate::crypto::KeyPair;
-use crate::error::ClientResult;
+use crate::crypto::mnemonic::mnemonics;
+use crate::crypto::{default_hdkey_compliant, Error, KeyPair};
+use crate::error::{ClientError, ClientResult};
 
 #[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
 pub struct SigningBoxHandle(pub u32);
@@ -27,12 +27,44 @@ impl From<u32> for SigningBoxHandle {
     }
 }
 
+/// Description of the message which data is being signed.
+///
+/// Passed to the signing box along with the data to sign, so it can show
+/// the user what is actually signed and reject suspicious requests.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct SigningBoxContext {
+    /// Destination address of the message.
+    pub address: Option<String>,
+    /// Name of the called function.
+    pub function_name: Option<String>,
+    /// Function input parameters.
+    pub input: Option<serde_json::Value>,
+}
+
 #[async_trait::async_trait]
 pub trait SigningBox {
     /// Get public key of key pair
     async fn get_public_key(&self) -> ClientResult<Vec<u8>>;
     /// Sign data with key pair
     async fn sign(&self, unsigned: &[u8]) -> ClientResult<Vec<u8>>;
+    /// Sign several data items with key pair. Signatures are returned in the same order.
+    async fn sign_batch(&self, unsigned: &[Vec<u8>]) -> ClientResult<Vec<Vec<u8>>> {
+        let mut signatures = Vec::with_capacity(unsigned.len());
+        for data in unsigned {
+            signatures.push(self.sign(data).await?);
+        }
+        Ok(signatures)
+    }
+    /// Sign message data with key pair. `context` describes the message being signed.
+    ///
+    /// Returns `SigningBoxRejected` error if the signing is rejected.
+    async fn sign_with_context(
+        &self,
+        unsigned: &[u8],
+        _context: &SigningBoxContext,
+    ) -> ClientResult<Vec<u8>> {
+        self.sign(unsigned).await
+    }
 }
 
 pub(crate) struct KeysSigningBox {
@@ -83,6 +115,122 @@ pub struct RegisteredSigningBox {
     pub handle: SigningBoxHandle,
 }
 
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfGetSigningBoxFromMnemonic {
+    /// Seed phrase
+    pub phrase: String,
+    /// Dictionary identifier
+    pub dictionary: Option<u8>,
+    /// Word count
+    pub word_count: Option<u8>,
+    /// Derivation path. Default is `CryptoConfig.hdkey_derivation_path`.
+    pub path: Option<String>,
+}
+
+/// Creates a signing box with the key pair derived from the seed phrase.
+///
+/// The key pair is derived the same way as in `mnemonic_derive_sign_keys`,
+/// but the secret key never leaves the SDK.
+#[api_function]
+pub async fn get_signing_box_from_mnemonic(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfGetSigningBoxFromMnemonic,
+) -> ClientResult<RegisteredSigningBox> {
+    let path = params
+        .path
+        .unwrap_or(context.config.crypto.hdkey_derivation_path.clone());
+    let key_pair = mnemonics(&context.config.crypto, params.dictionary, params.word_count)?
+        .master_key_from_phrase(&params.phrase)?
+        .derive_path(&path, default_hdkey_compliant())?
+        .ed25519_keypair()?;
+
+    register_signing_box(context, KeysSigningBox::new(key_pair)).await
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfDeriveSigningBoxes {
+    /// Seed phrase
+    pub phrase: String,
+    /// Dictionary identifier
+    pub dictionary: Option<u8>,
+    /// Word count
+    pub word_count: Option<u8>,
+    /// Derivation path of the parent key. Default is `CryptoConfig.hdkey_derivation_path`
+    /// without the last index, for instance `m/44'/396'/0'/0`.
+    pub path: Option<String>,
+    /// First child index. Default is 0.
+    pub first_index: Option<u32>,
+    /// Number of signing boxes to create
+    pub count: u32,
+    /// Derive hardened child keys. Default is `false`.
+    pub hardened: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct DerivedSigningBox {
+    /// Full derivation path of the key pair
+    pub path: String,
+    /// Public key of the signing box. Encoded with `hex`.
+    pub pubkey: String,
+    /// Handle of the signing box
+    pub handle: SigningBoxHandle,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfDeriveSigningBoxes {
+    /// Signing boxes in the order of child indices
+    pub signing_boxes: Vec<DerivedSigningBox>,
+}
+
+/// Creates signing boxes for a range of child indices derived from the seed phrase.
+///
+/// The parent key is derived once, then each signing box gets the key pair of the child
+/// `path/index` for `index` in `first_index..first_index + count`. Secret keys never leave the SDK.
+#[api_function]
+pub async fn derive_signing_boxes(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfDeriveSigningBoxes,
+) -> ClientResult<ResultOfDeriveSigningBoxes> {
+    let path = params.path.unwrap_or_else(|| {
+        let default_path = &context.config.crypto.hdkey_derivation_path;
+        match default_path.rfind('/') {
+            Some(pos) => default_path[..pos].to_string(),
+            None => default_path.clone(),
+        }
+    });
+    let first_index = params.first_index.unwrap_or(0);
+    let hardened = params.hardened.unwrap_or(false);
+    let last_index = first_index
+        .checked_add(params.count)
+        .filter(|last| *last <= 0x80000000)
+        .ok_or_else(|| {
+            Error::bip32_invalid_derive_path(format!(
+                "{}/{} (count {})",
+                path, first_index, params.count
+            ))
+        })?;
+
+    let parent = mnemonics(&context.config.crypto, params.dictionary, params.word_count)?
+        .master_key_from_phrase(&params.phrase)?
+        .derive_path(&path, default_hdkey_compliant())?;
+
+    let mut signing_boxes = Vec::new();
+    for index in first_index..last_index {
+        let key_pair = parent
+            .derive(index, hardened, default_hdkey_compliant())?
+            .ed25519_keypair()?;
+        let pubkey = hex::encode(key_pair.public.as_bytes());
+        let registered = register_signing_box(context.clone(), KeysSigningBox::new(key_pair)).await?;
+        signing_boxes.push(DerivedSigningBox {
+            path: format!("{}/{}{}", path, index, if hardened { "'" } else { "" }),
+            pubkey,
+            handle: registered.handle,
+        });
+    }
+
+    Ok(ResultOfDeriveSigningBoxes { signing_boxes })
+}
+
 /// Registers an application implemented signing box.
 pub async fn register_signing_box(
     context: std::sync::Arc<ClientContext>,
@@ -152,6 +300,145 @@ pub async fn signing_box_sign(
     })
 }
 
+pub(crate) async fn signing_box_sign_with_context(
+    context: Arc<ClientContext>,
+    handle: &SigningBoxHandle,
+    unsigned: &[u8],
+    signing_context: &SigningBoxContext,
+) -> ClientResult<Vec<u8>> {
+    let signing_box = context.boxes.signing_boxes
+        .get(&handle.0)
+        .ok_or(Error::signing_box_not_registered(handle.0))?;
+
+    signing_box.1.sign_with_context(unsigned, signing_context).await
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct CollectedSignature {
+    /// Signer's public key. Encoded with `hex`.
+    pub public_key: String,
+    /// Data signature. Encoded with `hex`.
+    pub signature: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct SigningBoxFailure {
+    /// Signing Box handle.
+    pub handle: SigningBoxHandle,
+    /// Signing error.
+    pub error: ClientError,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ParamsOfCollectSignatures {
+    /// Unsigned user data. Must be encoded with `base64`.
+    pub unsigned: String,
+    /// Signing boxes to request signatures from.
+    pub signing_boxes: Vec<SigningBoxHandle>,
+    /// Signatures collected before. Used to resume the collection:
+    /// signing boxes with public keys already present here are not asked again.
+    pub signatures: Option<Vec<CollectedSignature>>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ResultOfCollectSignatures {
+    /// All collected signatures including the ones passed in params.
+    pub signatures: Vec<CollectedSignature>,
+    /// Signing boxes that failed to sign the data.
+    pub failed: Vec<SigningBoxFailure>,
+}
+
+/// Collects signatures of the same data from several signing boxes.
+///
+/// Failure of one signing box does not stop the collection. Failed signing boxes are
+/// reported in the result, so the collection can be resumed later by passing the
+/// collected signatures back.
+#[api_function]
+pub async fn collect_signatures(
+    context: Arc<ClientContext>,
+    params: ParamsOfCollectSignatures,
+) -> ClientResult<ResultOfCollectSignatures> {
+    let unsigned = crate::encoding::base64_decode(&params.unsigned)?;
+    collect_signatures_with_context(
+        context,
+        &unsigned,
+        &params.signing_boxes,
+        params.signatures.unwrap_or_default(),
+        None,
+    ).await
+}
+
+pub(crate) async fn collect_signatures_with_context(
+    context: Arc<ClientContext>,
+    unsigned: &[u8],
+    signing_boxes: &[SigningBoxHandle],
+    mut signatures: Vec<CollectedSignature>,
+    signing_context: Option<&SigningBoxContext>,
+) -> ClientResult<ResultOfCollectSignatures> {
+    let mut failed = Vec::new();
+    for handle in signing_boxes {
+        let signing_box = context.boxes.signing_boxes
+            .get(&handle.0)
+            .ok_or(Error::signing_box_not_registered(handle.0))?;
+        let public_key = hex::encode(signing_box.1.get_public_key().await?);
+        if signatures.iter().any(|collected| collected.public_key == public_key) {
+            continue;
+        }
+        let signature = match signing_context {
+            Some(signing_context) => signing_box.1.sign_with_context(unsigned, signing_context).await,
+            None => signing_box.1.sign(unsigned).await,
+        };
+        match signature {
+            Ok(signature) => signatures.push(CollectedSignature {
+                public_key,
+                signature: hex::encode(&signature),
+            }),
+            Err(error) => failed.push(SigningBoxFailure { handle: handle.clone(), error }),
+        }
+    }
+
+    Ok(ResultOfCollectSignatures { signatures, failed })
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ParamsOfSigningBoxSignBatch {
+    /// Signing Box handle.
+    pub signing_box: SigningBoxHandle,
+    /// Unsigned user data items. Must be encoded with `base64`.
+    pub unsigned: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ResultOfSigningBoxSignBatch {
+    /// Data signatures in the order of `unsigned` items. Encoded with `hex`.
+    pub signatures: Vec<String>,
+}
+
+/// Returns signatures of several user data items.
+///
+/// All the items are passed to the signing box at once, so an application
+/// implemented signing box is called only once.
+#[api_function]
+pub async fn signing_box_sign_batch(
+    context: Arc<ClientContext>,
+    params: ParamsOfSigningBoxSignBatch,
+) -> ClientResult<ResultOfSigningBoxSignBatch> {
+    let signing_box = context.boxes.signing_boxes
+        .get(&params.signing_box.0)
+        .ok_or(Error::signing_box_not_registered(params.signing_box.0))?;
+
+    let unsigned = params.unsigned
+        .iter()
+        .map(|data| crate::encoding::base64_decode(data))
+        .collect::<ClientResult<Vec<Vec<u8>>>>()?;
+
+    let signatures = signing_box.1.sign_batch(&unsigned).await?;
+
+    Ok(ResultOfSigningBoxSignBatch {
+        signatures: signatures.iter().map(|signature| hex::encode(signature)).collect()
+    })
+}
+
 /// Removes signing box from SDK.
 #[api_function]
 pub fn remove_signing_box(
@@ -32,6 +32,13 @@ pub enum ErrorCode {
     EncryptDataError = 127,
     DecryptDataError = 128,
     IvRequired = 129,
+    KeystoreInvalidPassword = 130,
+    InvalidKeystore = 131,
+    SecretSharingFailed = 132,
+    InvalidShare = 133,
+    Secp256k1Failed = 134,
+    SigningBoxRejected = 135,
+    SigningBoxesFailed = 136,
 }
 
 pub struct Error;
@@ -230,4 +237,61 @@ impl Error {
             format!("initialization vector is required for {:?} cipher mode", mode),
         )
     }
+
+    pub fn keystore_invalid_password() -> ClientError {
+        error(
+            ErrorCode::KeystoreInvalidPassword,
+            "Invalid keystore password or keystore is corrupted".into(),
+        )
+    }
+
+    pub fn invalid_keystore(err: impl Display) -> ClientError {
+        error(
+            ErrorCode::InvalidKeystore,
+            format!("Invalid keystore: {}", err),
+        )
+    }
+
+    pub fn secret_sharing_failed(err: impl Display) -> ClientError {
+        error(
+            ErrorCode::SecretSharingFailed,
+            format!("Secret sharing failed: {}", err),
+        )
+    }
+
+    pub fn invalid_share(index: usize, err: impl Display) -> ClientError {
+        error(
+            ErrorCode::InvalidShare,
+            format!("Invalid share #{}: {}", index, err),
+        )
+    }
+
+    pub fn secp256k1_failed(err: impl Display) -> ClientError {
+        error(
+            ErrorCode::Secp256k1Failed,
+            format!("secp256k1 operation failed: {}", err),
+        )
+    }
+
+    pub fn signing_box_rejected(reason: impl Display) -> ClientError {
+        let mut error = error(
+            ErrorCode::SigningBoxRejected,
+            format!("Signing box rejected signing: {}", reason),
+        );
+        error.data["reason"] = reason.to_string().into();
+        error
+    }
+
+    pub fn signing_boxes_failed(failed: &[super::SigningBoxFailure]) -> ClientError {
+        let messages: Vec<String> = failed
+            .iter()
+            .map(|failure| format!("box {}: {}", failure.handle.0, failure.error.message))
+            .collect();
+        let mut error = error(
+            ErrorCode::SigningBoxesFailed,
+            format!("Signing boxes failed: {}", messages.join("; ")),
+        );
+        error.data["failed"] = serde_json::to_value(failed).unwrap_or_default();
+        error
+    }
 }
@@ -55,3 +55,18 @@ pub fn sha512(
         hash: hex::encode(hasher.finalize().to_vec()),
     })
 }
+
+/// Calculates Keccak-256 hash of the specified data.
+///
+/// This is the original Keccak padding used by Ethereum, not the standardized SHA3-256.
+#[api_function]
+pub fn keccak256(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfHash,
+) -> ClientResult<ResultOfHash> {
+    let mut hasher = sha3::Keccak256::new();
+    hasher.update(base64_decode(&params.data)?);
+    Ok(ResultOfHash {
+        hash: hex::encode(hasher.finalize().to_vec()),
+    })
+}
@@ -14,7 +14,8 @@
 use crate::client::ClientContext;
 use crate::crypto;
 use crate::crypto::internal::{key256, key512, sha256, Key256, Key264};
-use crate::crypto::mnemonic::{check_phrase, mnemonics};
+use crate::crypto::mnemonic::{check_phrase, mnemonics, TON_DICTIONARY};
+use crate::encoding::hex_decode;
 use crate::error::{ClientError, ClientResult};
 use base58::*;
 use byteorder::{BigEndian, ByteOrder, LittleEndian};
@@ -23,6 +24,7 @@ use pbkdf2::pbkdf2;
 use libsecp256k1::{SecretKey, PublicKey};
 use sha2::{Digest, Sha512};
 use crate::crypto::default_hdkey_compliant;
+use zeroize::Zeroize;
 
 //----------------------------------------------------------------- crypto.hdkey_xprv_from_mnemonic
 
@@ -176,6 +178,139 @@ pub fn hdkey_derive_from_xprv_path(
     })
 }
 
+//---------------------------------------------------------------------- crypto.slip10_xprv_from_seed
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfSlip10XPrvFromSeed {
+    /// Seed bytes (16 to 64 bytes). Encoded with `hex`.
+    pub seed: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfSlip10XPrvFromSeed {
+    /// Serialized extended master private key
+    pub xprv: String,
+}
+
+/// Generates SLIP-0010 ed25519 extended master private key from the seed.
+///
+/// SLIP-0010 keys are serialized in the BIP-32 format with their own `eprv` version bytes,
+/// so they can not be passed to BIP-32 derivation functions and vice versa.
+/// `hdkey_secret_from_xprv` and `hdkey_public_from_xprv` can be used
+/// to get the ed25519 key pair.
+#[api_function]
+pub fn slip10_xprv_from_seed(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfSlip10XPrvFromSeed,
+) -> ClientResult<ResultOfSlip10XPrvFromSeed> {
+    let mut seed = hex_decode(&params.seed)?;
+    if seed.len() < 16 || seed.len() > 64 {
+        return Err(crypto::Error::invalid_key_size(seed.len(), &[16, 32, 64]));
+    }
+    let master = HDPrivateKey::slip10_master(&seed)?;
+    seed.zeroize();
+    Ok(ResultOfSlip10XPrvFromSeed {
+        xprv: master.serialize_to_string(),
+    })
+}
+
+//------------------------------------------------------------------ crypto.slip10_xprv_from_mnemonic
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfSlip10XPrvFromMnemonic {
+    /// String with seed phrase
+    pub phrase: String,
+    /// Dictionary identifier
+    pub dictionary: Option<u8>,
+    /// Mnemonic word count
+    pub word_count: Option<u8>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfSlip10XPrvFromMnemonic {
+    /// Serialized extended master private key
+    pub xprv: String,
+}
+
+/// Generates SLIP-0010 ed25519 extended master private key from the seed phrase.
+///
+/// BIP-39 seed of the phrase with an empty passphrase is used for BIP-39 dictionaries,
+/// so the keys are compatible with wallets using SLIP-0010 ed25519 derivation.
+#[api_function]
+pub fn slip10_xprv_from_mnemonic(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfSlip10XPrvFromMnemonic,
+) -> ClientResult<ResultOfSlip10XPrvFromMnemonic> {
+    let dictionary = params.dictionary.unwrap_or(context.config.crypto.mnemonic_dictionary);
+    let salt = if dictionary == TON_DICTIONARY { "TON default seed" } else { "" };
+    let mut seed = hex_decode(
+        &mnemonics(&context.config.crypto, params.dictionary, params.word_count)?
+            .seed_from_phrase_and_salt(&params.phrase, &salt.to_string())?,
+    )?;
+    let master = HDPrivateKey::slip10_master(&seed)?;
+    seed.zeroize();
+    Ok(ResultOfSlip10XPrvFromMnemonic {
+        xprv: master.serialize_to_string(),
+    })
+}
+
+//-------------------------------------------------------------------- crypto.slip10_derive_from_xprv
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfSlip10DeriveFromXPrv {
+    /// Serialized extended private key
+    pub xprv: String,
+    /// Child index. The key is always derived as hardened.
+    pub child_index: u32,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfSlip10DeriveFromXPrv {
+    /// Serialized extended private key
+    pub xprv: String,
+}
+
+/// Returns SLIP-0010 ed25519 extended private key derived from the specified
+/// extended private key and child index.
+#[api_function]
+pub fn slip10_derive_from_xprv(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfSlip10DeriveFromXPrv,
+) -> ClientResult<ResultOfSlip10DeriveFromXPrv> {
+    let xprv = HDPrivateKey::from_serialized_string(&params.xprv)?;
+    Ok(ResultOfSlip10DeriveFromXPrv {
+        xprv: xprv.slip10_derive(params.child_index)?.serialize_to_string(),
+    })
+}
+
+//--------------------------------------------------------------- crypto.slip10_derive_from_xprv_path
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfSlip10DeriveFromXPrvPath {
+    /// Serialized extended private key
+    pub xprv: String,
+    /// Derivation path with hardened indices only, for instance "m/44'/396'/0'"
+    pub path: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfSlip10DeriveFromXPrvPath {
+    /// Derived serialized extended private key
+    pub xprv: String,
+}
+
+/// Derives SLIP-0010 ed25519 extended private key from the specified key and path.
+#[api_function]
+pub fn slip10_derive_from_xprv_path(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfSlip10DeriveFromXPrvPath,
+) -> ClientResult<ResultOfSlip10DeriveFromXPrvPath> {
+    let xprv = HDPrivateKey::from_serialized_string(&params.xprv)?;
+    Ok(ResultOfSlip10DeriveFromXPrvPath {
+        xprv: xprv.slip10_derive_path(&params.path)?.serialize_to_string(),
+    })
+}
+
 // Internals
 
 #[derive(Default, Clone)]
@@ -185,9 +320,19 @@ pub(crate) struct HDPrivateKey {
     child_number: [u8; 4],
     child_chain: Key256,
     key: Key256,
+    slip10: bool,
 }
 
 static XPRV_VERSION: [u8; 4] = [0x04, 0x88, 0xAD, 0xE4];
+/// Version bytes of SLIP-0010 ed25519 keys. Serialized keys start with `eprv`.
+static SLIP10_XPRV_VERSION: [u8; 4] = [0x03, 0x12, 0x6F, 0x7C];
+
+impl Drop for HDPrivateKey {
+    fn drop(&mut self) {
+        self.child_chain.zeroize();
+        self.key.zeroize();
+    }
+}
 
 impl HDPrivateKey {
     pub(crate) fn master(child_chain: &Key256, key: &Key256) -> HDPrivateKey {
@@ -197,7 +342,19 @@ impl HDPrivateKey {
             child_number: [0; 4],
             child_chain: *child_chain,
             key: *key,
+            slip10: false,
+        }
+    }
+
+    fn check_slip10(&self, slip10: bool) -> ClientResult<()> {
+        if self.slip10 == slip10 {
+            return Ok(());
         }
+        Err(crypto::Error::bip32_invalid_key(if self.slip10 {
+            "SLIP-0010 ed25519 key can not be used for BIP-32 derivation"
+        } else {
+            "BIP-32 key can not be used for SLIP-0010 ed25519 derivation"
+        }))
     }
 
     pub(crate) fn from_mnemonic(phrase: &String) -> ClientResult<HDPrivateKey> {
@@ -206,17 +363,27 @@ impl HDPrivateKey {
         pbkdf2::<Hmac<Sha512>>(phrase.as_bytes(), salt.as_bytes(), 2048, &mut seed);
         let mut hmac: Hmac<Sha512> = Hmac::new_from_slice(b"Bitcoin seed").unwrap();
         hmac.update(&seed);
-        let child_chain_with_key = key512(&hmac.finalize().into_bytes())?;
-        Ok(HDPrivateKey::master(
+        seed.zeroize();
+        let mut child_chain_with_key = key512(&hmac.finalize().into_bytes())?;
+        let master = HDPrivateKey::master(
             &key256(&child_chain_with_key[32..])?,
             &key256(&child_chain_with_key[..32])?,
-        ))
+        );
+        child_chain_with_key.zeroize();
+        Ok(master)
     }
 
     pub(crate) fn secret(&self) -> Key256 {
         self.key
     }
 
+    pub(crate) fn ed25519_keypair(&self) -> ClientResult<ed25519_dalek::Keypair> {
+        let secret = ed25519_dalek::SecretKey::from_bytes(&self.key)
+            .map_err(|err| crypto::Error::bip32_invalid_key(err))?;
+        let public = ed25519_dalek::PublicKey::from(&secret);
+        Ok(ed25519_dalek::Keypair { secret, public })
+    }
+
     fn public(&self) -> Key264 {
         let secret_key = SecretKey::parse(&self.key).unwrap();
         let public_key = PublicKey::from_secret_key(&secret_key);
@@ -253,6 +420,7 @@ impl HDPrivateKey {
         hardened: bool,
         compliant: bool,
     ) -> ClientResult<HDPrivateKey> {
+        self.check_slip10(false)?;
         let mut child: HDPrivateKey = Default::default();
         child.depth = self.depth + 1;
 
@@ -304,7 +472,71 @@ impl HDPrivateKey {
         Ok(child)
     }
 
+    pub(crate) fn slip10_master(seed: &[u8]) -> ClientResult<HDPrivateKey> {
+        let mut hmac: Hmac<Sha512> = Hmac::new_from_slice(b"ed25519 seed").unwrap();
+        hmac.update(seed);
+        let mut child_chain_with_key = key512(&hmac.finalize().into_bytes())?;
+        let mut master = HDPrivateKey::master(
+            &key256(&child_chain_with_key[32..])?,
+            &key256(&child_chain_with_key[..32])?,
+        );
+        master.slip10 = true;
+        child_chain_with_key.zeroize();
+        Ok(master)
+    }
+
+    /// SLIP-0010 ed25519 child key derivation. Only hardened keys can be derived.
+    pub(crate) fn slip10_derive(&self, child_index: u32) -> ClientResult<HDPrivateKey> {
+        self.check_slip10(true)?;
+        let mut child: HDPrivateKey = Default::default();
+        child.depth = self.depth + 1;
+        child.slip10 = true;
+
+        let mut public = vec![0u8];
+        public.extend_from_slice(self.ed25519_keypair()?.public.as_bytes());
+        let mut sha_hasher = sha2::Sha256::new();
+        sha_hasher.update(&public);
+        let sha: Key256 = sha_hasher.finalize().into();
+        let fingerprint = Ripemd160::new().update(&sha).digest();
+        child.parent_fingerprint.copy_from_slice(&fingerprint[0..4]);
+
+        BigEndian::write_u32(&mut child.child_number, 0x80000000 | child_index);
+
+        let mut hmac: Hmac<Sha512> = Hmac::new_from_slice(&self.child_chain)
+            .map_err(|err| crypto::Error::bip32_invalid_key(err))?;
+        hmac.update(&[0]);
+        hmac.update(&self.key);
+        hmac.update(&child.child_number);
+        let mut result = key512(&hmac.finalize().into_bytes())?;
+        child.key.copy_from_slice(&result[..32]);
+        child.child_chain.copy_from_slice(&result[32..]);
+        result.zeroize();
+        Ok(child)
+    }
+
+    pub(crate) fn slip10_derive_path(&self, path: &String) -> ClientResult<HDPrivateKey> {
+        self.check_slip10(true)?;
+        let mut child: HDPrivateKey = self.clone();
+        for step in path.split("/") {
+            if step == "m" {
+                continue;
+            }
+            if !step.ends_with('\'') {
+                return Err(crypto::Error::bip32_invalid_derive_path(format!(
+                    "{} (SLIP-0010 ed25519 supports hardened derivation only)",
+                    path
+                )));
+            }
+            let index: u32 = step[0..(step.len() - 1)]
+                .parse()
+                .map_err(|_| crypto::Error::bip32_invalid_derive_path(path))?;
+            child = child.slip10_derive(index)?;
+        }
+        Ok(child)
+    }
+
     pub(crate) fn derive_path(&self, path: &String, compliant: bool) -> ClientResult<HDPrivateKey> {
+        self.check_slip10(false)?;
         let mut child: HDPrivateKey = self.clone();
         for step in path.split("/") {
             if step == "m" {
@@ -331,10 +563,11 @@ impl HDPrivateKey {
         }
         let mut version = [0u8; 4];
         version.clone_from_slice(&bytes[0..4]);
-        if version != XPRV_VERSION {
+        if version != XPRV_VERSION && version != SLIP10_XPRV_VERSION {
             return Err(crypto::Error::bip32_invalid_key(bytes.to_base58()));
         }
         let mut xprv: HDPrivateKey = Default::default();
+        xprv.slip10 = version == SLIP10_XPRV_VERSION;
         xprv.depth = bytes[4];
         xprv.parent_fingerprint.copy_from_slice(&bytes[5..9]);
         xprv.child_number.copy_from_slice(&bytes[9..13]);
@@ -348,7 +581,7 @@ impl HDPrivateKey {
 
     fn serialize(&self) -> Vec<u8> {
         let mut bytes: Vec<u8> = Vec::new();
-        bytes.extend(&XPRV_VERSION);
+        bytes.extend(if self.slip10 { &SLIP10_XPRV_VERSION } else { &XPRV_VERSION });
         bytes.push(self.depth);
         bytes.extend(&self.parent_fingerprint);
         bytes.extend(&self.child_number);
@@ -359,7 +592,7 @@ impl HDPrivateKey {
         bytes
     }
 
-    fn from_serialized_string(string: &String) -> ClientResult<HDPrivateKey> {
+    pub(crate) fn from_serialized_string(string: &String) -> ClientResult<HDPrivateKey> {
         Self::from_serialized(
             &string
                 .from_base58()
@@ -367,7 +600,7 @@ impl HDPrivateKey {
         )
     }
 
-    fn serialize_to_string(&self) -> String {
+    pub(crate) fn serialize_to_string(&self) -> String {
         self.serialize().to_base58()
     }
 }
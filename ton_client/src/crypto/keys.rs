@@ -14,12 +14,12 @@
 use crate::client::ClientContext;
 use crate::crypto;
 use crate::crypto::internal::{
-    decode_public_key, decode_secret_key, key256, sign_using_keys, ton_crc16,
+    decode_public_key, decode_secret_key, key256, key512, sign_using_keys, ton_crc16,
 };
 use crate::encoding::{base64_decode, hex_decode};
 use crate::error::ClientResult;
 use base64::URL_SAFE;
-use ed25519_dalek::Keypair;
+use ed25519_dalek::{Keypair, Verifier};
 use std::fmt::{Debug, Formatter};
 
 pub(crate) fn strip_secret(secret: &str) -> String {
@@ -181,3 +181,89 @@ pub fn verify_signature(
         unsigned: base64::encode(&unsigned),
     })
 }
+
+//----------------------------------------------------------------------- verify_signatures_batch
+
+#[derive(Serialize, Deserialize, Clone, ApiType, Default)]
+pub struct SignatureBatchItem {
+    /// Unsigned data. Encoded with `base64`.
+    pub unsigned: String,
+    /// Signature. Encoded with `hex`.
+    pub signature: String,
+    /// Signer's public key - 64 symbols hex string
+    pub public: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfVerifySignaturesBatch {
+    /// Signatures to verify
+    pub items: Vec<SignatureBatchItem>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfVerifySignaturesBatch {
+    /// `true` if all the signatures are valid
+    pub valid: bool,
+    /// Validity of each signature in the order of `items`
+    pub items_valid: Vec<bool>,
+}
+
+/// Verifies a batch of ed25519 signatures.
+///
+/// All the signatures are verified at once, which is much faster than verifying them
+/// one by one. If the batch verification fails, each signature is verified separately
+/// to report which ones are invalid. Malformed signatures and public keys are reported
+/// as invalid items.
+#[api_function]
+pub fn verify_signatures_batch(
+    _context: std::sync::Arc<ClientContext>,
+    params: ParamsOfVerifySignaturesBatch,
+) -> ClientResult<ResultOfVerifySignaturesBatch> {
+    let mut messages = Vec::with_capacity(params.items.len());
+    let mut signatures = Vec::with_capacity(params.items.len());
+    let mut public_keys = Vec::with_capacity(params.items.len());
+    for item in &params.items {
+        messages.push(base64_decode(&item.unsigned)?);
+        signatures.push(
+            hex_decode(&item.signature)
+                .and_then(|signature| key512(&signature))
+                .ok()
+                .map(ed25519_dalek::Signature::new),
+        );
+        public_keys.push(
+            hex_decode(&item.public)
+                .ok()
+                .and_then(|public| ed25519_dalek::PublicKey::from_bytes(&public).ok()),
+        );
+    }
+
+    if !params.items.is_empty()
+        && public_keys.iter().all(|key| key.is_some())
+        && signatures.iter().all(|signature| signature.is_some())
+    {
+        let keys: Vec<ed25519_dalek::PublicKey> =
+            public_keys.iter().map(|key| key.unwrap()).collect();
+        let batch_signatures: Vec<ed25519_dalek::Signature> =
+            signatures.iter().map(|signature| signature.unwrap()).collect();
+        let data: Vec<&[u8]> = messages.iter().map(|message| message.as_slice()).collect();
+        if ed25519_dalek::verify_batch(&data, &batch_signatures, &keys).is_ok() {
+            return Ok(ResultOfVerifySignaturesBatch {
+                valid: true,
+                items_valid: vec![true; params.items.len()],
+            });
+        }
+    }
+
+    let items_valid: Vec<bool> = public_keys
+        .iter()
+        .zip(messages.iter().zip(signatures.iter()))
+        .map(|(key, (message, signature))| match (key, signature) {
+            (Some(key), Some(signature)) => key.verify(message, signature).is_ok(),
+            _ => false,
+        })
+        .collect();
+    Ok(ResultOfVerifySignaturesBatch {
+        valid: items_valid.iter().all(|valid| *valid),
+        items_valid,
+    })
+}
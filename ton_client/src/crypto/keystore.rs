@@ -0,0 +1,477 @@
+/*
+* Copyright 2018-2021 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use crate::client::ClientContext;
+use crate::crypto;
+use crate::crypto::boxes::signing_box::{KeysSigningBox, RegisteredSigningBox, SigningBoxHandle};
+use crate::crypto::hdkey::HDPrivateKey;
+use crate::crypto::internal::pbkdf2_hmac_sha512;
+use crate::crypto::keys::KeyPair;
+use crate::crypto::mnemonic::{check_phrase, mnemonics};
+use crate::encoding::{base64_decode, hex_decode};
+use crate::error::ClientResult;
+use chacha20::cipher::{NewStreamCipher, SyncStreamCipher};
+use chacha20::{Key, Nonce};
+use hmac::{Hmac, Mac, NewMac};
+use rand::RngCore;
+use sha2::Sha256;
+use std::sync::Arc;
+use zeroize::{Zeroize, Zeroizing};
+
+const KEYSTORE_VERSION: u32 = 1;
+const KEYSTORE_CIPHER: &str = "chacha20-hmac-sha256";
+const SALT_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+
+// Keystores can come from untrusted sources, so KDF cost is limited.
+/// Maximum `scrypt` `log_n`.
+const MAX_SCRYPT_LOG_N: u8 = 20;
+/// Maximum `scrypt` memory `128 * r * 2^log_n` in bytes.
+const MAX_SCRYPT_MEMORY: u64 = 256 * 1024 * 1024;
+/// Maximum `scrypt` work factor `2^log_n * r * p`.
+const MAX_SCRYPT_WORK: u64 = 1 << 24;
+/// Maximum `PBKDF2` iteration count.
+const MAX_PBKDF2_ITERATIONS: u32 = 10_000_000;
+
+//------------------------------------------------------------------------------------------ Types
+
+#[derive(Serialize, Deserialize, Clone, ApiType)]
+#[serde(tag = "type")]
+pub enum KeystoreSecret {
+    /// Ed25519 key pair
+    KeyPair { keys: KeyPair },
+    /// Seed phrase
+    Mnemonic {
+        /// Phrase
+        phrase: String,
+        /// Dictionary identifier
+        dictionary: Option<u8>,
+        /// Word count
+        word_count: Option<u8>,
+    },
+    /// Serialized extended private key
+    XPrv { xprv: String },
+}
+
+impl Default for KeystoreSecret {
+    fn default() -> Self {
+        KeystoreSecret::KeyPair {
+            keys: Default::default(),
+        }
+    }
+}
+
+impl Zeroize for KeystoreSecret {
+    fn zeroize(&mut self) {
+        match self {
+            KeystoreSecret::KeyPair { keys } => keys.secret.zeroize(),
+            KeystoreSecret::Mnemonic { phrase, .. } => phrase.zeroize(),
+            KeystoreSecret::XPrv { xprv } => xprv.zeroize(),
+        }
+    }
+}
+
+impl Drop for KeystoreSecret {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, PartialEq)]
+#[serde(tag = "type")]
+pub enum KeystoreKdf {
+    /// `scrypt` key derivation. See `crypto.scrypt` for parameters description.
+    Scrypt { log_n: u8, r: u32, p: u32 },
+    /// `PBKDF2` key derivation with `HMAC-SHA512`
+    Pbkdf2 {
+        /// Iteration count
+        c: u32,
+    },
+}
+
+impl Default for KeystoreKdf {
+    fn default() -> Self {
+        KeystoreKdf::Scrypt {
+            log_n: 15,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+/// Password protected secret.
+///
+/// Can be stored by the application as is. All the fields except `mac`
+/// are authenticated with `mac`.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct Keystore {
+    /// Keystore format version
+    pub version: u32,
+    /// Type of the encrypted secret: `KeyPair`, `Mnemonic` or `XPrv`
+    pub secret_type: String,
+    /// Public key of the encrypted secret if it is known without decryption.
+    /// Encoded with `hex`.
+    pub public: Option<String>,
+    /// Key derivation function and its parameters
+    pub kdf: KeystoreKdf,
+    /// Key derivation salt. Encoded with `hex`.
+    pub salt: String,
+    /// Cipher identifier
+    pub cipher: String,
+    /// Cipher nonce. Encoded with `hex`.
+    pub nonce: String,
+    /// Encrypted secret. Encoded with `base64`.
+    pub ciphertext: String,
+    /// `HMAC-SHA256` of the keystore. Encoded with `hex`.
+    pub mac: String,
+}
+
+impl KeystoreSecret {
+    fn type_name(&self) -> &'static str {
+        match self {
+            KeystoreSecret::KeyPair { .. } => "KeyPair",
+            KeystoreSecret::Mnemonic { .. } => "Mnemonic",
+            KeystoreSecret::XPrv { .. } => "XPrv",
+        }
+    }
+
+    fn check(&self, context: &ClientContext) -> ClientResult<Option<String>> {
+        match self {
+            KeystoreSecret::KeyPair { keys } => {
+                let decoded = keys.decode()?;
+                Ok(Some(hex::encode(decoded.public.as_bytes())))
+            }
+            KeystoreSecret::Mnemonic {
+                phrase,
+                dictionary,
+                word_count,
+            } => {
+                check_phrase(
+                    &*mnemonics(&context.config.crypto, *dictionary, *word_count)?,
+                    phrase,
+                )?;
+                Ok(None)
+            }
+            KeystoreSecret::XPrv { xprv } => {
+                let key = HDPrivateKey::from_serialized_string(xprv)?;
+                Ok(Some(hex::encode(key.ed25519_keypair()?.public.as_bytes())))
+            }
+        }
+    }
+
+    fn signing_keys(
+        &self,
+        context: &ClientContext,
+        path: Option<String>,
+    ) -> ClientResult<ed25519_dalek::Keypair> {
+        match self {
+            KeystoreSecret::KeyPair { keys } => keys.decode(),
+            KeystoreSecret::Mnemonic {
+                phrase,
+                dictionary,
+                word_count,
+            } => {
+                let path = path.unwrap_or(context.config.crypto.hdkey_derivation_path.clone());
+                mnemonics(&context.config.crypto, *dictionary, *word_count)?
+                    .derive_ed25519_keys_from_phrase(&context.config.crypto, phrase, &path)?
+                    .decode()
+            }
+            KeystoreSecret::XPrv { xprv } => {
+                let mut key = HDPrivateKey::from_serialized_string(xprv)?;
+                if let Some(path) = path {
+                    key = key.derive_path(&path, crypto::default_hdkey_compliant())?;
+                }
+                key.ed25519_keypair()
+            }
+        }
+    }
+}
+
+fn check_kdf(kdf: &KeystoreKdf) -> ClientResult<()> {
+    match kdf {
+        KeystoreKdf::Scrypt { log_n, r, p } => {
+            let n = 1u64 << (*log_n).min(MAX_SCRYPT_LOG_N);
+            let r_n = n.saturating_mul(*r as u64);
+            if *log_n > MAX_SCRYPT_LOG_N
+                || r_n.saturating_mul(128) > MAX_SCRYPT_MEMORY
+                || r_n.saturating_mul(*p as u64) > MAX_SCRYPT_WORK
+            {
+                return Err(crypto::Error::invalid_keystore(format!(
+                    "scrypt parameters log_n = {}, r = {}, p = {} exceed the allowed cost",
+                    log_n, r, p
+                )));
+            }
+        }
+        KeystoreKdf::Pbkdf2 { c } => {
+            if *c == 0 {
+                return Err(crypto::Error::invalid_keystore("PBKDF2 iteration count is zero"));
+            }
+            if *c > MAX_PBKDF2_ITERATIONS {
+                return Err(crypto::Error::invalid_keystore(format!(
+                    "PBKDF2 iteration count {} exceeds {}",
+                    c, MAX_PBKDF2_ITERATIONS
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn derive_key(
+    kdf: &KeystoreKdf,
+    password: &[u8],
+    salt: &[u8],
+) -> ClientResult<Zeroizing<[u8; 64]>> {
+    check_kdf(kdf)?;
+    match kdf {
+        KeystoreKdf::Scrypt { log_n, r, p } => {
+            let params = scrypt::Params::new(*log_n, *r, *p)
+                .map_err(|err| crypto::Error::scrypt_failed(err))?;
+            let mut key = Zeroizing::new([0u8; 64]);
+            scrypt::scrypt(password, salt, &params, &mut *key)
+                .map_err(|err| crypto::Error::scrypt_failed(err))?;
+            Ok(key)
+        }
+        KeystoreKdf::Pbkdf2 { c } => Ok(Zeroizing::new(pbkdf2_hmac_sha512(password, salt, *c))),
+    }
+}
+
+fn keystore_mac(mac_key: &[u8], keystore: &Keystore) -> ClientResult<Hmac<Sha256>> {
+    let mut authenticated = keystore.clone();
+    authenticated.mac = String::new();
+    let data = serde_json::to_vec(&authenticated)
+        .map_err(|err| crypto::Error::invalid_keystore(err))?;
+    let mut mac = Hmac::<Sha256>::new_from_slice(mac_key)
+        .map_err(|err| crypto::Error::invalid_keystore(err))?;
+    mac.update(&data);
+    Ok(mac)
+}
+
+fn apply_cipher(key: &[u8], nonce: &[u8], data: &mut [u8]) {
+    let mut cipher = chacha20::ChaCha20::new(Key::from_slice(key), Nonce::from_slice(nonce));
+    cipher.apply_keystream(data);
+}
+
+fn encrypt(
+    context: &ClientContext,
+    secret: &KeystoreSecret,
+    password: &[u8],
+    kdf: KeystoreKdf,
+) -> ClientResult<Keystore> {
+    let public = secret.check(context)?;
+
+    let mut rng = rand::thread_rng();
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    rng.fill_bytes(&mut salt);
+    rng.fill_bytes(&mut nonce);
+
+    let key = derive_key(&kdf, password, &salt)?;
+    let mut data = Zeroizing::new(
+        serde_json::to_vec(secret).map_err(|err| crypto::Error::invalid_keystore(err))?,
+    );
+    apply_cipher(&key[..32], &nonce, &mut data);
+
+    let mut keystore = Keystore {
+        version: KEYSTORE_VERSION,
+        secret_type: secret.type_name().to_string(),
+        public,
+        kdf,
+        salt: hex::encode(&salt),
+        cipher: KEYSTORE_CIPHER.to_string(),
+        nonce: hex::encode(&nonce),
+        ciphertext: base64::encode(&*data),
+        mac: String::new(),
+    };
+    keystore.mac = hex::encode(keystore_mac(&key[32..], &keystore)?.finalize().into_bytes());
+    Ok(keystore)
+}
+
+fn decrypt(keystore: &Keystore, password: &[u8]) -> ClientResult<KeystoreSecret> {
+    if keystore.version != KEYSTORE_VERSION {
+        return Err(crypto::Error::invalid_keystore(format!(
+            "unsupported version {}",
+            keystore.version
+        )));
+    }
+    if keystore.cipher != KEYSTORE_CIPHER {
+        return Err(crypto::Error::invalid_keystore(format!(
+            "unsupported cipher {}",
+            keystore.cipher
+        )));
+    }
+    let salt = hex_decode(&keystore.salt)?;
+    let nonce = hex_decode(&keystore.nonce)?;
+    if nonce.len() != NONCE_LEN {
+        return Err(crypto::Error::invalid_keystore(format!(
+            "invalid nonce size {}",
+            nonce.len()
+        )));
+    }
+    let mac = hex_decode(&keystore.mac)?;
+
+    let key = derive_key(&keystore.kdf, password, &salt)?;
+    keystore_mac(&key[32..], keystore)?
+        .verify(&mac)
+        .map_err(|_| crypto::Error::keystore_invalid_password())?;
+
+    let mut data = Zeroizing::new(base64_decode(&keystore.ciphertext)?);
+    apply_cipher(&key[..32], &nonce, &mut data);
+    let secret: KeystoreSecret =
+        serde_json::from_slice(&data).map_err(|err| crypto::Error::invalid_keystore(err))?;
+    if secret.type_name() != keystore.secret_type {
+        return Err(crypto::Error::invalid_keystore("secret type mismatch"));
+    }
+    Ok(secret)
+}
+
+//---------------------------------------------------------------------------------- keystore_import
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfKeystoreImport {
+    /// Secret to be encrypted
+    pub secret: KeystoreSecret,
+    /// Password. Must be encoded with `base64`.
+    pub password: String,
+    /// Key derivation function. Default is `scrypt` with `log_n = 15`, `r = 8`, `p = 1`.
+    pub kdf: Option<KeystoreKdf>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfKeystoreImport {
+    /// Encrypted keystore
+    pub keystore: Keystore,
+}
+
+/// Encrypts a key pair, a seed phrase or an extended private key with a password.
+///
+/// The secret is validated before encryption. The encryption key is derived from the
+/// password with the specified key derivation function and a random salt. The secret
+/// is encrypted with `chacha20` and the whole keystore is authenticated with `HMAC-SHA256`.
+#[api_function]
+pub fn keystore_import(
+    context: Arc<ClientContext>,
+    params: ParamsOfKeystoreImport,
+) -> ClientResult<ResultOfKeystoreImport> {
+    let password = Zeroizing::new(base64_decode(&params.password)?);
+    Ok(ResultOfKeystoreImport {
+        keystore: encrypt(
+            &context,
+            &params.secret,
+            &password,
+            params.kdf.unwrap_or_default(),
+        )?,
+    })
+}
+
+//---------------------------------------------------------------------------------- keystore_export
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfKeystoreExport {
+    /// Encrypted keystore
+    pub keystore: Keystore,
+    /// Password. Must be encoded with `base64`.
+    pub password: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfKeystoreExport {
+    /// Decrypted secret
+    pub secret: KeystoreSecret,
+}
+
+/// Decrypts the secret stored in the keystore.
+#[api_function]
+pub fn keystore_export(
+    _context: Arc<ClientContext>,
+    params: ParamsOfKeystoreExport,
+) -> ClientResult<ResultOfKeystoreExport> {
+    let password = Zeroizing::new(base64_decode(&params.password)?);
+    Ok(ResultOfKeystoreExport {
+        secret: decrypt(&params.keystore, &password)?,
+    })
+}
+
+//------------------------------------------------------------------------- keystore_change_password
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfKeystoreChangePassword {
+    /// Encrypted keystore
+    pub keystore: Keystore,
+    /// Current password. Must be encoded with `base64`.
+    pub password: String,
+    /// New password. Must be encoded with `base64`.
+    pub new_password: String,
+    /// New key derivation function. The current one is used if not specified.
+    pub kdf: Option<KeystoreKdf>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfKeystoreChangePassword {
+    /// Keystore encrypted with the new password
+    pub keystore: Keystore,
+}
+
+/// Re-encrypts the keystore with a new password.
+///
+/// New salt and nonce are generated.
+#[api_function]
+pub fn keystore_change_password(
+    context: Arc<ClientContext>,
+    params: ParamsOfKeystoreChangePassword,
+) -> ClientResult<ResultOfKeystoreChangePassword> {
+    let password = Zeroizing::new(base64_decode(&params.password)?);
+    let new_password = Zeroizing::new(base64_decode(&params.new_password)?);
+    let secret = decrypt(&params.keystore, &password)?;
+    let kdf = params.kdf.unwrap_or(params.keystore.kdf);
+    Ok(ResultOfKeystoreChangePassword {
+        keystore: encrypt(&context, &secret, &new_password, kdf)?,
+    })
+}
+
+//------------------------------------------------------------------------ keystore_get_signing_box
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfKeystoreGetSigningBox {
+    /// Encrypted keystore
+    pub keystore: Keystore,
+    /// Password. Must be encoded with `base64`.
+    pub password: String,
+    /// Derivation path for `Mnemonic` and `XPrv` secrets.
+    /// For `Mnemonic` the default is `CryptoConfig.hdkey_derivation_path`,
+    /// for `XPrv` the key itself is used if not specified.
+    pub path: Option<String>,
+}
+
+/// Unlocks the keystore and registers a signing box with the decrypted keys.
+///
+/// The secret itself is never returned to the caller.
+#[api_function]
+pub async fn keystore_get_signing_box(
+    context: Arc<ClientContext>,
+    params: ParamsOfKeystoreGetSigningBox,
+) -> ClientResult<RegisteredSigningBox> {
+    let password = Zeroizing::new(base64_decode(&params.password)?);
+    let secret = decrypt(&params.keystore, &password)?;
+    let keys = secret.signing_keys(&context, params.path)?;
+
+    let id = context.get_next_id();
+    context
+        .boxes
+        .signing_boxes
+        .insert(id, Box::new(KeysSigningBox::new(keys)));
+
+    Ok(RegisteredSigningBox {
+        handle: SigningBoxHandle(id),
+    })
+}
@@ -25,8 +25,10 @@ use hmac::Hmac;
 use pbkdf2::pbkdf2;
 use rand::RngCore;
 use sha2::Sha512;
+use std::collections::HashSet;
+use zeroize::Zeroize;
 
-const TON_DICTIONARY: u8 = 0;
+pub(super) const TON_DICTIONARY: u8 = 0;
 const ENGLISH_DICTIONARY: u8 = 1;
 const CHINESE_SIMPLIFIED_DICTIONARY: u8 = 2;
 const CHINESE_TRADITIONAL_DICTIONARY: u8 = 3;
@@ -159,6 +161,115 @@ pub fn mnemonic_verify(
     })
 }
 
+//-------------------------------------------------------------------------------- mnemonic_suggest
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfMnemonicSuggest {
+    /// Phrase. Can be incomplete or contain misspelled words.
+    pub phrase: String,
+    /// Dictionary identifier. If not specified, the dictionary is inferred from the phrase words.
+    pub dictionary: Option<u8>,
+    /// Maximum number of suggestions for each word. Default is 5.
+    pub max_suggestions: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default)]
+pub struct MnemonicWordSuggestions {
+    /// Word of the phrase
+    pub word: String,
+    /// Flag indicating if the word is present in the dictionary
+    pub known: bool,
+    /// Dictionary words closest to the unknown word: words starting with it go first,
+    /// then words sorted by edit distance. Empty for known words.
+    pub suggestions: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default)]
+pub struct MnemonicCorrection {
+    /// Index of the replaced word in the phrase
+    pub index: u32,
+    /// Replacement word
+    pub word: String,
+    /// Corrected phrase
+    pub phrase: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfMnemonicSuggest {
+    /// Dictionary identifier, specified or inferred
+    pub dictionary: u8,
+    /// Flag indicating if the phrase is valid as is
+    pub valid: bool,
+    /// Suggestions for each word of the phrase
+    pub words: Vec<MnemonicWordSuggestions>,
+    /// Replacements of a single word that make the phrase valid.
+    /// Sorted by edit distance to the replaced word.
+    pub corrections: Vec<MnemonicCorrection>,
+}
+
+/// Suggests corrections of a mistyped or incomplete mnemonic phrase
+///
+/// Returns the dictionary words closest to each unknown word of the phrase.
+/// If the phrase is invalid, finds single word replacements that make it valid:
+/// if the phrase has one unknown word, all dictionary words are tried in its place,
+/// if all the words are known, each word is replaced with similar dictionary words.
+///
+/// If the dictionary is not specified, the one that contains the most phrase words is used.
+#[api_function]
+pub fn mnemonic_suggest(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfMnemonicSuggest,
+) -> ClientResult<ResultOfMnemonicSuggest> {
+    let config = &context.config.crypto;
+    let words: Vec<&str> = params.phrase.split_whitespace().collect();
+    let dictionary = match params.dictionary {
+        Some(dictionary) => dictionary,
+        None => infer_dictionary(config, &words)?,
+    };
+    let dictionary_words = dictionary_words(config, dictionary)?;
+    let known_words: HashSet<&str> = dictionary_words.iter().map(|word| word.as_str()).collect();
+    let max_suggestions = params.max_suggestions.unwrap_or(5) as usize;
+
+    let suggestions: Vec<MnemonicWordSuggestions> = words
+        .iter()
+        .map(|word| {
+            let known = known_words.contains(word);
+            MnemonicWordSuggestions {
+                word: word.to_string(),
+                known,
+                suggestions: if known {
+                    Vec::new()
+                } else {
+                    closest_words(word, &dictionary_words, max_suggestions)
+                },
+            }
+        })
+        .collect();
+
+    let mnemonic = mnemonics(config, Some(dictionary), Some(words.len() as u8)).ok();
+    let valid = match &mnemonic {
+        Some(mnemonic) => mnemonic.is_phrase_valid(&words.join(" "))?,
+        None => false,
+    };
+    let corrections = match &mnemonic {
+        Some(mnemonic) if !valid => find_corrections(
+            &**mnemonic,
+            &words,
+            &suggestions,
+            &dictionary_words,
+            max_suggestions,
+        )?,
+        _ => Vec::new(),
+    };
+
+    Ok(ResultOfMnemonicSuggest {
+        dictionary,
+        valid,
+        words: suggestions,
+        corrections,
+    })
+}
+
 //----------------------------------------------------------------------- mnemonic_derive_sign_keys
 
 #[derive(Serialize, Deserialize, ApiType, Default)]
@@ -223,6 +334,134 @@ pub(super) fn mnemonics(
     Ok(Box::new(Bip39Mnemonic::new(mnemonic_type, language)))
 }
 
+/// Maximal edit distance between a mistyped word and its correction
+/// when all the phrase words are present in the dictionary.
+const MAX_TYPO_DISTANCE: usize = 2;
+
+fn dictionary_words(config: &CryptoConfig, dictionary: u8) -> ClientResult<Vec<String>> {
+    Ok(mnemonics(config, Some(dictionary), Some(config.mnemonic_word_count))?
+        .get_words()?
+        .split(' ')
+        .map(|word| word.to_string())
+        .collect())
+}
+
+fn infer_dictionary(config: &CryptoConfig, words: &[&str]) -> ClientResult<u8> {
+    let phrase = words.join(" ");
+    let mut best = (config.mnemonic_dictionary, (0, false, false));
+    for dictionary in TON_DICTIONARY..=SPANISH_DICTIONARY {
+        let dictionary_words: HashSet<String> =
+            dictionary_words(config, dictionary)?.into_iter().collect();
+        let known = words.iter().filter(|word| dictionary_words.contains(**word)).count();
+        if known == 0 {
+            continue;
+        }
+        let valid = match mnemonics(config, Some(dictionary), Some(words.len() as u8)) {
+            Ok(mnemonic) => mnemonic.is_phrase_valid(&phrase)?,
+            Err(_) => false,
+        };
+        let score = (known, valid, dictionary == config.mnemonic_dictionary);
+        if score > best.1 {
+            best = (dictionary, score);
+        }
+    }
+    Ok(best.0)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for j in 0..b.len() {
+            let above = row[j + 1];
+            row[j + 1] = if a_char == b[j] {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+fn closest_words(word: &str, dictionary_words: &[String], max_count: usize) -> Vec<String> {
+    let mut scored: Vec<(usize, &String)> = dictionary_words
+        .iter()
+        .map(|candidate| {
+            let score = if candidate.starts_with(word) {
+                0
+            } else {
+                edit_distance(word, candidate)
+            };
+            (score, candidate)
+        })
+        .filter(|(score, _)| *score <= MAX_TYPO_DISTANCE)
+        .collect();
+    scored.sort();
+    scored
+        .into_iter()
+        .take(max_count)
+        .map(|(_, candidate)| candidate.clone())
+        .collect()
+}
+
+fn find_corrections(
+    mnemonic: &dyn CryptoMnemonic,
+    words: &[&str],
+    suggestions: &[MnemonicWordSuggestions],
+    dictionary_words: &[String],
+    max_count: usize,
+) -> ClientResult<Vec<MnemonicCorrection>> {
+    let unknown: Vec<usize> = suggestions
+        .iter()
+        .enumerate()
+        .filter(|(_, suggestion)| !suggestion.known)
+        .map(|(index, _)| index)
+        .collect();
+    let positions: Vec<(usize, Vec<&String>)> = match unknown.len() {
+        0 => (0..words.len())
+            .map(|index| {
+                let candidates = dictionary_words
+                    .iter()
+                    .filter(|candidate| {
+                        *candidate != words[index]
+                            && edit_distance(words[index], candidate) <= MAX_TYPO_DISTANCE
+                    })
+                    .collect();
+                (index, candidates)
+            })
+            .collect(),
+        1 => vec![(unknown[0], dictionary_words.iter().collect())],
+        _ => return Ok(Vec::new()),
+    };
+
+    let mut corrections = Vec::new();
+    for (index, candidates) in positions {
+        let mut found = Vec::new();
+        for candidate in candidates {
+            let mut corrected = words.to_vec();
+            corrected[index] = candidate.as_str();
+            let phrase = corrected.join(" ");
+            if mnemonic.is_phrase_valid(&phrase)? {
+                found.push((edit_distance(words[index], candidate), MnemonicCorrection {
+                    index: index as u32,
+                    word: candidate.clone(),
+                    phrase,
+                }));
+            }
+        }
+        found.sort_by_key(|(distance, _)| *distance);
+        corrections.extend(found.into_iter().take(max_count));
+    }
+    corrections.sort_by_key(|correction| {
+        edit_distance(words[correction.index as usize], &correction.word)
+    });
+    Ok(corrections)
+}
+
 pub trait CryptoMnemonic {
     fn get_words(&self) -> ClientResult<String>;
     fn generate_random_phrase(&self) -> ClientResult<String>;
@@ -233,6 +472,7 @@ pub trait CryptoMnemonic {
         path: &String,
     ) -> ClientResult<KeyPair>;
     fn phrase_from_entropy(&self, entropy: &[u8]) -> ClientResult<String>;
+    fn master_key_from_phrase(&self, phrase: &String) -> ClientResult<HDPrivateKey>;
     fn is_phrase_valid(&self, phrase: &String) -> ClientResult<bool>;
     fn seed_from_phrase_and_salt(&self, phrase: &String, salt: &String) -> ClientResult<String>;
     fn entropy_from_phrase(&self, phrase: &String) -> ClientResult<String>;
@@ -294,12 +534,16 @@ impl CryptoMnemonic for Bip39Mnemonic {
         phrase: &String,
         path: &String,
     ) -> ClientResult<KeyPair> {
-        check_phrase(self, phrase)?;
         let derived =
-            HDPrivateKey::from_mnemonic(phrase)?.derive_path(path, default_hdkey_compliant())?;
+            self.master_key_from_phrase(phrase)?.derive_path(path, default_hdkey_compliant())?;
         ed25519_keys_from_secret_bytes(&derived.secret())
     }
 
+    fn master_key_from_phrase(&self, phrase: &String) -> ClientResult<HDPrivateKey> {
+        check_phrase(self, phrase)?;
+        HDPrivateKey::from_mnemonic(phrase)
+    }
+
     fn phrase_from_entropy(&self, entropy: &[u8]) -> ClientResult<String> {
         let mnemonic = Mnemonic::from_entropy(&entropy, self.language)
             .map_err(|err| crypto::Error::bip39_invalid_entropy(err))?;
@@ -413,12 +657,18 @@ impl CryptoMnemonic for TonMnemonic {
         phrase: &String,
         path: &String,
     ) -> ClientResult<KeyPair> {
+        let derived =
+            self.master_key_from_phrase(phrase)?.derive_path(path, default_hdkey_compliant())?;
+        ed25519_keys_from_secret_bytes(&derived.secret())
+    }
+
+    fn master_key_from_phrase(&self, phrase: &String) -> ClientResult<HDPrivateKey> {
         check_phrase(self, phrase)?;
 
-        let seed = Self::seed_from_string(&phrase, "TON default seed", 100_000);
+        let mut seed = Self::seed_from_string(&phrase, "TON default seed", 100_000);
         let master = HDPrivateKey::master(&key256(&seed[32..])?, &key256(&seed[..32])?);
-        let derived = master.derive_path(path, default_hdkey_compliant())?;
-        ed25519_keys_from_secret_bytes(&derived.secret())
+        seed.zeroize();
+        Ok(master)
     }
 
     fn phrase_from_entropy(&self, entropy: &[u8]) -> ClientResult<String> {
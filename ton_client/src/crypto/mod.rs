@@ -18,9 +18,12 @@ pub(crate) mod hash;
 pub(crate) mod hdkey;
 pub(crate) mod internal;
 pub(crate) mod keys;
+pub(crate) mod keystore;
 pub(crate) mod math;
 pub(crate) mod mnemonic;
 pub(crate) mod nacl;
+pub(crate) mod secp256k1;
+pub(crate) mod secret_sharing;
 
 pub use errors::{Error, ErrorCode};
 pub(crate) mod encryption;
@@ -28,10 +31,14 @@ pub(crate) mod encryption;
 mod tests;
 
 pub use crate::crypto::boxes::signing_box::{
-    get_signing_box, register_signing_box, remove_signing_box, signing_box_get_public_key,
-    signing_box_sign,
-    ParamsOfSigningBoxSign, RegisteredSigningBox, ResultOfSigningBoxGetPublicKey,
-    ResultOfSigningBoxSign, SigningBox, SigningBoxHandle,
+    collect_signatures, derive_signing_boxes, get_signing_box, get_signing_box_from_mnemonic,
+    register_signing_box, remove_signing_box, signing_box_get_public_key, signing_box_sign,
+    signing_box_sign_batch, CollectedSignature, DerivedSigningBox, ParamsOfCollectSignatures,
+    ParamsOfDeriveSigningBoxes, ParamsOfGetSigningBoxFromMnemonic, ParamsOfSigningBoxSign,
+    ParamsOfSigningBoxSignBatch, RegisteredSigningBox, ResultOfCollectSignatures,
+    ResultOfDeriveSigningBoxes, ResultOfSigningBoxGetPublicKey, ResultOfSigningBoxSign,
+    ResultOfSigningBoxSignBatch, SigningBox, SigningBoxContext, SigningBoxFailure,
+    SigningBoxHandle,
 };
 pub use crate::crypto::boxes::encryption_box::{
     register_encryption_box, remove_encryption_box, create_encryption_box,
@@ -41,21 +48,42 @@ pub use crate::crypto::boxes::encryption_box::{
     ParamsOfEncryptionBoxGetInfo, ResultOfEncryptionBoxGetInfo,
     ParamsOfEncryptionBoxEncrypt, ResultOfEncryptionBoxEncrypt,
     ParamsOfEncryptionBoxDecrypt, ResultOfEncryptionBoxDecrypt,
+    encryption_box_encrypt_init, encryption_box_encrypt_update, encryption_box_encrypt_finish,
+    encryption_box_decrypt_init, encryption_box_decrypt_update, encryption_box_decrypt_finish,
+    remove_encryption_session, EncryptionSessionHandle,
+    ParamsOfEncryptionBoxEncryptInit, ResultOfEncryptionBoxEncryptInit,
+    ParamsOfEncryptionBoxEncryptUpdate, ResultOfEncryptionBoxEncryptUpdate,
+    ParamsOfEncryptionBoxEncryptFinish, ResultOfEncryptionBoxEncryptFinish,
+    ParamsOfEncryptionBoxDecryptInit, ResultOfEncryptionBoxDecryptInit,
+    ParamsOfEncryptionBoxDecryptUpdate, ResultOfEncryptionBoxDecryptUpdate,
+    ParamsOfEncryptionBoxDecryptFinish, ResultOfEncryptionBoxDecryptFinish,
+    ParamsOfRemoveEncryptionSession,
 };
 pub use crate::crypto::boxes::encryption_box::aes::{AesInfo, AesParams};
 pub use crate::crypto::encscrypt::{scrypt, ParamsOfScrypt, ResultOfScrypt};
-pub use crate::crypto::hash::{sha256, sha512, ParamsOfHash, ResultOfHash};
+pub use crate::crypto::hash::{keccak256, sha256, sha512, ParamsOfHash, ResultOfHash};
 pub use crate::crypto::hdkey::{
     hdkey_derive_from_xprv, hdkey_derive_from_xprv_path, hdkey_public_from_xprv,
     hdkey_secret_from_xprv, hdkey_xprv_from_mnemonic, ParamsOfHDKeyDeriveFromXPrv,
     ParamsOfHDKeyDeriveFromXPrvPath, ParamsOfHDKeyPublicFromXPrv, ParamsOfHDKeySecretFromXPrv,
     ParamsOfHDKeyXPrvFromMnemonic, ResultOfHDKeyDeriveFromXPrv, ResultOfHDKeyDeriveFromXPrvPath,
     ResultOfHDKeyPublicFromXPrv, ResultOfHDKeySecretFromXPrv, ResultOfHDKeyXPrvFromMnemonic,
+    slip10_derive_from_xprv, slip10_derive_from_xprv_path, slip10_xprv_from_mnemonic,
+    slip10_xprv_from_seed, ParamsOfSlip10DeriveFromXPrv, ParamsOfSlip10DeriveFromXPrvPath,
+    ParamsOfSlip10XPrvFromMnemonic, ParamsOfSlip10XPrvFromSeed, ResultOfSlip10DeriveFromXPrv,
+    ResultOfSlip10DeriveFromXPrvPath, ResultOfSlip10XPrvFromMnemonic, ResultOfSlip10XPrvFromSeed,
 };
 pub use crate::crypto::keys::{
     convert_public_key_to_ton_safe_format, generate_random_sign_keys, sign, verify_signature,
-    KeyPair, ParamsOfConvertPublicKeyToTonSafeFormat, ParamsOfSign, ParamsOfVerifySignature,
-    ResultOfConvertPublicKeyToTonSafeFormat, ResultOfSign, ResultOfVerifySignature,
+    verify_signatures_batch, KeyPair, ParamsOfConvertPublicKeyToTonSafeFormat, ParamsOfSign,
+    ParamsOfVerifySignature, ParamsOfVerifySignaturesBatch, ResultOfConvertPublicKeyToTonSafeFormat,
+    ResultOfSign, ResultOfVerifySignature, ResultOfVerifySignaturesBatch, SignatureBatchItem,
+};
+pub use crate::crypto::keystore::{
+    keystore_change_password, keystore_export, keystore_get_signing_box, keystore_import,
+    Keystore, KeystoreKdf, KeystoreSecret, ParamsOfKeystoreChangePassword,
+    ParamsOfKeystoreExport, ParamsOfKeystoreGetSigningBox, ParamsOfKeystoreImport,
+    ResultOfKeystoreChangePassword, ResultOfKeystoreExport, ResultOfKeystoreImport,
 };
 pub use crate::crypto::math::{
     factorize, generate_random_bytes, modular_power, ton_crc16, ParamsOfFactorize,
@@ -63,11 +91,12 @@ pub use crate::crypto::math::{
     ResultOfGenerateRandomBytes, ResultOfModularPower, ResultOfTonCrc16,
 };
 pub use crate::crypto::mnemonic::{
-    mnemonic_derive_sign_keys, mnemonic_from_entropy, mnemonic_from_random, mnemonic_verify,
-    mnemonic_words, ParamsOfMnemonicDeriveSignKeys, ParamsOfMnemonicFromEntropy,
-    ParamsOfMnemonicFromRandom, ParamsOfMnemonicVerify, ParamsOfMnemonicWords,
-    ResultOfMnemonicFromEntropy, ResultOfMnemonicFromRandom, ResultOfMnemonicVerify,
-    ResultOfMnemonicWords,
+    mnemonic_derive_sign_keys, mnemonic_from_entropy, mnemonic_from_random, mnemonic_suggest,
+    mnemonic_verify, mnemonic_words, MnemonicCorrection, MnemonicWordSuggestions,
+    ParamsOfMnemonicDeriveSignKeys, ParamsOfMnemonicFromEntropy, ParamsOfMnemonicFromRandom,
+    ParamsOfMnemonicSuggest, ParamsOfMnemonicVerify, ParamsOfMnemonicWords,
+    ResultOfMnemonicFromEntropy, ResultOfMnemonicFromRandom, ResultOfMnemonicSuggest,
+    ResultOfMnemonicVerify, ResultOfMnemonicWords,
 };
 pub use crate::crypto::nacl::{
     nacl_box, nacl_box_keypair, nacl_box_keypair_from_secret_key, nacl_box_open, nacl_secret_box,
@@ -79,6 +108,18 @@ pub use crate::crypto::nacl::{
     ResultOfNaclBox, ResultOfNaclBoxOpen, ResultOfNaclSign, ResultOfNaclSignDetached,
     ResultOfNaclSignDetachedVerify, ResultOfNaclSignOpen,
 };
+pub use crate::crypto::secp256k1::{
+    generate_random_secp256k1_keys, get_secp256k1_signing_box, secp256k1_eth_address,
+    secp256k1_keypair_from_secret, secp256k1_recover_public_key, secp256k1_sign,
+    secp256k1_verify, ParamsOfSecp256k1EthAddress, ParamsOfSecp256k1KeyPairFromSecret,
+    ParamsOfSecp256k1RecoverPublicKey, ParamsOfSecp256k1Sign, ParamsOfSecp256k1Verify,
+    ResultOfSecp256k1EthAddress, ResultOfSecp256k1RecoverPublicKey, ResultOfSecp256k1Sign,
+    ResultOfSecp256k1Verify,
+};
+pub use crate::crypto::secret_sharing::{
+    combine_shares, split_secret, ParamsOfCombineShares, ParamsOfSplitSecret,
+    ResultOfCombineShares, ResultOfSplitSecret,
+};
 pub use encryption::{chacha20, ParamsOfChaCha20, ResultOfChaCha20};
 
 use serde::{Deserialize, Deserializer};
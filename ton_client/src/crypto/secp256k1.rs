@@ -0,0 +1,316 @@
+/*
+* Copyright 2018-2021 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use crate::client::ClientContext;
+use crate::crypto;
+use crate::crypto::boxes::signing_box::{register_signing_box, RegisteredSigningBox, SigningBox};
+use crate::crypto::keys::KeyPair;
+use crate::encoding::{base64_decode, hex_decode};
+use crate::error::ClientResult;
+use libsecp256k1::{Message, PublicKey, RecoveryId, SecretKey, Signature};
+use rand::RngCore;
+use sha3::{Digest, Keccak256};
+use std::sync::Arc;
+
+const ETH_RECOVERY_ID_OFFSET: u8 = 27;
+
+// Internals
+
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+fn map_error(err: libsecp256k1::Error) -> crate::error::ClientError {
+    crypto::Error::secp256k1_failed(format!("{:?}", err))
+}
+
+fn decode_secret(secret: &String) -> ClientResult<SecretKey> {
+    SecretKey::parse_slice(&hex_decode(secret)?)
+        .map_err(|err| crypto::Error::invalid_secret_key(format!("{:?}", err), secret))
+}
+
+fn decode_public(public: &String) -> ClientResult<PublicKey> {
+    PublicKey::parse_slice(&hex_decode(public)?, None)
+        .map_err(|err| crypto::Error::invalid_public_key(format!("{:?}", err), public))
+}
+
+fn message(unsigned: &String, prehashed: Option<bool>) -> ClientResult<Message> {
+    let data = base64_decode(unsigned)?;
+    if prehashed.unwrap_or(false) {
+        Message::parse_slice(&data).map_err(map_error)
+    } else {
+        Ok(Message::parse(&keccak256(&data)))
+    }
+}
+
+fn decode_signature(signature: &String) -> ClientResult<(Signature, RecoveryId)> {
+    let bytes = hex_decode(signature)?;
+    if bytes.len() != 65 {
+        return Err(crypto::Error::invalid_signature(
+            format!("invalid signature size {}, expected 65", bytes.len()),
+            signature,
+        ));
+    }
+    let recovery_id = if bytes[64] >= ETH_RECOVERY_ID_OFFSET {
+        bytes[64] - ETH_RECOVERY_ID_OFFSET
+    } else {
+        bytes[64]
+    };
+    Ok((
+        Signature::parse_standard_slice(&bytes[..64])
+            .map_err(|err| crypto::Error::invalid_signature(format!("{:?}", err), signature))?,
+        RecoveryId::parse(recovery_id)
+            .map_err(|err| crypto::Error::invalid_signature(format!("{:?}", err), signature))?,
+    ))
+}
+
+fn encode_signature(signature: &Signature, recovery_id: &RecoveryId) -> Vec<u8> {
+    let mut bytes = signature.serialize().to_vec();
+    bytes.push(recovery_id.serialize());
+    bytes
+}
+
+fn key_pair(secret: &SecretKey) -> KeyPair {
+    KeyPair::new(
+        hex::encode(&PublicKey::from_secret_key(secret).serialize_compressed()[..]),
+        hex::encode(secret.serialize()),
+    )
+}
+
+pub(crate) struct Secp256k1SigningBox {
+    secret: SecretKey,
+}
+
+impl Secp256k1SigningBox {
+    pub fn new(secret: SecretKey) -> Self {
+        Self { secret }
+    }
+}
+
+#[async_trait::async_trait]
+impl SigningBox for Secp256k1SigningBox {
+    async fn get_public_key(&self) -> ClientResult<Vec<u8>> {
+        Ok(PublicKey::from_secret_key(&self.secret)
+            .serialize_compressed()
+            .to_vec())
+    }
+
+    async fn sign(&self, unsigned: &[u8]) -> ClientResult<Vec<u8>> {
+        let (signature, recovery_id) =
+            libsecp256k1::sign(&Message::parse(&keccak256(unsigned)), &self.secret);
+        Ok(encode_signature(&signature, &recovery_id))
+    }
+}
+
+//------------------------------------------------------------------ generate_random_secp256k1_keys
+
+/// Generates random secp256k1 key pair.
+///
+/// Public key is returned in compressed form (33 bytes).
+#[api_function]
+pub fn generate_random_secp256k1_keys(_context: Arc<ClientContext>) -> ClientResult<KeyPair> {
+    let mut rng = rand::thread_rng();
+    let mut bytes = [0u8; 32];
+    loop {
+        rng.fill_bytes(&mut bytes);
+        if let Ok(secret) = SecretKey::parse(&bytes) {
+            return Ok(key_pair(&secret));
+        }
+    }
+}
+
+//------------------------------------------------------------------- secp256k1_keypair_from_secret
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfSecp256k1KeyPairFromSecret {
+    /// Secret key - 64 symbols hex string
+    pub secret: String,
+}
+
+/// Generates secp256k1 key pair from the secret key.
+///
+/// Can be used with the secret derived from a seed phrase with `hdkey_*` functions,
+/// since BIP-32 keys are secp256k1 keys.
+#[api_function]
+pub fn secp256k1_keypair_from_secret(
+    _context: Arc<ClientContext>,
+    params: ParamsOfSecp256k1KeyPairFromSecret,
+) -> ClientResult<KeyPair> {
+    Ok(key_pair(&decode_secret(&params.secret)?))
+}
+
+//---------------------------------------------------------------------------------- secp256k1_sign
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfSecp256k1Sign {
+    /// Data to be signed. Encoded with `base64`.
+    pub unsigned: String,
+    /// Secret key - 64 symbols hex string
+    pub secret: String,
+    /// `true` if `unsigned` is already a 32-byte hash. Otherwise Keccak-256 hash
+    /// of `unsigned` is signed. Default is `false`.
+    pub prehashed: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfSecp256k1Sign {
+    /// Recoverable signature `r || s || v` where `v` is the recovery id (0 or 1).
+    /// Encoded with `hex`.
+    pub signature: String,
+}
+
+/// Signs data with secp256k1 ECDSA producing a recoverable signature.
+#[api_function]
+pub fn secp256k1_sign(
+    _context: Arc<ClientContext>,
+    params: ParamsOfSecp256k1Sign,
+) -> ClientResult<ResultOfSecp256k1Sign> {
+    let secret = decode_secret(&params.secret)?;
+    let (signature, recovery_id) =
+        libsecp256k1::sign(&message(&params.unsigned, params.prehashed)?, &secret);
+    Ok(ResultOfSecp256k1Sign {
+        signature: hex::encode(encode_signature(&signature, &recovery_id)),
+    })
+}
+
+//-------------------------------------------------------------------------------- secp256k1_verify
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfSecp256k1Verify {
+    /// Signed data. Encoded with `base64`.
+    pub unsigned: String,
+    /// Signature `r || s || v`. Encoded with `hex`.
+    /// Ethereum style recovery id (27 or 28) is also accepted.
+    pub signature: String,
+    /// Signer's public key in compressed or uncompressed form. Encoded with `hex`.
+    pub public: String,
+    /// `true` if `unsigned` is already a 32-byte hash. Default is `false`.
+    pub prehashed: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfSecp256k1Verify {
+    /// `true` if verification succeeded or `false` if it failed
+    pub succeeded: bool,
+}
+
+/// Verifies secp256k1 ECDSA signature.
+#[api_function]
+pub fn secp256k1_verify(
+    _context: Arc<ClientContext>,
+    params: ParamsOfSecp256k1Verify,
+) -> ClientResult<ResultOfSecp256k1Verify> {
+    let public = decode_public(&params.public)?;
+    let (signature, _) = decode_signature(&params.signature)?;
+    let message = message(&params.unsigned, params.prehashed)?;
+    Ok(ResultOfSecp256k1Verify {
+        succeeded: libsecp256k1::verify(&message, &signature, &public),
+    })
+}
+
+//-------------------------------------------------------------------- secp256k1_recover_public_key
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfSecp256k1RecoverPublicKey {
+    /// Signed data. Encoded with `base64`.
+    pub unsigned: String,
+    /// Recoverable signature `r || s || v`. Encoded with `hex`.
+    pub signature: String,
+    /// `true` if `unsigned` is already a 32-byte hash. Default is `false`.
+    pub prehashed: Option<bool>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfSecp256k1RecoverPublicKey {
+    /// Signer's public key in compressed form. Encoded with `hex`.
+    pub public: String,
+}
+
+/// Recovers signer's public key from the recoverable secp256k1 signature.
+#[api_function]
+pub fn secp256k1_recover_public_key(
+    _context: Arc<ClientContext>,
+    params: ParamsOfSecp256k1RecoverPublicKey,
+) -> ClientResult<ResultOfSecp256k1RecoverPublicKey> {
+    let (signature, recovery_id) = decode_signature(&params.signature)?;
+    let message = message(&params.unsigned, params.prehashed)?;
+    let public = libsecp256k1::recover(&message, &signature, &recovery_id).map_err(map_error)?;
+    Ok(ResultOfSecp256k1RecoverPublicKey {
+        public: hex::encode(&public.serialize_compressed()[..]),
+    })
+}
+
+//---------------------------------------------------------------------------- secp256k1_eth_address
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfSecp256k1EthAddress {
+    /// Public key in compressed or uncompressed form. Encoded with `hex`.
+    pub public: String,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfSecp256k1EthAddress {
+    /// `0x` prefixed address with EIP-55 mixed-case checksum
+    pub address: String,
+}
+
+/// Calculates Ethereum style address of the secp256k1 public key.
+///
+/// The address is the last 20 bytes of Keccak-256 hash of the uncompressed public key.
+#[api_function]
+pub fn secp256k1_eth_address(
+    _context: Arc<ClientContext>,
+    params: ParamsOfSecp256k1EthAddress,
+) -> ClientResult<ResultOfSecp256k1EthAddress> {
+    let public = decode_public(&params.public)?;
+    let address = hex::encode(&keccak256(&public.serialize()[1..])[12..]);
+    let hash = keccak256(address.as_bytes());
+    let checksummed: String = address
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = if i % 2 == 0 { hash[i / 2] >> 4 } else { hash[i / 2] & 0x0F };
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+    Ok(ResultOfSecp256k1EthAddress {
+        address: format!("0x{}", checksummed),
+    })
+}
+
+//---------------------------------------------------------------------- get_secp256k1_signing_box
+
+/// Creates a signing box with the secp256k1 key pair.
+///
+/// The signing box signs Keccak-256 hash of the data and returns recoverable
+/// signatures `r || s || v`. Its public key is returned in compressed form.
+#[api_function]
+pub async fn get_secp256k1_signing_box(
+    context: Arc<ClientContext>,
+    params: KeyPair,
+) -> ClientResult<RegisteredSigningBox> {
+    let secret = decode_secret(&params.secret)?;
+    if !params.public.is_empty()
+        && decode_public(&params.public)? != PublicKey::from_secret_key(&secret)
+    {
+        return Err(crypto::Error::invalid_public_key(
+            "public key does not match the secret key",
+            &params.public,
+        ));
+    }
+    register_signing_box(context, Secp256k1SigningBox::new(secret)).await
+}
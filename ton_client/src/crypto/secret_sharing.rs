@@ -0,0 +1,435 @@
+/*
+* Copyright 2018-2021 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use crate::client::ClientContext;
+use crate::crypto;
+use crate::crypto::internal::sha256;
+use crate::crypto::mnemonic::{check_phrase, mnemonics};
+use crate::encoding::hex_decode;
+use crate::error::ClientResult;
+use hmac::{Hmac, Mac, NewMac};
+use rand::RngCore;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::sync::Arc;
+use zeroize::Zeroize;
+
+const MAX_SHARE_COUNT: u8 = 16;
+const SECRET_SIZE: usize = 32;
+const CHECKSUM_SIZE: usize = 4;
+const DIGEST_SIZE: usize = 4;
+const DIGEST_INDEX: u8 = 254;
+const SECRET_INDEX: u8 = 255;
+const KIND_SECRET: u8 = 0;
+const KIND_MNEMONIC: u8 = 1;
+const HEADER_SIZE: usize = 5;
+
+//------------------------------------------------------------------------------------ split_secret
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfSplitSecret {
+    /// Seed phrase to be split. Either `phrase` or `secret` must be specified.
+    pub phrase: Option<String>,
+    /// 256-bit secret to be split. Must be encoded with `hex`.
+    pub secret: Option<String>,
+    /// Dictionary identifier of the seed phrase. Shares are encoded with the same dictionary.
+    pub dictionary: Option<u8>,
+    /// Word count of the seed phrase
+    pub word_count: Option<u8>,
+    /// Number of shares required to restore the secret
+    pub threshold: u8,
+    /// Total number of shares. Must not exceed 16.
+    pub share_count: u8,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfSplitSecret {
+    /// Shares encoded as mnemonic word lists
+    pub shares: Vec<String>,
+}
+
+/// Splits a seed phrase or a 256-bit secret into shares.
+///
+/// Uses threshold secret sharing over GF(256) in the style of SLIP-0039: any `threshold`
+/// shares restore the secret, while fewer shares reveal nothing about it.
+/// Each share is encoded as a list of words from the dictionary and carries a checksum,
+/// so a corrupt share is detected before reconstruction. A digest of the secret is shared
+/// along with it, so a combination of shares from different splits is detected too.
+#[api_function]
+pub fn split_secret(
+    context: Arc<ClientContext>,
+    params: ParamsOfSplitSecret,
+) -> ClientResult<ResultOfSplitSecret> {
+    let dictionary = params
+        .dictionary
+        .unwrap_or(context.config.crypto.mnemonic_dictionary);
+    let words = dictionary_words(&context, dictionary)?;
+
+    let (kind, mut secret) = match (&params.phrase, &params.secret) {
+        (Some(phrase), None) => {
+            let mnemonic = mnemonics(&context.config.crypto, Some(dictionary), params.word_count)?;
+            check_phrase(&*mnemonic, phrase)?;
+            let indices = phrase_to_indices(&words, phrase)
+                .ok_or_else(|| crypto::Error::bip39_invalid_phrase(phrase))?;
+            (KIND_MNEMONIC, indices_to_bytes(&indices))
+        }
+        (None, Some(secret)) => {
+            let secret = hex_decode(secret)?;
+            if secret.len() != SECRET_SIZE {
+                return Err(crypto::Error::invalid_key_size(secret.len(), &[SECRET_SIZE]));
+            }
+            (KIND_SECRET, secret)
+        }
+        _ => {
+            return Err(crypto::Error::secret_sharing_failed(
+                "either `phrase` or `secret` must be specified",
+            ))
+        }
+    };
+
+    if params.threshold == 0
+        || params.threshold > params.share_count
+        || params.share_count > MAX_SHARE_COUNT
+    {
+        return Err(crypto::Error::secret_sharing_failed(format!(
+            "invalid threshold {} of {} shares, at most {} shares are allowed",
+            params.threshold, params.share_count, MAX_SHARE_COUNT
+        )));
+    }
+
+    let mut identifier = [0u8; 2];
+    rand::thread_rng().fill_bytes(&mut identifier);
+    let values = split(params.threshold, params.share_count, &secret);
+    secret.zeroize();
+
+    let shares = values
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut value)| {
+            let mut share = Vec::with_capacity(HEADER_SIZE + value.len() + CHECKSUM_SIZE);
+            share.extend_from_slice(&identifier);
+            share.push(kind << 4 | dictionary);
+            share.push((params.threshold - 1) << 4 | index as u8);
+            share.push(value.len() as u8);
+            share.extend_from_slice(&value);
+            share.extend_from_slice(&sha256(&share)[..CHECKSUM_SIZE]);
+            value.zeroize();
+
+            let phrase = bytes_to_indices(&share)
+                .into_iter()
+                .map(|index| words[index as usize].as_str())
+                .collect::<Vec<&str>>()
+                .join(" ");
+            share.zeroize();
+            phrase
+        })
+        .collect();
+
+    Ok(ResultOfSplitSecret { shares })
+}
+
+//---------------------------------------------------------------------------------- combine_shares
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfCombineShares {
+    /// Shares produced by `split_secret`. At least `threshold` shares are required.
+    pub shares: Vec<String>,
+    /// Dictionary identifier the shares are encoded with
+    pub dictionary: Option<u8>,
+}
+
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfCombineShares {
+    /// Restored seed phrase if a seed phrase was split
+    pub phrase: Option<String>,
+    /// Dictionary identifier of the restored seed phrase
+    pub dictionary: Option<u8>,
+    /// Restored secret if a 256-bit secret was split. Encoded with `hex`.
+    pub secret: Option<String>,
+}
+
+/// Restores the seed phrase or the secret from shares produced by `split_secret`.
+///
+/// Checksum of every share is verified first, then shares are checked to belong to the same
+/// split. The restored secret is verified against the shared digest.
+#[api_function]
+pub fn combine_shares(
+    context: Arc<ClientContext>,
+    params: ParamsOfCombineShares,
+) -> ClientResult<ResultOfCombineShares> {
+    let dictionary = params
+        .dictionary
+        .unwrap_or(context.config.crypto.mnemonic_dictionary);
+    let words = dictionary_words(&context, dictionary)?;
+
+    let mut shares = Vec::new();
+    for (index, phrase) in params.shares.iter().enumerate() {
+        shares.push(Share::decode(&words, phrase).map_err(|err| {
+            crypto::Error::invalid_share(index, err)
+        })?);
+    }
+    let first = shares
+        .first()
+        .ok_or_else(|| crypto::Error::secret_sharing_failed("no shares specified"))?;
+    for (index, share) in shares.iter().enumerate() {
+        if share.identifier != first.identifier
+            || share.kind != first.kind
+            || share.dictionary != first.dictionary
+            || share.threshold != first.threshold
+            || share.value.len() != first.value.len()
+        {
+            return Err(crypto::Error::invalid_share(
+                index,
+                "share belongs to a different split",
+            ));
+        }
+        if shares[..index].iter().any(|other| other.index == share.index) {
+            return Err(crypto::Error::invalid_share(index, "duplicate share"));
+        }
+    }
+    if shares.len() < first.threshold as usize {
+        return Err(crypto::Error::secret_sharing_failed(format!(
+            "{} shares are required, {} specified",
+            first.threshold,
+            shares.len()
+        )));
+    }
+
+    let points: Vec<(u8, &[u8])> = shares
+        .iter()
+        .take(first.threshold as usize)
+        .map(|share| (share.index, share.value.as_slice()))
+        .collect();
+    let mut secret = combine(&points)?;
+
+    let result = if first.kind == KIND_MNEMONIC {
+        let word_count = secret.len() * 8 / 11;
+        let original_words = dictionary_words(&context, first.dictionary)?;
+        let phrase = bytes_to_indices(&secret)
+            .into_iter()
+            .take(word_count)
+            .map(|index| original_words[index as usize].as_str())
+            .collect::<Vec<&str>>()
+            .join(" ");
+        ResultOfCombineShares {
+            phrase: Some(phrase),
+            dictionary: Some(first.dictionary),
+            secret: None,
+        }
+    } else {
+        ResultOfCombineShares {
+            phrase: None,
+            dictionary: None,
+            secret: Some(hex::encode(&secret)),
+        }
+    };
+    secret.zeroize();
+    Ok(result)
+}
+
+// Internals
+
+struct Share {
+    identifier: [u8; 2],
+    kind: u8,
+    dictionary: u8,
+    threshold: u8,
+    index: u8,
+    value: Vec<u8>,
+}
+
+impl Share {
+    fn decode(words: &[String], phrase: &str) -> Result<Self, &'static str> {
+        let indices = phrase_to_indices(words, phrase).ok_or("unknown word")?;
+        let mut bytes = indices_to_bytes(&indices);
+        if bytes.len() < HEADER_SIZE {
+            return Err("share is too short");
+        }
+        let size = HEADER_SIZE + bytes[4] as usize + CHECKSUM_SIZE;
+        if bytes.len() < size || bytes[size..].iter().any(|byte| *byte != 0) {
+            return Err("invalid share length");
+        }
+        bytes.truncate(size);
+        let (data, checksum) = bytes.split_at(size - CHECKSUM_SIZE);
+        if sha256(data)[..CHECKSUM_SIZE] != *checksum {
+            return Err("invalid share checksum");
+        }
+        let share = Share {
+            identifier: [data[0], data[1]],
+            kind: data[2] >> 4,
+            dictionary: data[2] & 0x0F,
+            threshold: (data[3] >> 4) + 1,
+            index: data[3] & 0x0F,
+            value: data[HEADER_SIZE..].to_vec(),
+        };
+        bytes.zeroize();
+        if share.kind != KIND_SECRET && share.kind != KIND_MNEMONIC {
+            return Err("unknown secret kind");
+        }
+        Ok(share)
+    }
+}
+
+impl Drop for Share {
+    fn drop(&mut self) {
+        self.value.zeroize();
+    }
+}
+
+fn dictionary_words(context: &ClientContext, dictionary: u8) -> ClientResult<Vec<String>> {
+    Ok(mnemonics(&context.config.crypto, Some(dictionary), None)?
+        .get_words()?
+        .split(' ')
+        .map(|word| word.to_string())
+        .collect())
+}
+
+fn phrase_to_indices(words: &[String], phrase: &str) -> Option<Vec<u16>> {
+    let positions: HashMap<&str, u16> = words
+        .iter()
+        .enumerate()
+        .map(|(index, word)| (word.as_str(), index as u16))
+        .collect();
+    phrase
+        .split_whitespace()
+        .map(|word| positions.get(word).cloned())
+        .collect()
+}
+
+/// Packs 11-bit word indices into bytes, most significant bit first.
+fn indices_to_bytes(indices: &[u16]) -> Vec<u8> {
+    let mut bytes = vec![0u8; (indices.len() * 11 + 7) / 8];
+    for (i, index) in indices.iter().enumerate() {
+        for bit in 0..11 {
+            if index & (1 << (10 - bit)) != 0 {
+                let offset = i * 11 + bit;
+                bytes[offset / 8] |= 0x80 >> (offset % 8);
+            }
+        }
+    }
+    bytes
+}
+
+/// Splits bytes into 11-bit word indices, most significant bit first.
+/// The last index is padded with zero bits.
+fn bytes_to_indices(bytes: &[u8]) -> Vec<u16> {
+    let mut indices = vec![0u16; (bytes.len() * 8 + 10) / 11];
+    for offset in 0..bytes.len() * 8 {
+        if bytes[offset / 8] & (0x80 >> (offset % 8)) != 0 {
+            indices[offset / 11] |= 1 << (10 - offset % 11);
+        }
+    }
+    indices
+}
+
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    while b != 0 {
+        if b & 1 != 0 {
+            product ^= a;
+        }
+        let carry = a & 0x80;
+        a <<= 1;
+        if carry != 0 {
+            a ^= 0x1B;
+        }
+        b >>= 1;
+    }
+    product
+}
+
+fn gf_inv(a: u8) -> u8 {
+    // a^254 = a^-1 in GF(256)
+    let mut result = 1;
+    let mut base = a;
+    let mut exp = 254u8;
+    while exp != 0 {
+        if exp & 1 != 0 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Evaluates at `x` the polynomial passing through the `points`
+fn interpolate(points: &[(u8, &[u8])], x: u8) -> Vec<u8> {
+    if let Some((_, value)) = points.iter().find(|(xi, _)| *xi == x) {
+        return value.to_vec();
+    }
+    let mut result = vec![0u8; points[0].1.len()];
+    for (i, (xi, value)) in points.iter().enumerate() {
+        let mut basis = 1u8;
+        for (j, (xj, _)) in points.iter().enumerate() {
+            if i != j {
+                basis = gf_mul(basis, gf_mul(x ^ xj, gf_inv(xi ^ xj)));
+            }
+        }
+        for (byte, value_byte) in result.iter_mut().zip(value.iter()) {
+            *byte ^= gf_mul(basis, *value_byte);
+        }
+    }
+    result
+}
+
+fn secret_digest(random: &[u8], secret: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(random).unwrap();
+    mac.update(secret);
+    mac.finalize().into_bytes()[..DIGEST_SIZE].to_vec()
+}
+
+fn split(threshold: u8, share_count: u8, secret: &[u8]) -> Vec<Vec<u8>> {
+    if threshold == 1 {
+        return (0..share_count).map(|_| secret.to_vec()).collect();
+    }
+    let mut rng = rand::thread_rng();
+    let mut random_shares = Vec::new();
+    for _ in 0..threshold - 2 {
+        let mut value = vec![0u8; secret.len()];
+        rng.fill_bytes(&mut value);
+        random_shares.push(value);
+    }
+    let mut random = vec![0u8; secret.len() - DIGEST_SIZE];
+    rng.fill_bytes(&mut random);
+    let mut digest = secret_digest(&random, secret);
+    digest.extend_from_slice(&random);
+
+    let mut points: Vec<(u8, &[u8])> = random_shares
+        .iter()
+        .enumerate()
+        .map(|(index, value)| (index as u8, value.as_slice()))
+        .collect();
+    points.push((DIGEST_INDEX, &digest[..]));
+    points.push((SECRET_INDEX, secret));
+
+    let shares = (0..share_count)
+        .map(|index| interpolate(&points, index))
+        .collect();
+    digest.zeroize();
+    shares
+}
+
+fn combine(points: &[(u8, &[u8])]) -> ClientResult<Vec<u8>> {
+    if points.len() == 1 {
+        return Ok(points[0].1.to_vec());
+    }
+    let secret = interpolate(points, SECRET_INDEX);
+    let digest = interpolate(points, DIGEST_INDEX);
+    if secret_digest(&digest[DIGEST_SIZE..], &secret)[..] != digest[..DIGEST_SIZE] {
+        return Err(crypto::Error::secret_sharing_failed(
+            "restored secret does not match its digest",
+        ));
+    }
+    Ok(secret)
+}
@@ -5,7 +5,9 @@ use crate::crypto::hdkey::{
     ParamsOfHDKeyDeriveFromXPrv, ParamsOfHDKeyDeriveFromXPrvPath, ParamsOfHDKeyPublicFromXPrv,
     ParamsOfHDKeySecretFromXPrv, ParamsOfHDKeyXPrvFromMnemonic, ResultOfHDKeyDeriveFromXPrv,
     ResultOfHDKeyDeriveFromXPrvPath, ResultOfHDKeyPublicFromXPrv, ResultOfHDKeySecretFromXPrv,
-    ResultOfHDKeyXPrvFromMnemonic,
+    ResultOfHDKeyXPrvFromMnemonic, ParamsOfSlip10DeriveFromXPrv, ParamsOfSlip10DeriveFromXPrvPath,
+    ParamsOfSlip10XPrvFromMnemonic, ParamsOfSlip10XPrvFromSeed, ResultOfSlip10DeriveFromXPrv,
+    ResultOfSlip10DeriveFromXPrvPath, ResultOfSlip10XPrvFromMnemonic, ResultOfSlip10XPrvFromSeed,
 };
 use crate::crypto::keys::{
     KeyPair, ParamsOfConvertPublicKeyToTonSafeFormat, ParamsOfSign, ParamsOfVerifySignature,
@@ -17,8 +19,9 @@ use crate::crypto::math::{
 };
 use crate::crypto::mnemonic::{
     ParamsOfMnemonicDeriveSignKeys, ParamsOfMnemonicFromEntropy, ParamsOfMnemonicFromRandom,
-    ParamsOfMnemonicVerify, ParamsOfMnemonicWords, ResultOfMnemonicFromEntropy,
-    ResultOfMnemonicFromRandom, ResultOfMnemonicVerify, ResultOfMnemonicWords,
+    ParamsOfMnemonicSuggest, ParamsOfMnemonicVerify, ParamsOfMnemonicWords,
+    ResultOfMnemonicFromEntropy, ResultOfMnemonicFromRandom, ResultOfMnemonicSuggest,
+    ResultOfMnemonicVerify, ResultOfMnemonicWords,
 };
 use crate::crypto::nacl::{
     ParamsOfNaclBox, ParamsOfNaclBoxKeyPairFromSecret, ParamsOfNaclBoxOpen, ParamsOfNaclSecretBox,
@@ -27,6 +30,7 @@ use crate::crypto::nacl::{
     ResultOfNaclSignDetached, ResultOfNaclSignOpen,
 };
 use crate::crypto::{ParamsOfChaCha20, ResultOfChaCha20};
+use crate::error::ClientResult;
 use crate::json_interface::crypto::{ParamsOfAppSigningBox, ResultOfAppSigningBox};
 use crate::tests::TestClient;
 use super::*;
@@ -622,6 +626,84 @@ fn mnemonic() {
     );
 }
 
+#[test]
+fn mnemonic_suggest() {
+    let client = TestClient::new();
+
+    let result: ResultOfMnemonicSuggest = client
+        .request(
+            "crypto.mnemonic_suggest",
+            ParamsOfMnemonicSuggest {
+                phrase: "abandon math mimik master filter design carbon crystal rookie group knife aban"
+                    .into(),
+                dictionary: None,
+                max_suggestions: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(result.dictionary, 1);
+    assert_eq!(result.valid, false);
+    assert_eq!(result.words.len(), 12);
+    assert!(result.words[0].known);
+    assert!(!result.words[2].known);
+    assert!(result.words[2].suggestions.contains(&"mimic".to_string()));
+    assert_eq!(result.words[11].suggestions[0], "abandon");
+    // two unknown words, so single word corrections are impossible
+    assert!(result.corrections.is_empty());
+
+    let result: ResultOfMnemonicSuggest = client
+        .request(
+            "crypto.mnemonic_suggest",
+            ParamsOfMnemonicSuggest {
+                phrase: "abandon math mimik master filter design carbon crystal rookie group knife young"
+                    .into(),
+                dictionary: Some(1),
+                max_suggestions: Some(3),
+            },
+        )
+        .unwrap();
+    assert_eq!(result.corrections[0].index, 2);
+    assert_eq!(result.corrections[0].word, "mimic");
+    assert_eq!(
+        result.corrections[0].phrase,
+        "abandon math mimic master filter design carbon crystal rookie group knife young"
+    );
+    assert!(result.corrections.len() <= 3);
+
+    let ton_phrase = "unit follow zone decline glare flower crisp vocal adapt magic much mesh cherry teach mechanic rain float vicious solution assume hedgehog rail sort chuckle";
+    let result: ResultOfMnemonicSuggest = client
+        .request(
+            "crypto.mnemonic_suggest",
+            ParamsOfMnemonicSuggest {
+                phrase: ton_phrase.into(),
+                dictionary: None,
+                max_suggestions: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(result.dictionary, 0);
+    assert_eq!(result.valid, true);
+    assert!(result.corrections.is_empty());
+
+    // misspelled word is present in the dictionary
+    let result: ResultOfMnemonicSuggest = client
+        .request(
+            "crypto.mnemonic_suggest",
+            ParamsOfMnemonicSuggest {
+                phrase: ton_phrase.replace("vocal", "local"),
+                dictionary: Some(0),
+                max_suggestions: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(result.valid, false);
+    assert!(result.words.iter().all(|word| word.known));
+    assert!(result
+        .corrections
+        .iter()
+        .any(|correction| correction.index == 7 && correction.phrase == ton_phrase));
+}
+
 #[test]
 fn hdkey() {
     TestClient::init_log();
@@ -743,6 +825,165 @@ fn hdkey() {
     );
 }
 
+#[test]
+fn slip10() {
+    let client = TestClient::new();
+
+    // SLIP-0010 test vector 1 for ed25519
+    let master: ResultOfSlip10XPrvFromSeed = client
+        .request(
+            "crypto.slip10_xprv_from_seed",
+            ParamsOfSlip10XPrvFromSeed {
+                seed: "000102030405060708090a0b0c0d0e0f".into(),
+            },
+        )
+        .unwrap();
+    assert_eq!(master.xprv, "eprv423G5rKnJnGfkA4zFxJXDE5YbBx67S3tfDFT6uTNXumJDMPByizUHoxaNimaFrzxMntsE41Kr4p3bSXNkByFQe6GqtAS2Sv7S2n3Rm5PRBV");
+
+    let result: ResultOfHDKeySecretFromXPrv = client
+        .request(
+            "crypto.hdkey_secret_from_xprv",
+            ParamsOfHDKeySecretFromXPrv {
+                xprv: master.xprv.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        result.secret,
+        "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+    );
+
+    let child: ResultOfSlip10DeriveFromXPrv = client
+        .request(
+            "crypto.slip10_derive_from_xprv",
+            ParamsOfSlip10DeriveFromXPrv {
+                xprv: master.xprv.clone(),
+                child_index: 0,
+            },
+        )
+        .unwrap();
+    let result: ResultOfHDKeyPublicFromXPrv = client
+        .request(
+            "crypto.hdkey_public_from_xprv",
+            ParamsOfHDKeyPublicFromXPrv {
+                xprv: child.xprv.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        result.public,
+        "8c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c"
+    );
+
+    let derived: ResultOfSlip10DeriveFromXPrvPath = client
+        .request(
+            "crypto.slip10_derive_from_xprv_path",
+            ParamsOfSlip10DeriveFromXPrvPath {
+                xprv: master.xprv.clone(),
+                path: "m/0'/1'/2'/2'/1000000000'".into(),
+            },
+        )
+        .unwrap();
+    assert_eq!(derived.xprv, "eprv4D2Fb5fbDfpwefXkPf6AoLmU1CoD6DYFMubxBkxmaWkeqwEk1aW9tJTAJJkX52XdKBD9xdhY2peQdYrQr2TXpT4GqymEwEVvJ2ZVdZtkgQk");
+
+    let result: ResultOfHDKeySecretFromXPrv = client
+        .request(
+            "crypto.hdkey_secret_from_xprv",
+            ParamsOfHDKeySecretFromXPrv {
+                xprv: derived.xprv.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        result.secret,
+        "8f94d394a8e8fd6b1bc2f3f49f5c47e385281d5c17e65324b0f62483e37e8793"
+    );
+
+    let result: ResultOfHDKeyPublicFromXPrv = client
+        .request(
+            "crypto.hdkey_public_from_xprv",
+            ParamsOfHDKeyPublicFromXPrv {
+                xprv: derived.xprv.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        result.public,
+        "3c24da049451555d51a7014a37337aa4e12d41e485abccfa46b47dfb2af54b7a"
+    );
+
+    let error = client
+        .request::<_, ResultOfSlip10DeriveFromXPrvPath>(
+            "crypto.slip10_derive_from_xprv_path",
+            ParamsOfSlip10DeriveFromXPrvPath {
+                xprv: master.xprv.clone(),
+                path: "m/0'/1".into(),
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::Bip32InvalidDerivePath as u32);
+
+    // SLIP-0010 and BIP-32 keys are not interchangeable
+    let error = client
+        .request::<_, ResultOfHDKeyDeriveFromXPrv>(
+            "crypto.hdkey_derive_from_xprv",
+            ParamsOfHDKeyDeriveFromXPrv {
+                xprv: master.xprv.clone(),
+                child_index: 0,
+                hardened: true,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::Bip32InvalidKey as u32);
+    let error = client
+        .request::<_, ResultOfSlip10DeriveFromXPrv>(
+            "crypto.slip10_derive_from_xprv",
+            ParamsOfSlip10DeriveFromXPrv {
+                xprv: "xprv9s21ZrQH143K25JhKqEwvJW7QAiVvkmi4WRenBZanA6kxHKtKAQQKwZG65kCyW5jWJ8NY9e3GkRoistUjjcpHNsGBUv94istDPXvqGNuWpC".into(),
+                child_index: 0,
+            },
+        )
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::Bip32InvalidKey as u32);
+
+    // BIP-39 seed phrase
+    let master: ResultOfSlip10XPrvFromMnemonic = client
+        .request(
+            "crypto.slip10_xprv_from_mnemonic",
+            ParamsOfSlip10XPrvFromMnemonic {
+                dictionary: Some(1),
+                word_count: Some(12),
+                phrase:
+                    "abuse boss fly battle rubber wasp afraid hamster guide essence vibrant tattoo"
+                        .into(),
+            },
+        )
+        .unwrap();
+    assert_eq!(master.xprv, "eprv423G5rKnJnGfjzdtvu2cYteaKXP2CqTpCPERvY3yK31XGXf7av4YQer9vrKfvMTtWqU5VSGn1qYwWkUfU9tYCbTMcMa1Dby1YCwmRVXVFzE");
+
+    let derived: ResultOfSlip10DeriveFromXPrvPath = client
+        .request(
+            "crypto.slip10_derive_from_xprv_path",
+            ParamsOfSlip10DeriveFromXPrvPath {
+                xprv: master.xprv,
+                path: "m/44'/396'/0'".into(),
+            },
+        )
+        .unwrap();
+    let result: ResultOfHDKeyPublicFromXPrv = client
+        .request(
+            "crypto.hdkey_public_from_xprv",
+            ParamsOfHDKeyPublicFromXPrv {
+                xprv: derived.xprv,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        result.public,
+        "4be1d559aab1f39e866eb045ed3ea7d778db1d00148ffc69e27e20afe80a3c17"
+    );
+}
+
 #[tokio::test(core_threads = 2)]
 async fn test_signing_box() {
     let client = std::sync::Arc::new(TestClient::new());
@@ -792,6 +1033,34 @@ async fn test_signing_box() {
                         ResultOfAppSigningBox::Sign { signature: result.signature }
                     ).await;
                 }
+                ParamsOfAppSigningBox::SignBatch { unsigned } => {
+                    let result: ResultOfSigningBoxSignBatch = client
+                        .request_async(
+                            "crypto.signing_box_sign_batch",
+                            ParamsOfSigningBoxSignBatch {
+                                signing_box: keys_box_handle.into(),
+                                unsigned,
+                            },
+                        ).await.unwrap();
+                    client.resolve_app_request(
+                        request.app_request_id,
+                        ResultOfAppSigningBox::SignBatch { signatures: result.signatures }
+                    ).await;
+                }
+                ParamsOfAppSigningBox::SignWithContext { unsigned, .. } => {
+                    let result: ResultOfSigningBoxSign = client
+                        .request_async(
+                            "crypto.signing_box_sign",
+                            ParamsOfSigningBoxSign {
+                                signing_box: keys_box_handle.into(),
+                                unsigned,
+                            },
+                        ).await.unwrap();
+                    client.resolve_app_request(
+                        request.app_request_id,
+                        ResultOfAppSigningBox::Sign { signature: result.signature }
+                    ).await;
+                }
             }
         });
         futures::future::ready(())
@@ -828,13 +1097,25 @@ async fn test_signing_box() {
         .request(
             "crypto.sign",
             ParamsOfSign {
-                unsigned,
+                unsigned: unsigned.clone(),
                 keys,
             },
         ).unwrap();
 
     assert_eq!(box_sign.signature, keys_sign.signature);
 
+    let batch_sign: ResultOfSigningBoxSignBatch = client
+        .request_async(
+            "crypto.signing_box_sign_batch",
+            ParamsOfSigningBoxSignBatch {
+                signing_box: external_box.handle.clone(),
+                unsigned: vec![base64::encode("First"), unsigned.clone(), base64::encode("")],
+            },
+        ).await.unwrap();
+
+    assert_eq!(batch_sign.signatures.len(), 3);
+    assert_eq!(batch_sign.signatures[1], keys_sign.signature);
+
     let _: () = client
         .request_async(
             "crypto.remove_signing_box",
@@ -952,3 +1233,809 @@ async fn test_aes_encryption_box() {
         "src/crypto/test_data/cbc-aes256.ciphertext.padded.bin"
     ).await;
 }
+
+async fn encrypt_stream(
+    client: &TestClient,
+    encryption_box: &EncryptionBoxHandle,
+    data: &[u8],
+    chunk_size: u32,
+    part_size: usize,
+) -> Vec<u8> {
+    let init: ResultOfEncryptionBoxEncryptInit = client
+        .request_async(
+            "crypto.encryption_box_encrypt_init",
+            ParamsOfEncryptionBoxEncryptInit {
+                encryption_box: encryption_box.clone(),
+                chunk_size: Some(chunk_size),
+            },
+        )
+        .await
+        .unwrap();
+    let mut encrypted = base64::decode(&init.data).unwrap();
+    for part in data.chunks(part_size) {
+        let result: ResultOfEncryptionBoxEncryptUpdate = client
+            .request_async(
+                "crypto.encryption_box_encrypt_update",
+                ParamsOfEncryptionBoxEncryptUpdate {
+                    session: init.session.clone(),
+                    data: base64::encode(part),
+                },
+            )
+            .await
+            .unwrap();
+        encrypted.extend(base64::decode(&result.data).unwrap());
+    }
+    let result: ResultOfEncryptionBoxEncryptFinish = client
+        .request_async(
+            "crypto.encryption_box_encrypt_finish",
+            ParamsOfEncryptionBoxEncryptFinish { session: init.session },
+        )
+        .await
+        .unwrap();
+    encrypted.extend(base64::decode(&result.data).unwrap());
+    encrypted
+}
+
+async fn decrypt_stream(
+    client: &TestClient,
+    encryption_box: &EncryptionBoxHandle,
+    encrypted: &[u8],
+    part_size: usize,
+) -> ClientResult<Vec<u8>> {
+    let session = client
+        .request_async::<_, ResultOfEncryptionBoxDecryptInit>(
+            "crypto.encryption_box_decrypt_init",
+            ParamsOfEncryptionBoxDecryptInit { encryption_box: encryption_box.clone() },
+        )
+        .await?
+        .session;
+    let mut decrypted = Vec::new();
+    for part in encrypted.chunks(part_size) {
+        let result: ResultOfEncryptionBoxDecryptUpdate = client
+            .request_async(
+                "crypto.encryption_box_decrypt_update",
+                ParamsOfEncryptionBoxDecryptUpdate {
+                    session: session.clone(),
+                    data: base64::encode(part),
+                },
+            )
+            .await?;
+        decrypted.extend(base64::decode(&result.data).unwrap());
+    }
+    let result: ResultOfEncryptionBoxDecryptFinish = client
+        .request_async(
+            "crypto.encryption_box_decrypt_finish",
+            ParamsOfEncryptionBoxDecryptFinish { session },
+        )
+        .await?;
+    decrypted.extend(base64::decode(&result.data).unwrap());
+    Ok(decrypted)
+}
+
+#[tokio::test(core_threads = 2)]
+async fn test_encryption_box_stream() {
+    let client = TestClient::new();
+    let key = hex::encode(&std::fs::read("src/crypto/test_data/aes256.key.bin").unwrap());
+    let iv = hex::encode(&std::fs::read("src/crypto/test_data/aes.iv.bin").unwrap());
+    let encryption_box = client
+        .request_async::<_, RegisteredEncryptionBox>(
+            "crypto.create_encryption_box",
+            ParamsOfCreateEncryptionBox {
+                algorithm: EncryptionAlgorithm::AES(AesParams {
+                    key,
+                    iv: Some(iv),
+                    mode: CipherMode::CBC,
+                }),
+            },
+        )
+        .await
+        .unwrap()
+        .handle;
+
+    let data: Vec<u8> = (0..1000u32).map(|i| (i * 7 % 251) as u8).collect();
+    let encrypted = encrypt_stream(&client, &encryption_box, &data, 64, 100).await;
+    assert_eq!(&encrypted[..4], b"TONS");
+
+    for part_size in &[1, 80, 81, 5000] {
+        let decrypted = decrypt_stream(&client, &encryption_box, &encrypted, *part_size)
+            .await
+            .unwrap();
+        assert_eq!(decrypted, data);
+    }
+
+    // data size is a multiple of the chunk size and empty data
+    for data in &[data[..640].to_vec(), vec![]] {
+        let encrypted = encrypt_stream(&client, &encryption_box, data, 64, 64).await;
+        let decrypted = decrypt_stream(&client, &encryption_box, &encrypted, 64)
+            .await
+            .unwrap();
+        assert_eq!(&decrypted, data);
+    }
+
+    // modified chunk
+    let mut modified = encrypted.clone();
+    let last = modified.len() - 1;
+    modified[last - 100] ^= 1;
+    let error = decrypt_stream(&client, &encryption_box, &modified, 100)
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::DecryptDataError as u32);
+
+    // truncated stream: the last chunk is dropped
+    let truncated = &encrypted[..encrypted.len() - (1000 % 64 + 16)];
+    let error = decrypt_stream(&client, &encryption_box, truncated, 100)
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, ErrorCode::DecryptDataError as u32);
+
+    // encryption session can not be used for decryption
+    let init: ResultOfEncryptionBoxEncryptInit = client
+        .request_async(
+            "crypto.encryption_box_encrypt_init",
+            ParamsOfEncryptionBoxEncryptInit {
+                encryption_box: encryption_box.clone(),
+                chunk_size: None,
+            },
+        )
+        .await
+        .unwrap();
+    let error = client
+        .request_async::<_, ResultOfEncryptionBoxDecryptFinish>(
+            "crypto.encryption_box_decrypt_finish",
+            ParamsOfEncryptionBoxDecryptFinish { session: init.session.clone() },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, crate::client::ErrorCode::InvalidHandle as u32);
+
+    // the session is not removed by the wrong finish call
+    let result: ResultOfEncryptionBoxEncryptFinish = client
+        .request_async(
+            "crypto.encryption_box_encrypt_finish",
+            ParamsOfEncryptionBoxEncryptFinish { session: init.session.clone() },
+        )
+        .await
+        .unwrap();
+    let mut encrypted = base64::decode(&init.data).unwrap();
+    encrypted.extend(base64::decode(&result.data).unwrap());
+    let decrypted = decrypt_stream(&client, &encryption_box, &encrypted, 100)
+        .await
+        .unwrap();
+    assert!(decrypted.is_empty());
+
+    // decryption session can not be finished as encryption one
+    let session = client
+        .request_async::<_, ResultOfEncryptionBoxDecryptInit>(
+            "crypto.encryption_box_decrypt_init",
+            ParamsOfEncryptionBoxDecryptInit { encryption_box: encryption_box.clone() },
+        )
+        .await
+        .unwrap()
+        .session;
+    let error = client
+        .request_async::<_, ResultOfEncryptionBoxEncryptFinish>(
+            "crypto.encryption_box_encrypt_finish",
+            ParamsOfEncryptionBoxEncryptFinish { session: session.clone() },
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, crate::client::ErrorCode::InvalidHandle as u32);
+    let _: () = client
+        .request_async(
+            "crypto.remove_encryption_session",
+            crate::crypto::ParamsOfRemoveEncryptionSession { session },
+        )
+        .await
+        .unwrap();
+}
+
+#[tokio::test(core_threads = 2)]
+async fn test_keystore() {
+    let client = TestClient::new();
+    let keys = client.generate_sign_keys();
+    let password = base64::encode("password");
+    let kdf = KeystoreKdf::Scrypt { log_n: 10, r: 8, p: 1 };
+
+    let imported: ResultOfKeystoreImport = client
+        .request(
+            "crypto.keystore_import",
+            ParamsOfKeystoreImport {
+                secret: KeystoreSecret::KeyPair { keys: keys.clone() },
+                password: password.clone(),
+                kdf: Some(kdf.clone()),
+            },
+        )
+        .unwrap();
+    assert_eq!(imported.keystore.secret_type, "KeyPair");
+    assert_eq!(imported.keystore.public, Some(keys.public.clone()));
+    assert_eq!(imported.keystore.kdf, kdf);
+
+    let error = client
+        .request::<_, ResultOfKeystoreExport>(
+            "crypto.keystore_export",
+            ParamsOfKeystoreExport {
+                keystore: imported.keystore.clone(),
+                password: base64::encode("wrong"),
+            },
+        )
+        .err()
+        .unwrap();
+    assert_eq!(error.code, ErrorCode::KeystoreInvalidPassword as u32);
+
+    let mut tampered = imported.keystore.clone();
+    tampered.kdf = KeystoreKdf::Scrypt { log_n: 11, r: 8, p: 1 };
+    assert!(client
+        .request::<_, ResultOfKeystoreExport>(
+            "crypto.keystore_export",
+            ParamsOfKeystoreExport {
+                keystore: tampered,
+                password: password.clone(),
+            },
+        )
+        .is_err());
+
+    let mut tampered = imported.keystore.clone();
+    tampered.public = Some(client.generate_sign_keys().public);
+    let error = client
+        .request::<_, ResultOfKeystoreExport>(
+            "crypto.keystore_export",
+            ParamsOfKeystoreExport {
+                keystore: tampered,
+                password: password.clone(),
+            },
+        )
+        .err()
+        .unwrap();
+    assert_eq!(error.code, ErrorCode::KeystoreInvalidPassword as u32);
+
+    let mut tampered = imported.keystore.clone();
+    tampered.kdf = KeystoreKdf::Pbkdf2 { c: u32::MAX };
+    let error = client
+        .request::<_, ResultOfKeystoreExport>(
+            "crypto.keystore_export",
+            ParamsOfKeystoreExport {
+                keystore: tampered,
+                password: password.clone(),
+            },
+        )
+        .err()
+        .unwrap();
+    assert_eq!(error.code, ErrorCode::InvalidKeystore as u32);
+
+    let error = client
+        .request::<_, ResultOfKeystoreImport>(
+            "crypto.keystore_import",
+            ParamsOfKeystoreImport {
+                secret: KeystoreSecret::KeyPair { keys: keys.clone() },
+                password: password.clone(),
+                kdf: Some(KeystoreKdf::Scrypt { log_n: 30, r: 8, p: 1 }),
+            },
+        )
+        .err()
+        .unwrap();
+    assert_eq!(error.code, ErrorCode::InvalidKeystore as u32);
+
+    let new_password = base64::encode("new password");
+    let changed: ResultOfKeystoreChangePassword = client
+        .request(
+            "crypto.keystore_change_password",
+            ParamsOfKeystoreChangePassword {
+                keystore: imported.keystore.clone(),
+                password: password.clone(),
+                new_password: new_password.clone(),
+                kdf: Some(KeystoreKdf::Pbkdf2 { c: 1000 }),
+            },
+        )
+        .unwrap();
+    assert_ne!(changed.keystore.salt, imported.keystore.salt);
+
+    let exported: ResultOfKeystoreExport = client
+        .request(
+            "crypto.keystore_export",
+            ParamsOfKeystoreExport {
+                keystore: changed.keystore.clone(),
+                password: new_password.clone(),
+            },
+        )
+        .unwrap();
+    match &exported.secret {
+        KeystoreSecret::KeyPair { keys: exported } => assert_eq!(exported, &keys),
+        _ => panic!("KeyPair secret expected"),
+    }
+
+    let signing_box: RegisteredSigningBox = client
+        .request_async(
+            "crypto.keystore_get_signing_box",
+            ParamsOfKeystoreGetSigningBox {
+                keystore: changed.keystore,
+                password: new_password,
+                path: None,
+            },
+        )
+        .await
+        .unwrap();
+    let box_pubkey: ResultOfSigningBoxGetPublicKey = client
+        .request_async(
+            "crypto.signing_box_get_public_key",
+            signing_box,
+        )
+        .await
+        .unwrap();
+    assert_eq!(box_pubkey.pubkey, keys.public);
+
+    let phrase = "abuse boss fly battle rubber wasp afraid hamster guide essence vibrant tattoo";
+    let mnemonic: ResultOfKeystoreImport = client
+        .request(
+            "crypto.keystore_import",
+            ParamsOfKeystoreImport {
+                secret: KeystoreSecret::Mnemonic {
+                    phrase: phrase.into(),
+                    dictionary: Some(1),
+                    word_count: Some(12),
+                },
+                password: password.clone(),
+                kdf: Some(KeystoreKdf::Pbkdf2 { c: 1000 }),
+            },
+        )
+        .unwrap();
+    assert_eq!(mnemonic.keystore.public, None);
+
+    let signing_box: RegisteredSigningBox = client
+        .request_async(
+            "crypto.keystore_get_signing_box",
+            ParamsOfKeystoreGetSigningBox {
+                keystore: mnemonic.keystore,
+                password: password.clone(),
+                path: None,
+            },
+        )
+        .await
+        .unwrap();
+    let box_pubkey: ResultOfSigningBoxGetPublicKey = client
+        .request_async(
+            "crypto.signing_box_get_public_key",
+            signing_box,
+        )
+        .await
+        .unwrap();
+    let derived: KeyPair = client
+        .request(
+            "crypto.mnemonic_derive_sign_keys",
+            ParamsOfMnemonicDeriveSignKeys {
+                phrase: phrase.into(),
+                path: None,
+                dictionary: Some(1),
+                word_count: Some(12),
+            },
+        )
+        .unwrap();
+    assert_eq!(box_pubkey.pubkey, derived.public);
+
+    let error = client
+        .request::<_, ResultOfKeystoreImport>(
+            "crypto.keystore_import",
+            ParamsOfKeystoreImport {
+                secret: KeystoreSecret::Mnemonic {
+                    phrase: "abuse boss fly".into(),
+                    dictionary: Some(1),
+                    word_count: Some(12),
+                },
+                password,
+                kdf: None,
+            },
+        )
+        .err()
+        .unwrap();
+    assert_eq!(error.code, ErrorCode::Bip39InvalidPhrase as u32);
+}
+
+#[tokio::test(core_threads = 2)]
+async fn test_signing_box_from_mnemonic() {
+    let client = TestClient::new();
+    let phrase = "abuse boss fly battle rubber wasp afraid hamster guide essence vibrant tattoo";
+
+    let derive_keys = |path: &str| -> KeyPair {
+        client
+            .request(
+                "crypto.mnemonic_derive_sign_keys",
+                ParamsOfMnemonicDeriveSignKeys {
+                    phrase: phrase.into(),
+                    path: Some(path.into()),
+                    dictionary: Some(1),
+                    word_count: Some(12),
+                },
+            )
+            .unwrap()
+    };
+
+    let signing_box: RegisteredSigningBox = client
+        .request_async(
+            "crypto.get_signing_box_from_mnemonic",
+            ParamsOfGetSigningBoxFromMnemonic {
+                phrase: phrase.into(),
+                dictionary: Some(1),
+                word_count: Some(12),
+                path: None,
+            },
+        )
+        .await
+        .unwrap();
+    let box_pubkey: ResultOfSigningBoxGetPublicKey = client
+        .request_async("crypto.signing_box_get_public_key", signing_box)
+        .await
+        .unwrap();
+    assert_eq!(box_pubkey.pubkey, derive_keys("m/44'/396'/0'/0/0").public);
+
+    let result: ResultOfDeriveSigningBoxes = client
+        .request_async(
+            "crypto.derive_signing_boxes",
+            ParamsOfDeriveSigningBoxes {
+                phrase: phrase.into(),
+                dictionary: Some(1),
+                word_count: Some(12),
+                path: None,
+                first_index: Some(1),
+                count: 3,
+                hardened: None,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.signing_boxes.len(), 3);
+    for (derived, index) in result.signing_boxes.iter().zip(1..) {
+        let path = format!("m/44'/396'/0'/0/{}", index);
+        assert_eq!(derived.path, path);
+        assert_eq!(derived.pubkey, derive_keys(&path).public);
+
+        let box_pubkey: ResultOfSigningBoxGetPublicKey = client
+            .request_async(
+                "crypto.signing_box_get_public_key",
+                RegisteredSigningBox {
+                    handle: derived.handle.clone(),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(box_pubkey.pubkey, derived.pubkey);
+    }
+
+    let result: ResultOfDeriveSigningBoxes = client
+        .request_async(
+            "crypto.derive_signing_boxes",
+            ParamsOfDeriveSigningBoxes {
+                phrase: phrase.into(),
+                dictionary: Some(1),
+                word_count: Some(12),
+                path: Some("m/44'/396'".into()),
+                first_index: None,
+                count: 2,
+                hardened: Some(true),
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.signing_boxes[1].path, "m/44'/396'/1'");
+    assert_eq!(result.signing_boxes[1].pubkey, derive_keys("m/44'/396'/1'").public);
+
+    let error = client
+        .request_async::<_, RegisteredSigningBox>(
+            "crypto.get_signing_box_from_mnemonic",
+            ParamsOfGetSigningBoxFromMnemonic {
+                phrase: "abuse boss fly".into(),
+                dictionary: Some(1),
+                word_count: Some(12),
+                path: None,
+            },
+        )
+        .await
+        .err()
+        .unwrap();
+    assert_eq!(error.code, ErrorCode::Bip39InvalidPhrase as u32);
+}
+
+#[test]
+fn test_secret_sharing() {
+    let client = TestClient::new();
+
+    let phrase = "abuse boss fly battle rubber wasp afraid hamster guide essence vibrant tattoo";
+    let split: ResultOfSplitSecret = client
+        .request(
+            "crypto.split_secret",
+            ParamsOfSplitSecret {
+                phrase: Some(phrase.into()),
+                dictionary: Some(1),
+                word_count: Some(12),
+                threshold: 3,
+                share_count: 5,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    assert_eq!(split.shares.len(), 5);
+
+    let combined: ResultOfCombineShares = client
+        .request(
+            "crypto.combine_shares",
+            ParamsOfCombineShares {
+                shares: vec![
+                    split.shares[4].clone(),
+                    split.shares[0].clone(),
+                    split.shares[2].clone(),
+                ],
+                dictionary: Some(1),
+            },
+        )
+        .unwrap();
+    assert_eq!(combined.phrase, Some(phrase.to_string()));
+    assert_eq!(combined.dictionary, Some(1));
+    assert_eq!(combined.secret, None);
+
+    let error = client
+        .request::<_, ResultOfCombineShares>(
+            "crypto.combine_shares",
+            ParamsOfCombineShares {
+                shares: split.shares[..2].to_vec(),
+                dictionary: Some(1),
+            },
+        )
+        .err()
+        .unwrap();
+    assert_eq!(error.code, ErrorCode::SecretSharingFailed as u32);
+
+    let mut corrupted: Vec<&str> = split.shares[1].split(' ').collect();
+    corrupted[3] = if corrupted[3] == "abandon" { "ability" } else { "abandon" };
+    let error = client
+        .request::<_, ResultOfCombineShares>(
+            "crypto.combine_shares",
+            ParamsOfCombineShares {
+                shares: vec![
+                    split.shares[0].clone(),
+                    corrupted.join(" "),
+                    split.shares[2].clone(),
+                ],
+                dictionary: Some(1),
+            },
+        )
+        .err()
+        .unwrap();
+    assert_eq!(error.code, ErrorCode::InvalidShare as u32);
+
+    let secret = "8fb4f56c1e85e4ebd3bb6a0ea86fe00d4b6d1d6d4f6d9aa2d8e3e31e5e3bd8ae";
+    let split: ResultOfSplitSecret = client
+        .request(
+            "crypto.split_secret",
+            ParamsOfSplitSecret {
+                secret: Some(secret.into()),
+                dictionary: Some(0),
+                threshold: 2,
+                share_count: 3,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let combined: ResultOfCombineShares = client
+        .request(
+            "crypto.combine_shares",
+            ParamsOfCombineShares {
+                shares: split.shares[1..].to_vec(),
+                dictionary: Some(0),
+            },
+        )
+        .unwrap();
+    assert_eq!(combined.secret, Some(secret.to_string()));
+    assert_eq!(combined.phrase, None);
+
+    let other: ResultOfSplitSecret = client
+        .request(
+            "crypto.split_secret",
+            ParamsOfSplitSecret {
+                secret: Some(secret.into()),
+                dictionary: Some(0),
+                threshold: 2,
+                share_count: 3,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+    let error = client
+        .request::<_, ResultOfCombineShares>(
+            "crypto.combine_shares",
+            ParamsOfCombineShares {
+                shares: vec![split.shares[0].clone(), other.shares[1].clone()],
+                dictionary: Some(0),
+            },
+        )
+        .err()
+        .unwrap();
+    // splits are distinguished by a random identifier and, in case of a collision, by the digest
+    assert!(
+        error.code == ErrorCode::InvalidShare as u32
+            || error.code == ErrorCode::SecretSharingFailed as u32
+    );
+}
+
+#[test]
+fn test_verify_signatures_batch() {
+    let client = TestClient::new();
+
+    let mut items = Vec::new();
+    for i in 0..5 {
+        let keys = client.generate_sign_keys();
+        let unsigned = base64::encode(&format!("Order {}", i));
+        let result: ResultOfSign = client
+            .request(
+                "crypto.sign",
+                ParamsOfSign {
+                    unsigned: unsigned.clone(),
+                    keys: keys.clone(),
+                },
+            )
+            .unwrap();
+        items.push(SignatureBatchItem {
+            unsigned,
+            signature: result.signature,
+            public: keys.public,
+        });
+    }
+
+    let result: ResultOfVerifySignaturesBatch = client
+        .request(
+            "crypto.verify_signatures_batch",
+            ParamsOfVerifySignaturesBatch {
+                items: items.clone(),
+            },
+        )
+        .unwrap();
+    assert!(result.valid);
+    assert_eq!(result.items_valid, vec![true; 5]);
+
+    items[1].unsigned = base64::encode("Forged order");
+    items[3].public = items[4].public.clone();
+    let result: ResultOfVerifySignaturesBatch = client
+        .request(
+            "crypto.verify_signatures_batch",
+            ParamsOfVerifySignaturesBatch {
+                items: items.clone(),
+            },
+        )
+        .unwrap();
+    assert!(!result.valid);
+    assert_eq!(result.items_valid, vec![true, false, true, false, true]);
+
+    items[0].signature = "0102".into();
+    items[2].signature = "not a hex".into();
+    let result: ResultOfVerifySignaturesBatch = client
+        .request(
+            "crypto.verify_signatures_batch",
+            ParamsOfVerifySignaturesBatch { items },
+        )
+        .unwrap();
+    assert!(!result.valid);
+    assert_eq!(result.items_valid, vec![false, false, false, false, true]);
+}
+
+#[tokio::test(core_threads = 2)]
+async fn test_secp256k1() {
+    let client = TestClient::new();
+
+    let hash: ResultOfHash = client
+        .request("crypto.keccak256", ParamsOfHash { data: String::new() })
+        .unwrap();
+    assert_eq!(hash.hash, "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470");
+
+    let keys: KeyPair = client
+        .request(
+            "crypto.secp256k1_keypair_from_secret",
+            ParamsOfSecp256k1KeyPairFromSecret {
+                secret: "0000000000000000000000000000000000000000000000000000000000000001".into(),
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        keys.public,
+        "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798"
+    );
+
+    let address: ResultOfSecp256k1EthAddress = client
+        .request(
+            "crypto.secp256k1_eth_address",
+            ParamsOfSecp256k1EthAddress {
+                public: keys.public.clone(),
+            },
+        )
+        .unwrap();
+    assert_eq!(address.address, "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf");
+
+    let keys: KeyPair = client
+        .request_no_params("crypto.generate_random_secp256k1_keys")
+        .unwrap();
+    let unsigned = base64::encode("Cross-chain transfer");
+    let signed: ResultOfSecp256k1Sign = client
+        .request(
+            "crypto.secp256k1_sign",
+            ParamsOfSecp256k1Sign {
+                unsigned: unsigned.clone(),
+                secret: keys.secret.clone(),
+                prehashed: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(signed.signature.len(), 130);
+
+    let verified: ResultOfSecp256k1Verify = client
+        .request(
+            "crypto.secp256k1_verify",
+            ParamsOfSecp256k1Verify {
+                unsigned: unsigned.clone(),
+                signature: signed.signature.clone(),
+                public: keys.public.clone(),
+                prehashed: None,
+            },
+        )
+        .unwrap();
+    assert!(verified.succeeded);
+
+    let verified: ResultOfSecp256k1Verify = client
+        .request(
+            "crypto.secp256k1_verify",
+            ParamsOfSecp256k1Verify {
+                unsigned: base64::encode("Forged transfer"),
+                signature: signed.signature.clone(),
+                public: keys.public.clone(),
+                prehashed: None,
+            },
+        )
+        .unwrap();
+    assert!(!verified.succeeded);
+
+    let recovered: ResultOfSecp256k1RecoverPublicKey = client
+        .request(
+            "crypto.secp256k1_recover_public_key",
+            ParamsOfSecp256k1RecoverPublicKey {
+                unsigned: unsigned.clone(),
+                signature: signed.signature.clone(),
+                prehashed: None,
+            },
+        )
+        .unwrap();
+    assert_eq!(recovered.public, keys.public);
+
+    let hash: ResultOfHash = client
+        .request("crypto.keccak256", ParamsOfHash { data: unsigned.clone() })
+        .unwrap();
+    let prehashed: ResultOfSecp256k1Sign = client
+        .request(
+            "crypto.secp256k1_sign",
+            ParamsOfSecp256k1Sign {
+                unsigned: base64::encode(&hex::decode(&hash.hash).unwrap()),
+                secret: keys.secret.clone(),
+                prehashed: Some(true),
+            },
+        )
+        .unwrap();
+    assert_eq!(prehashed.signature, signed.signature);
+
+    let signing_box: RegisteredSigningBox = client
+        .request_async("crypto.get_secp256k1_signing_box", keys.clone())
+        .await
+        .unwrap();
+    let box_pubkey: ResultOfSigningBoxGetPublicKey = client
+        .request_async(
+            "crypto.signing_box_get_public_key",
+            signing_box.clone(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(box_pubkey.pubkey, keys.public);
+    let box_sign: ResultOfSigningBoxSign = client
+        .request_async(
+            "crypto.signing_box_sign",
+            ParamsOfSigningBoxSign {
+                signing_box: signing_box.handle,
+                unsigned,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(box_sign.signature, signed.signature);
+}
@@ -1,3 +1,4 @@
+use serde_json::Value;
 
 /// [UNSTABLE](UNSTABLE.md) Describes how much funds will be debited from the target
 ///  contract balance as a result of the transaction.
@@ -9,6 +10,19 @@ pub struct Spending {
     pub dst: String,
 }
 
+/// [UNSTABLE](UNSTABLE.md) Message decoded with one of the ABIs known to the DeBot engine.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct DecodedCall {
+    /// Destination address of the message.
+    pub dst: String,
+    /// Amount of nanotokens attached to the message. Zero for external messages.
+    pub amount: u64,
+    /// Name of the called function.
+    pub function_name: String,
+    /// Function arguments.
+    pub value: Option<Value>,
+}
+
 /// [UNSTABLE](UNSTABLE.md) Describes the operation that the DeBot wants to perform.
 #[derive(Serialize, Deserialize, Clone, Debug, ApiType)]
 #[serde(tag="type")]
@@ -29,5 +43,69 @@ pub enum DebotActivity {
         signkey: String,
         /// Signing box handle used to sign external message.
         signing_box_handle: u32,
-    }
-}
\ No newline at end of file
+        /// External message decoded with known ABIs.
+        #[serde(default)]
+        call: Option<DecodedCall>,
+        /// Outbound internal messages which are decoded with known ABIs,
+        /// e.g. token transfers.
+        #[serde(default)]
+        out_calls: Vec<DecodedCall>,
+    },
+    /// DeBot wants to deploy new smart contract.
+    Deploy {
+        /// External inbound message BOC with contract state init.
+        msg: String,
+        /// Address of the deployed contract.
+        dst: String,
+        /// Hash of the contract code.
+        code_hash: String,
+        /// Contract initial data BOC encoded with `base64`.
+        data: String,
+        /// Initial values of contract's static variables.
+        /// Decoded if the contract ABI is known to the engine.
+        initial_data: Option<Value>,
+        /// Initial account owner's public key.
+        initial_pubkey: String,
+        /// Constructor call decoded with known ABIs.
+        call: Option<DecodedCall>,
+        /// List of spendings as a result of transaction.
+        out: Vec<Spending>,
+        /// Outbound internal messages which are decoded with known ABIs.
+        out_calls: Vec<DecodedCall>,
+        /// Transaction total fee.
+        fee: u64,
+        /// Public key from keypair that was used to sign external message.
+        signkey: String,
+        /// Signing box handle used to sign external message.
+        signing_box_handle: u32,
+    },
+    /// DeBot wants to invoke another DeBot.
+    InvokeDebot {
+        /// Address of the invoked DeBot.
+        debot: String,
+        /// Invocation message BOC.
+        msg: String,
+        /// Invocation message decoded with known ABIs.
+        call: Option<DecodedCall>,
+    },
+    /// DeBot wants to sign arbitrary hash with the signing box.
+    SignHash {
+        /// Signing box handle.
+        signing_box_handle: u32,
+        /// Public key of the signing box.
+        signkey: String,
+        /// Hash to sign encoded in `hex`.
+        hash: String,
+    },
+    /// DeBot wants to encrypt or decrypt data with the encryption box.
+    EncryptionBoxUse {
+        /// Encryption box handle.
+        encryption_box_handle: u32,
+        /// `true` for encryption, `false` for decryption.
+        encrypt: bool,
+        /// Encryption algorithm, if reported by the box.
+        algorithm: Option<String>,
+        /// Size of the data in bytes.
+        data_size: u32,
+    },
+}
@@ -0,0 +1,71 @@
+use super::dinterface::{decode_answer_id, get_string_arg, DebotInterface, InterfaceResult};
+use super::ui_browser::{input_valid, UiCallbacks};
+use crate::abi::Abi;
+use crate::encoding::account_decode;
+use serde_json::Value;
+use std::sync::Arc;
+
+const ABI: &str = r#"
+{
+	"ABI version": 2,
+	"header": ["time"],
+	"functions": [
+		{
+			"name": "get",
+			"inputs": [
+				{"name":"answerId","type":"uint32"},
+				{"name":"prompt","type":"bytes"}
+			],
+			"outputs": [
+				{"name":"value","type":"address"}
+			]
+		}
+	],
+	"data": [
+	],
+	"events": [
+	]
+}
+"#;
+
+const ADDRESS_INPUT_ID: &str = "d7ed1bd8e6230871116f4522e58df0a93c5520c56f4ade23ef3d8919a984653b";
+
+pub struct AddressInputInterface {
+    ui: Arc<dyn UiCallbacks + Send + Sync>,
+}
+
+impl AddressInputInterface {
+    pub fn new(ui: Arc<dyn UiCallbacks + Send + Sync>) -> Self {
+        Self { ui }
+    }
+
+    async fn get(&self, args: &Value) -> InterfaceResult {
+        let answer_id = decode_answer_id(args)?;
+        let prompt = get_string_arg(args, "prompt")?;
+        let value = input_valid(self.ui.as_ref(), &prompt, |value| {
+            account_decode(value)
+                .map(|address| address.to_string())
+                .map_err(|_| format!("invalid address \"{}\"", value))
+        })
+        .await?;
+        Ok((answer_id, json!({ "value": value })))
+    }
+}
+
+#[async_trait::async_trait]
+impl DebotInterface for AddressInputInterface {
+    fn get_id(&self) -> String {
+        ADDRESS_INPUT_ID.to_string()
+    }
+
+    fn get_abi(&self) -> Abi {
+        Abi::Json(ABI.to_owned())
+    }
+
+    async fn call(&self, func: &str, args: &Value) -> InterfaceResult {
+        match func {
+            "get" => self.get(args).await,
+            _ => Err(format!("function \"{}\" is not implemented", func)),
+        }
+    }
+}
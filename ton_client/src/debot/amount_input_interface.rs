@@ -0,0 +1,96 @@
+use super::dinterface::{
+    decode_answer_id, get_arg, get_num_arg, get_string_arg, DebotInterface, InterfaceResult,
+};
+use super::locale::Locale;
+use super::ui_browser::{input_valid, UiCallbacks};
+use crate::abi::Abi;
+use crate::encoding::decode_abi_bigint;
+use serde_json::Value;
+use std::sync::Arc;
+
+const ABI: &str = r#"
+{
+	"ABI version": 2,
+	"header": ["time"],
+	"functions": [
+		{
+			"name": "get",
+			"inputs": [
+				{"name":"answerId","type":"uint32"},
+				{"name":"prompt","type":"bytes"},
+				{"name":"decimals","type":"uint8"},
+				{"name":"min","type":"uint128"},
+				{"name":"max","type":"uint128"}
+			],
+			"outputs": [
+				{"name":"value","type":"uint128"}
+			]
+		}
+	],
+	"data": [
+	],
+	"events": [
+	]
+}
+"#;
+
+const AMOUNT_INPUT_ID: &str = "a1d347099e29c1624c8890619daf207bde18e92df5220a54bcc6d858309ece84";
+
+pub struct AmountInputInterface {
+    ui: Arc<dyn UiCallbacks + Send + Sync>,
+    locale: Locale,
+}
+
+impl AmountInputInterface {
+    pub fn new(ui: Arc<dyn UiCallbacks + Send + Sync>) -> Self {
+        Self { ui, locale: Locale::default() }
+    }
+
+    /// Sets preferred locale of the user used to parse and format amounts.
+    pub fn with_locale(mut self, locale: Option<String>) -> Self {
+        self.locale = Locale::new(locale);
+        self
+    }
+
+    async fn get(&self, args: &Value) -> InterfaceResult {
+        let answer_id = decode_answer_id(args)?;
+        let prompt = get_string_arg(args, "prompt")?;
+        let decimals = get_num_arg::<u8>(args, "decimals")?;
+        let min = decode_abi_bigint(&get_arg(args, "min")?).map_err(|e| e.to_string())?;
+        let max = decode_abi_bigint(&get_arg(args, "max")?).map_err(|e| e.to_string())?;
+        if min > max {
+            return Err(format!("min amount is greater than max amount"));
+        }
+        let value = input_valid(self.ui.as_ref(), &prompt, |value| {
+            let amount = self.locale.parse_amount(value, decimals)?;
+            if amount < min || amount > max {
+                return Err(format!(
+                    "amount must be between {} and {}",
+                    self.locale.format_amount(&min, decimals),
+                    self.locale.format_amount(&max, decimals)
+                ));
+            }
+            Ok(amount)
+        })
+        .await?;
+        Ok((answer_id, json!({ "value": value.to_string() })))
+    }
+}
+
+#[async_trait::async_trait]
+impl DebotInterface for AmountInputInterface {
+    fn get_id(&self) -> String {
+        AMOUNT_INPUT_ID.to_string()
+    }
+
+    fn get_abi(&self) -> Abi {
+        Abi::Json(ABI.to_owned())
+    }
+
+    async fn call(&self, func: &str, args: &Value) -> InterfaceResult {
+        match func {
+            "get" => self.get(args).await,
+            _ => Err(format!("function \"{}\" is not implemented", func)),
+        }
+    }
+}
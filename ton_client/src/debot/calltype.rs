@@ -1,8 +1,14 @@
 use super::errors::Error;
 use super::helpers::build_internal_message;
-use super::{BrowserCallbacks, DebotActivity, Spending, TonClient};
-use crate::abi::Signer;
-use crate::boc::internal::{deserialize_object_from_base64, serialize_object_to_base64};
+use super::mock_node::MockNode;
+use super::{BrowserCallbacks, DebotActivity, DecodedCall, Spending, TonClient};
+use crate::abi::{
+    decode_initial_data, decode_message, Abi, ParamsOfDecodeInitialData, ParamsOfDecodeMessage,
+    Signer,
+};
+use crate::boc::internal::{
+    deserialize_object_from_base64, serialize_cell_to_base64, serialize_object_to_base64,
+};
 use crate::boc::{get_boc_hash, parse_message, ParamsOfParse, ParamsOfGetBocHash};
 use crate::crypto::{KeyPair, SigningBoxHandle, get_signing_box};
 use crate::encoding::decode_abi_number;
@@ -213,6 +219,8 @@ pub(crate) struct ContractCall {
     dest_addr: String,
     local_run: bool,
     meta: Metadata,
+    node: Option<Arc<MockNode>>,
+    abis: Vec<Abi>,
 }
 
 impl ContractCall {
@@ -224,6 +232,8 @@ impl ContractCall {
         target_state: String,
         debot_addr: String,
         local_run: bool,
+        node: Option<Arc<MockNode>>,
+        abis: Vec<Abi>,
     ) -> ClientResult<Self> {
         let mut msg: Message = deserialize_object_from_base64(&msg, "message")
             .map_err(msg_err)?
@@ -240,7 +250,19 @@ impl ContractCall {
             .get_dst_address()
             .map(|x| x.to_string())
             .unwrap_or_default();
-        Ok(Self { browser, ton, msg, signer, target_state, debot_addr, dest_addr, local_run, meta })
+        Ok(Self {
+            browser,
+            ton,
+            msg,
+            signer,
+            target_state,
+            debot_addr,
+            dest_addr,
+            local_run,
+            meta,
+            node,
+            abis,
+        })
     }
 
     pub async fn execute(&self) -> ClientResult<String> {
@@ -300,6 +322,7 @@ impl ContractCall {
             fixed_msg.clone(),
             self.target_state.clone(),
             self.signer.clone(),
+            &self.abis,
         ).await;
         match activity {
             Ok(activity) => {
@@ -311,7 +334,14 @@ impl ContractCall {
                 return self.build_error_answer_msg(e);
             },
         }
-        
+
+        if let Some(node) = self.node.as_ref() {
+            return match node.process_message(self.ton.clone(), fixed_msg, None).await {
+                Ok(res) => self.build_answer_from_out_messages(func_id, &res.out_messages),
+                Err(e) => self.build_error_answer_msg(e),
+            };
+        }
+
         let browser = self.browser.clone();
         let callback = move |event| {
             debug!("{:?}", event);
@@ -367,24 +397,7 @@ impl ContractCall {
                 if let Err(e) = result {
                     return self.build_error_answer_msg(e);
                 }
-                for out_msg in &res.out_messages {
-                    let res = build_answer_msg(
-                        out_msg,
-                        self.meta.answer_id,
-                        func_id,
-                        &self.dest_addr,
-                        &self.debot_addr
-                    );
-                    if let Some(answer_msg) = res {
-                        return Ok(answer_msg);
-                    }
-                    debug!("Skip outbound message");
-                }
-                debug!("Build empty body");
-                // answer message not found, build empty answer.
-                let mut new_body = BuilderData::new();
-                new_body.append_u32(self.meta.answer_id).map_err(msg_err)?;
-                build_internal_message(&self.dest_addr, &self.debot_addr, new_body.into_cell().map_err(msg_err)?.into())
+                self.build_answer_from_out_messages(func_id, &res.out_messages)
             }
             Err(e) => {
                 debug!("Transaction failed: {:?}", e);
@@ -393,6 +406,31 @@ impl ContractCall {
         }
     }
 
+    fn build_answer_from_out_messages(
+        &self,
+        func_id: u32,
+        out_messages: &Vec<String>,
+    ) -> ClientResult<String> {
+        for out_msg in out_messages {
+            let res = build_answer_msg(
+                out_msg,
+                self.meta.answer_id,
+                func_id,
+                &self.dest_addr,
+                &self.debot_addr
+            );
+            if let Some(answer_msg) = res {
+                return Ok(answer_msg);
+            }
+            debug!("Skip outbound message");
+        }
+        debug!("Build empty body");
+        // answer message not found, build empty answer.
+        let mut new_body = BuilderData::new();
+        new_body.append_u32(self.meta.answer_id).map_err(msg_err)?;
+        build_internal_message(&self.dest_addr, &self.debot_addr, new_body.into_cell().map_err(msg_err)?.into())
+    }
+
     async fn decode_and_fix_ext_msg(&self) -> ClientResult<(u32, String)> {
         let now_ms = self.ton.env.now_ms();
         let result: (u32, Message) = decode_and_fix_ext_msg(&self.msg, now_ms, &self.signer, &self.meta, &self.ton).await?;
@@ -483,28 +521,73 @@ fn get_meta(message: &mut Message) -> ClientResult<Metadata> {
     Metadata::try_from(src)
 }
 
+/// Checks if the message contains state init, i.e. deploys the contract.
+pub(super) fn has_state_init(msg: &str) -> bool {
+    deserialize_object_from_base64::<Message>(msg, "message")
+        .map(|msg| msg.object.state_init().is_some())
+        .unwrap_or(false)
+}
+
+/// Returns destination address of the message or empty string.
+pub(super) fn message_dst(msg: &str) -> String {
+    deserialize_object_from_base64::<Message>(msg, "message")
+        .ok()
+        .and_then(|msg| msg.object.dst_ref().map(|dst| dst.to_string()))
+        .unwrap_or_default()
+}
+
+/// Decodes the message with the first ABI which fits it.
+pub(super) async fn decode_call(client: TonClient, abis: &[Abi], msg: &str) -> Option<DecodedCall> {
+    let parsed = parse_message(client.clone(), ParamsOfParse { boc: msg.to_owned() })
+        .await
+        .ok()?
+        .parsed;
+    for abi in abis {
+        let params = ParamsOfDecodeMessage { abi: abi.clone(), message: msg.to_owned() };
+        if let Ok(body) = decode_message(client.clone(), params).await {
+            return Some(DecodedCall {
+                dst: parsed["dst"].as_str().unwrap_or_default().to_owned(),
+                amount: parsed["value"]
+                    .as_str()
+                    .and_then(|value| decode_abi_number(value).ok())
+                    .unwrap_or(0),
+                function_name: body.name,
+                value: body.value,
+            });
+        }
+    }
+    None
+}
+
 async fn emulate_transaction(
     client: TonClient,
     dst: String,
     msg: String,
     target_state: String,
     signer: Signer,
+    abis: &[Abi],
 ) -> ClientResult<DebotActivity> {
+    let account = if target_state.is_empty() {
+        AccountForExecutor::Uninit
+    } else {
+        AccountForExecutor::Account {
+            boc: target_state,
+            unlimited_balance: None,
+        }
+    };
     let result = run_executor(
         client.clone(),
         ParamsOfRunExecutor {
             message: msg.clone(),
-            account: AccountForExecutor::Account {
-                boc: target_state,
-                unlimited_balance: None,
-            },
+            account,
             ..Default::default()
         },
     )
     .await?;
     let mut out = vec![];
+    let mut out_calls = vec![];
     for out_msg in result.out_messages {
-        let parsed = parse_message(client.clone(), ParamsOfParse { boc: out_msg })
+        let parsed = parse_message(client.clone(), ParamsOfParse { boc: out_msg.clone() })
             .await?
             .parsed;
         let msg_type = parsed["msg_type"].as_u64().unwrap();
@@ -516,6 +599,9 @@ async fn emulate_transaction(
                 dst: out_dst,
                 amount: out_amount,
             });
+            if let Some(call) = decode_call(client.clone(), abis, &out_msg).await {
+                out_calls.push(call);
+            }
         }
     }
 
@@ -524,6 +610,44 @@ async fn emulate_transaction(
     } else {
         (0, String::new())
     };
+    let call = decode_call(client.clone(), abis, &msg).await;
+    let message: Message = deserialize_object_from_base64(&msg, "message")
+        .map_err(msg_err)?
+        .object;
+    if let Some(state_init) = message.state_init() {
+        let code_hash = state_init
+            .code
+            .as_ref()
+            .map(|code| code.repr_hash().as_hex_string())
+            .unwrap_or_default();
+        let data = match state_init.data.as_ref() {
+            Some(data) => serialize_cell_to_base64(data, "data")?,
+            None => String::new(),
+        };
+        let (mut initial_data, mut initial_pubkey) = (None, String::new());
+        for abi in abis.iter().map(|abi| Some(abi.clone())).chain(std::iter::once(None)) {
+            let params = ParamsOfDecodeInitialData { abi, data: data.clone() };
+            if let Ok(decoded) = decode_initial_data(client.clone(), params).await {
+                initial_data = decoded.initial_data;
+                initial_pubkey = decoded.initial_pubkey;
+                break;
+            }
+        }
+        return Ok(DebotActivity::Deploy {
+            msg,
+            dst,
+            code_hash,
+            data,
+            initial_data,
+            initial_pubkey,
+            call,
+            out,
+            out_calls,
+            fee: result.fees.total_account_fees,
+            signkey,
+            signing_box_handle,
+        });
+    }
     Ok(DebotActivity::Transaction {
         msg: msg.clone(),
         dst: dst.clone(),
@@ -532,5 +656,7 @@ async fn emulate_transaction(
         setcode: false,
         signkey, 
         signing_box_handle,
+        call,
+        out_calls,
     })
 }
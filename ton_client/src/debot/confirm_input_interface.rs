@@ -0,0 +1,65 @@
+use super::dinterface::{decode_answer_id, get_string_arg, DebotInterface, InterfaceResult};
+use super::ui_browser::UiCallbacks;
+use crate::abi::Abi;
+use serde_json::Value;
+use std::sync::Arc;
+
+const ABI: &str = r#"
+{
+	"ABI version": 2,
+	"header": ["time"],
+	"functions": [
+		{
+			"name": "get",
+			"inputs": [
+				{"name":"answerId","type":"uint32"},
+				{"name":"prompt","type":"bytes"}
+			],
+			"outputs": [
+				{"name":"value","type":"bool"}
+			]
+		}
+	],
+	"data": [
+	],
+	"events": [
+	]
+}
+"#;
+
+const CONFIRM_INPUT_ID: &str = "16653eaf34c921467120f2685d425ff963db5cbb5aa676a62a2e33bfc3f6828a";
+
+pub struct ConfirmInputInterface {
+    ui: Arc<dyn UiCallbacks + Send + Sync>,
+}
+
+impl ConfirmInputInterface {
+    pub fn new(ui: Arc<dyn UiCallbacks + Send + Sync>) -> Self {
+        Self { ui }
+    }
+
+    async fn get(&self, args: &Value) -> InterfaceResult {
+        let answer_id = decode_answer_id(args)?;
+        let prompt = get_string_arg(args, "prompt")?;
+        let value = self.ui.confirm(&prompt).await?;
+        Ok((answer_id, json!({ "value": value })))
+    }
+}
+
+#[async_trait::async_trait]
+impl DebotInterface for ConfirmInputInterface {
+    fn get_id(&self) -> String {
+        CONFIRM_INPUT_ID.to_string()
+    }
+
+    fn get_abi(&self) -> Abi {
+        Abi::Json(ABI.to_owned())
+    }
+
+    async fn call(&self, func: &str, args: &Value) -> InterfaceResult {
+        match func {
+            "get" => self.get(args).await,
+            _ => Err(format!("function \"{}\" is not implemented", func)),
+        }
+    }
+}
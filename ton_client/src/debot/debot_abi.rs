@@ -85,4 +85,35 @@ pub const DEBOT_ABI: &'static str = r#"{
 	],
 	"events": [
 	]
-}"#;
\ No newline at end of file
+}"#;
+/// `getDebotInfo` with metadata translations. DeBots which do not support
+/// translations return the layout from `DEBOT_ABI`.
+pub const DEBOT_INFO_TRANSLATIONS_ABI: &'static str = r#"{
+	"ABI version": 2,
+	"header": ["pubkey", "time", "expire"],
+	"functions": [
+		{
+			"name": "getDebotInfo",
+            "id": "0xDEB",
+			"inputs": [
+			],
+			"outputs": [
+				{"name":"name","type":"bytes"},
+				{"name":"version","type":"bytes"},
+				{"name":"publisher","type":"bytes"},
+				{"name":"caption","type":"bytes"},
+				{"name":"author","type":"bytes"},
+				{"name":"support","type":"address"},
+				{"name":"hello","type":"bytes"},
+				{"name":"language","type":"bytes"},
+				{"name":"dabi","type":"bytes"},
+				{"name":"icon","type":"bytes"},
+				{"components":[{"name":"language","type":"bytes"},{"name":"name","type":"bytes"},{"name":"publisher","type":"bytes"},{"name":"caption","type":"bytes"},{"name":"author","type":"bytes"},{"name":"hello","type":"bytes"}],"name":"translations","type":"tuple[]"}
+			]
+		}
+	],
+	"data": [
+	],
+	"events": [
+	]
+}"#;
@@ -3,32 +3,48 @@ use super::browser::BrowserCallbacks;
 use super::context::{
     str_hex_to_utf8, DContext, STATE_CURRENT, STATE_EXIT, STATE_PREV, STATE_ZERO,
 };
-use super::debot_abi::DEBOT_ABI;
+use super::debot_abi::{DEBOT_ABI, DEBOT_INFO_TRANSLATIONS_ABI};
 use super::errors::Error;
-use super::calltype::{ContractCall, DebotCallType};
+use super::calltype::{
+    decode_call, has_state_init, message_dst, ContractCall, DebotCallType,
+};
 use super::routines;
 use super::run_output::RunOutput;
-use super::{JsonValue, TonClient, DInfo};
+use super::{DebotActivity, JsonValue, TonClient, DInfo};
 use crate::abi::{
     decode_message_body, encode_message, encode_message_body, Abi, CallSet, DeploySet,
     ErrorCode, ParamsOfDecodeMessageBody, ParamsOfEncodeMessage, ParamsOfEncodeMessageBody, Signer,
 };
-use crate::boc::internal::{deserialize_cell_from_base64};
+use crate::boc::internal::{deserialize_cell_from_base64, deserialize_object_from_base64};
 use crate::crypto::{remove_signing_box, RegisteredSigningBox, SigningBoxHandle};
 use crate::encoding::{decode_abi_number};
 use crate::error::{ClientError, ClientResult};
 use crate::net::{query_collection, NetworkConfig, ParamsOfQueryCollection};
 use crate::processing::{process_message, ParamsOfProcessMessage, ProcessingEvent};
-use crate::tvm::{run_tvm, ParamsOfRunTvm};
+use crate::tvm::{run_tvm, ExecutionOptions, ParamsOfRunTvm};
 use crate::{ClientConfig, ClientContext};
-use std::collections::VecDeque;
+use futures::future::{BoxFuture, FutureExt};
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
 use ton_abi::Contract;
-use super::dinterface::{BuiltinInterfaces, DebotInterfaceExecutor};
+use super::dinterface::{BuiltinInterfaces, DebotInterface, DebotInterfaceExecutor};
+use super::event_log::{DebotEvent, DebotEventLog, EventRecorder, RecordingBrowser};
 use super::DEBOT_WC;
 use super::helpers::build_internal_message;
-use super::msg_interface::MsgInterface;
-use super::json_interface::JsonInterface;
+use super::inspect::{self, ResultOfInspect};
+use super::mock_node::MockNode;
+use super::msg_interface::{self, MsgInterface};
+use super::json_interface::{self, JsonInterface};
+use super::locale::Locale;
+use super::network_interface::NetworkInterface;
+use super::policy::{DebotPolicy, PolicyGuard};
+use super::sdk_interface::SdkInterface;
+use super::session::{self, DebotSession, SESSION_VERSION};
+use super::ui_browser::UiCallbacks;
+use super::{
+    AddressInputInterface, AmountInputInterface, ConfirmInputInterface, MenuInterface,
+    NumberInputInterface, SigningBoxInputInterface, TerminalInterface,
+};
 
 const EMPTY_CELL: &'static str = "te6ccgEBAQEAAgAAAA==";
 
@@ -51,6 +67,23 @@ fn load_abi(abi: &str) -> Result<Abi, String> {
     ))
 }
 
+/// Creates standard interfaces which interact with the user through `ui`.
+fn ui_interfaces(
+    ton: TonClient,
+    ui: Arc<dyn UiCallbacks + Send + Sync>,
+    locale: Option<String>,
+) -> Vec<Arc<dyn DebotInterface + Send + Sync>> {
+    vec![
+        Arc::new(TerminalInterface::new(ui.clone())),
+        Arc::new(MenuInterface::new(ui.clone())),
+        Arc::new(AddressInputInterface::new(ui.clone())),
+        Arc::new(AmountInputInterface::new(ui.clone()).with_locale(locale)),
+        Arc::new(ConfirmInputInterface::new(ui.clone())),
+        Arc::new(NumberInputInterface::new(ui.clone())),
+        Arc::new(SigningBoxInputInterface::new(ton, ui)),
+    ]
+}
+
 // TODO: implement address validation
 pub fn load_ton_address(addr: &str) -> Result<String, String> {
     Ok(addr.to_owned())
@@ -77,6 +110,24 @@ pub struct DEngine {
     browser: Arc<dyn BrowserCallbacks + Send + Sync>,
     builtin_interfaces: BuiltinInterfaces,
     info: DInfo,
+    node: Option<Arc<MockNode>>,
+    actions: Vec<DAction>,
+    pending_messages: Vec<String>,
+    guard: Arc<PolicyGuard>,
+    abis: Vec<Abi>,
+    locale: Option<String>,
+    ui: Option<Arc<dyn UiCallbacks + Send + Sync>>,
+    recorder: Option<Arc<EventRecorder>>,
+    manage_invoked: bool,
+    /// Engines of DeBots invoked by this one, by address.
+    invoked: HashMap<String, DEngine>,
+    /// Addresses of DeBots which invoked this one, the caller is the last.
+    call_stack: Vec<String>,
+    /// Messages to DeBots from `call_stack` waiting for delivery.
+    answers: Vec<String>,
+    /// Sessions of invoked DeBots restored from the saved session, by address.
+    /// Engines are created when the DeBots receive messages.
+    saved_invoked: HashMap<String, DebotSession>,
 }
 
 impl DEngine {
@@ -111,29 +162,346 @@ impl DEngine {
             target_addr: None,
             target_abi: None,
             browser: browser.clone(),
-            builtin_interfaces: BuiltinInterfaces::new(ton),
+            builtin_interfaces: BuiltinInterfaces::new(ton.clone(), browser.clone()),
             info: Default::default(),
+            node: None,
+            actions: vec![],
+            pending_messages: vec![],
+            guard: Arc::new(PolicyGuard::new(ton, DebotPolicy::default())),
+            abis: vec![],
+            locale: None,
+            ui: None,
+            recorder: None,
+            manage_invoked: false,
+            invoked: HashMap::new(),
+            call_stack: vec![],
+            answers: vec![],
+            saved_invoked: HashMap::new(),
+        }
+    }
+
+    /// Applies restrictions to DeBot execution.
+    pub fn with_policy(mut self, policy: DebotPolicy) -> Self {
+        if let Some(recorder) = self.recorder.as_ref() {
+            recorder.set_policy(policy.clone());
+        }
+        self.guard = Arc::new(PolicyGuard::new(self.ton.clone(), policy));
+        self.builtin_interfaces.add(Arc::new(
+            NetworkInterface::with_guard(self.ton.clone(), self.guard.clone())
+        ));
+        self
+    }
+
+    /// Returns the first policy violation since the last call, if any.
+    ///
+    /// Violations are also reported to DeBot as errors, so engine functions
+    /// do not always fail on them.
+    pub fn take_policy_violation(&self) -> Option<ClientError> {
+        self.guard.take_violation()
+    }
+
+    /// Creates engine from the saved session. Browser callbacks are not called.
+    ///
+    /// Event log recording and running of invoked DeBots are enabled if they
+    /// were enabled in the saved engine. Interfaces added with `add_interface`
+    /// are not saved in the session and must be added again.
+    pub fn restore(
+        session: DebotSession,
+        ton: TonClient,
+        browser: Arc<dyn BrowserCallbacks + Send + Sync>,
+    ) -> Result<Self, String> {
+        if session.version != SESSION_VERSION {
+            return Err(format!("unsupported session version {}", session.version));
+        }
+        let abi = if session.abi.is_empty() { None } else { Some(session.abi.clone()) };
+        if let Some(abi) = abi.as_ref() {
+            load_abi(abi)?;
+        }
+        let mut dengine = DEngine::new_with_client(session.address, abi, ton, browser)
+            .with_locale(session.locale);
+        dengine.state = session.state;
+        dengine.info = session.info.into();
+        dengine.state_machine = session.contexts.into_iter().map(|ctx| ctx.into()).collect();
+        dengine.curr_state = session.curr_context;
+        dengine.prev_state = session.prev_context;
+        dengine.target_addr = session.target_addr;
+        dengine.target_abi = session.target_abi;
+        dengine.actions = session.actions.into_iter().map(|act| act.into()).collect();
+        dengine.pending_messages = session.pending_messages;
+        dengine.manage_invoked = session.manage_invoked_debots;
+        dengine.call_stack = session.call_stack;
+        dengine.answers = session.answers;
+        dengine.saved_invoked = session.invoked
+            .into_iter()
+            .map(|invoked| (invoked.address.clone(), invoked))
+            .collect();
+        if !session.abi.is_empty() {
+            dengine.raw_abi = session.abi;
+            dengine.add_abi_interfaces();
+        }
+        if let Some(log) = session.event_log {
+            dengine = dengine.with_recorder(Arc::new(EventRecorder::from_log(log)));
+        }
+        Ok(dengine)
+    }
+
+    /// Returns the snapshot of the engine state.
+    pub fn save(&self) -> DebotSession {
+        DebotSession {
+            version: SESSION_VERSION,
+            address: self.addr.clone(),
+            abi: self.raw_abi.clone(),
+            state: self.state.clone(),
+            info: self.info.clone().into(),
+            contexts: self.state_machine.iter().cloned().map(|ctx| ctx.into()).collect(),
+            curr_context: self.curr_state,
+            prev_context: self.prev_state,
+            target_addr: self.target_addr.clone(),
+            target_abi: self.target_abi.clone(),
+            actions: self.actions.iter().cloned().map(|act| act.into()).collect(),
+            pending_messages: self.pending_messages.clone(),
+            locale: self.locale.clone(),
+            // invoked DeBots share the recorder of the DeBot which started the dialog
+            event_log: if self.call_stack.is_empty() { self.event_log() } else { None },
+            manage_invoked_debots: self.manage_invoked,
+            invoked: self.invoked.values()
+                .map(|engine| engine.save())
+                .chain(self.saved_invoked.values().cloned())
+                .collect(),
+            call_stack: self.call_stack.clone(),
+            answers: self.answers.clone(),
         }
     }
 
+    /// Continues the dialog of the restored engine.
+    ///
+    /// Shows actions of the current context again and resends
+    /// unanswered messages of this DeBot and invoked DeBots to the browser.
+    pub async fn resume(&mut self) {
+        if self.curr_state != STATE_EXIT {
+            self.browser.switch(self.curr_state).await;
+            for act in self.actions.clone() {
+                self.browser.show_action(act).await;
+            }
+            self.browser.switch_completed().await;
+        }
+        let mut pending_messages = self.pending_messages.clone();
+        for session in self.saved_invoked.values() {
+            session.collect_pending_messages(&mut pending_messages);
+        }
+        for msg in pending_messages {
+            self.browser.send(msg).await;
+        }
+    }
+
+    /// Runs DeBot on the local mock node instead of the network.
+    pub fn with_mock_node(mut self, node: Arc<MockNode>) -> Self {
+        self.node = Some(node);
+        if !self.raw_abi.is_empty() {
+            self.add_abi_interfaces();
+        }
+        self
+    }
+
+    /// Adds interface implemented by the application. Calls to this interface
+    /// are handled by the engine instead of `BrowserCallbacks::send`.
+    pub fn add_interface(&mut self, iface: Arc<dyn DebotInterface + Send + Sync>) {
+        self.builtin_interfaces.add(iface);
+    }
+
+    /// Adds standard Terminal, Menu, AddressInput, AmountInput, ConfirmInput,
+    /// NumberInput and SigningBoxInput interfaces which use `ui` to talk to user.
+    pub fn with_ui(mut self, ui: Arc<dyn UiCallbacks + Send + Sync>) -> Self {
+        self.ui = Some(ui);
+        self.add_ui_interfaces();
+        self
+    }
+
+    /// Sets preferred locale of the user, e.g. `en-US`. DeBot metadata is
+    /// translated to it if DeBot provides translations. DeBot can read it with
+    /// Sdk `getLocale`, Sdk and standard UI interfaces format amounts and dates
+    /// according to it.
+    pub fn with_locale(mut self, locale: Option<String>) -> Self {
+        self.locale = locale;
+        if let Some(recorder) = self.recorder.as_ref() {
+            recorder.set_locale(self.locale.clone());
+        }
+        self.add_interface(Arc::new(
+            SdkInterface::new(self.ton.clone(), self.browser.clone())
+                .with_locale(self.locale.clone()),
+        ));
+        self.add_ui_interfaces();
+        self
+    }
+
+    /// Enables recording of the detailed event log: DeBot functions and get-methods
+    /// calls, interface calls, actions, context switches and browser callbacks.
+    /// The log can be replayed offline with `DebotEventLog::replay`.
+    pub fn with_event_log(self) -> Self {
+        let recorder = Arc::new(EventRecorder::new(self.addr.clone(), self.locale.clone()));
+        if self.manage_invoked {
+            recorder.set_manage_invoked();
+        }
+        recorder.set_policy(self.guard.policy().clone());
+        for abi in &self.abis {
+            recorder.add_abi(abi);
+        }
+        self.with_recorder(recorder)
+    }
+
+    fn with_recorder(mut self, recorder: Arc<EventRecorder>) -> Self {
+        self.browser = Arc::new(RecordingBrowser::new(self.browser.clone(), recorder.clone()));
+        self.recorder = Some(recorder);
+        // interfaces which talk to browser must use the recording one
+        self.add_interface(Arc::new(
+            SdkInterface::new(self.ton.clone(), self.browser.clone())
+                .with_locale(self.locale.clone()),
+        ));
+        if !self.raw_abi.is_empty() {
+            self.add_abi_interfaces();
+        }
+        self
+    }
+
+    /// Makes the engine run DeBots invoked with internal messages instead of
+    /// passing the messages to `BrowserCallbacks::send`.
+    ///
+    /// Invoked DeBots use the same browser, interfaces and policy as this
+    /// DeBot, their activities are approved with the same browser. Answers
+    /// are delivered back to the calling DeBot. Invoked DeBots are saved
+    /// in the session. Invoke actions are still passed to `BrowserCallbacks::invoke_debot`.
+    pub fn with_invoked_debots(mut self) -> Self {
+        self.manage_invoked = true;
+        if let Some(recorder) = self.recorder.as_ref() {
+            recorder.set_manage_invoked();
+        }
+        self
+    }
+
+    /// Returns the event log recorded so far, `None` if recording is not enabled.
+    pub fn event_log(&self) -> Option<DebotEventLog> {
+        self.recorder.as_ref().map(|recorder| recorder.snapshot())
+    }
+
+    fn record(&self, event: DebotEvent) {
+        if let Some(recorder) = self.recorder.as_ref() {
+            recorder.record(event);
+        }
+    }
+
+    fn record_failure<T>(&self, result: &Result<T, impl std::fmt::Display>) {
+        if let Err(e) = result {
+            self.record(DebotEvent::Failed { error: e.to_string() });
+        }
+    }
+
+    fn add_ui_interfaces(&mut self) {
+        let ui = match self.ui.clone() {
+            Some(ui) => ui,
+            None => return,
+        };
+        for iface in ui_interfaces(self.ton.clone(), ui, self.locale.clone()) {
+            self.add_interface(iface);
+        }
+    }
+
+    /// Adds ABI of the contract DeBot works with. Known ABIs are used
+    /// to decode messages in activities passed to `BrowserCallbacks::approve`.
+    pub fn add_abi(&mut self, abi: Abi) {
+        if let Some(recorder) = self.recorder.as_ref() {
+            recorder.add_abi(&abi);
+        }
+        self.abis.push(abi);
+    }
+
+    fn known_abis(&self) -> Vec<Abi> {
+        let mut abis = self.abis.clone();
+        if let Some(abi) = self.target_abi.as_ref().and_then(|abi| load_abi(abi).ok()) {
+            abis.push(abi);
+        }
+        abis
+    }
+
     pub async fn fetch(ton: TonClient, addr: String) -> Result<DInfo, String> {
         let state = Self::load_state(ton.clone(), addr.clone()).await?;
-        Self::fetch_info(ton, addr, state).await
+        Self::fetch_info(ton, addr, state, None).await
     }
 
     pub async fn init(&mut self) -> Result<DInfo, String> {
-        self.fetch_state().await?;
+        self.record(DebotEvent::Init);
+        let result = self.fetch_state().await;
+        self.record_failure(&result);
+        result?;
         self.prev_state = STATE_EXIT;
         Ok(self.info.clone())
     }
 
+    /// Analyzes code of the DeBot loaded by `init` without running it.
+    pub fn inspect(&self) -> Result<ResultOfInspect, String> {
+        Self::inspect_code(
+            &self.state,
+            &self.info.interfaces,
+            self.builtin_interfaces.get_interfaces(),
+            self.target_abi.as_deref(),
+        )
+    }
+
+    /// Analyzes code of the DeBot with the given account state without creating
+    /// an engine. Only get-methods returning DeBot metadata and options are called.
+    pub async fn inspect_state(
+        ton: TonClient,
+        addr: String,
+        state: String,
+    ) -> Result<ResultOfInspect, String> {
+        let info = Self::fetch_info(ton.clone(), addr.clone(), state.clone(), None).await?;
+        let abi = load_abi(info.dabi.as_deref().unwrap_or(DEBOT_ABI))?;
+        let options = Self::run(
+            ton.clone(), state.clone(), addr, abi, "getDebotOptions", None, None
+        ).await.map_err(|e| e.to_string())?;
+        let (target_abi, _) = Self::parse_options(options.return_value)?;
+
+        let mut interfaces = BuiltinInterfaces::new(ton.clone(), Arc::new(inspect::NoBrowser));
+        for iface in ui_interfaces(ton, Arc::new(inspect::NoUi), None) {
+            interfaces.add(iface);
+        }
+        Self::inspect_code(
+            &state,
+            &info.interfaces,
+            interfaces.get_interfaces(),
+            target_abi.as_deref(),
+        )
+    }
+
+    fn inspect_code(
+        state: &str,
+        declared_interfaces: &[String],
+        interfaces: &HashMap<String, Arc<dyn DebotInterface + Send + Sync>>,
+        target_abi: Option<&str>,
+    ) -> Result<ResultOfInspect, String> {
+        let account: ton_block::Account = deserialize_object_from_base64(state, "account")
+            .map_err(|e| format!("failed to load debot state: {}", e))?
+            .object;
+        let code = account.get_code().ok_or("debot has no code")?;
+        Ok(inspect::inspect(&code, declared_interfaces, interfaces, target_abi))
+    }
+
     pub async fn start(&mut self) -> Result<(), String> {
-        self.fetch_state().await?;
-        self.switch_state(STATE_ZERO, true).await?;
-        Ok(())
+        self.record(DebotEvent::Start);
+        self.guard.begin_action();
+        let result = match self.fetch_state().await {
+            Ok(()) => self.switch_state(STATE_ZERO, true).await,
+            Err(e) => Err(e),
+        };
+        self.record_failure(&result);
+        result
     }
 
-    async fn fetch_info(ton: TonClient, addr: String, state: String) -> Result<DInfo, String> {
+    async fn fetch_info(
+        ton: TonClient,
+        addr: String,
+        state: String,
+        options: Option<ExecutionOptions>,
+    ) -> Result<DInfo, String> {
         let abi = load_abi(DEBOT_ABI).unwrap();
         let result = Self::run(
             ton.clone(),
@@ -141,7 +509,8 @@ impl DEngine {
             addr.clone(),
             abi.clone(),
             "getRequiredInterfaces",
-            None
+            None,
+            options.clone(),
         ).await;
         let interfaces: Vec<String> = match result {
             Ok(r) => {
@@ -153,7 +522,15 @@ impl DEngine {
             },
             Err(_) => vec![],
         };
-        let result = Self::run(ton.clone(), state.clone(), addr.clone(), abi.clone(), "getDebotInfo", None).await;
+        let translations_abi = load_abi(DEBOT_INFO_TRANSLATIONS_ABI).unwrap();
+        let result = match Self::run(
+            ton.clone(), state.clone(), addr.clone(), translations_abi, "getDebotInfo", None, options.clone()
+        ).await {
+            Ok(r) => Ok(r),
+            Err(_) => Self::run(
+                ton.clone(), state.clone(), addr.clone(), abi.clone(), "getDebotInfo", None, options.clone()
+            ).await,
+        };
         let mut info: DInfo = match result {
             Ok(r) => {
                 let output = r.return_value.unwrap_or(json!({}));
@@ -167,7 +544,7 @@ impl DEngine {
         // TODO: for compatibility with previous debots that returns abi in
         // getDebotOptions. Remove later.
         if info.dabi.is_none() {
-            let params = Self::run(ton, state, addr, abi, "getDebotOptions", None).await;
+            let params = Self::run(ton, state, addr, abi, "getDebotOptions", None, options).await;
             if let Ok(params) = params {
                 let params = params.return_value.unwrap_or(json!({}));
                 let opt_str = params["options"].as_str().unwrap_or("0");
@@ -184,22 +561,18 @@ impl DEngine {
     }
 
     async fn fetch_state(&mut self) -> Result<(), String> {
-        self.state = Self::load_state(self.ton.clone(), self.addr.clone()).await?;
-        self.info = Self::fetch_info(self.ton.clone(), self.addr.clone(), self.state.clone()).await?;
+        self.state = self.load_account(self.addr.clone()).await?;
+        self.info = Self::fetch_info(
+            self.ton.clone(),
+            self.addr.clone(),
+            self.state.clone(),
+            self.guard.execution_options(),
+        ).await?;
+        self.info.localize(&Locale::new(self.locale.clone()));
         if let Some(dabi) = self.info.dabi.as_ref() {
             self.raw_abi = dabi.clone();
             self.abi = load_abi(&self.raw_abi)?;
-            self.builtin_interfaces.add(
-                Arc::new(MsgInterface::new(
-                    self.ton.clone(),
-                    self.addr.clone(),
-                    self.abi.clone(),
-                    self.browser.clone(),
-                ))
-            );
-            self.builtin_interfaces.add(
-                Arc::new(JsonInterface::new(&self.raw_abi))
-            );
+            self.add_abi_interfaces();
         }
         self.update_options().await?;
         let result = self.run_debot_external("fetch", None).await;
@@ -228,8 +601,25 @@ impl DEngine {
         Ok(())
     }
 
+    fn add_abi_interfaces(&mut self) {
+        self.builtin_interfaces.add(
+            Arc::new(MsgInterface::new(
+                self.ton.clone(),
+                self.addr.clone(),
+                self.abi.clone(),
+                self.browser.clone(),
+                self.node.clone(),
+            ))
+        );
+        self.builtin_interfaces.add(
+            Arc::new(JsonInterface::new(&self.raw_abi))
+        );
+    }
+
     pub async fn execute_action(&mut self, act: &DAction) -> Result<(), String> {
-        match self.handle_action(&act).await {
+        self.record(DebotEvent::Execute { action: act.clone().into() });
+        self.guard.begin_action();
+        let result = match self.handle_action(&act).await {
             Ok(acts) => {
                 if let Some(acts) = acts {
                     for a in acts {
@@ -246,14 +636,108 @@ impl DEngine {
                     .await;
                 self.switch_state(self.prev_state, false).await
             }
-        }
+        };
+        self.record_failure(&result);
+        result
     }
 
     pub async fn send(&mut self, message: String) -> ClientResult<()> {
-        let output = self.send_to_debot(message).await?;
+        self.record(DebotEvent::Receive { message: message.clone() });
+        self.guard.begin_action();
+        let result = self.receive(message).await;
+        self.record_failure(&result);
+        result
+    }
+
+    /// Delivers message to this DeBot or to the invoked DeBot it is addressed to.
+    async fn receive(&mut self, message: String) -> ClientResult<()> {
+        if let Some(pos) = self.pending_messages
+            .iter()
+            .position(|request| session::is_answer(&message, request))
+        {
+            self.pending_messages.remove(pos);
+        }
+        let dst = message_dst(&message);
+        let invoked = self.invoked.iter()
+            .find(|(_, engine)| engine.runs(&dst))
+            .map(|(addr, _)| addr.clone())
+            .or_else(|| {
+                self.saved_invoked.iter()
+                    .find(|(_, session)| session.runs(&dst))
+                    .map(|(addr, _)| addr.clone())
+            });
+        let output = match invoked {
+            Some(addr) => self.send_to_invoked(addr, message).await?,
+            None => self.send_to_debot(message).await?,
+        };
         self.handle_output(output).await
     }
 
+    fn receive_boxed(&mut self, message: String) -> BoxFuture<'_, ClientResult<()>> {
+        self.receive(message).boxed()
+    }
+
+    /// Returns true if `addr` is the address of this DeBot or of DeBot invoked by it.
+    fn runs(&self, addr: &str) -> bool {
+        self.addr == addr
+            || self.invoked.values().any(|engine| engine.runs(addr))
+            || self.saved_invoked.values().any(|session| session.runs(addr))
+    }
+
+    /// Creates engine for DeBot invoked by this one. Engine of DeBot saved
+    /// in the session is restored from it.
+    async fn invoked_engine(&mut self, addr: String) -> ClientResult<DEngine> {
+        let saved = self.saved_invoked.remove(&addr);
+        let restored = saved.is_some();
+        let mut engine = match saved {
+            Some(session) => DEngine::restore(session, self.ton.clone(), self.browser.clone())
+                .map_err(Error::invalid_session)?,
+            None => DEngine::new_with_client(addr, None, self.ton.clone(), self.browser.clone()),
+        };
+        engine.node = self.node.clone();
+        engine.guard = self.guard.clone();
+        engine.locale = self.locale.clone();
+        engine.ui = self.ui.clone();
+        engine.abis = self.abis.clone();
+        engine.recorder = self.recorder.clone();
+        engine.manage_invoked = true;
+        engine.call_stack = self.call_stack.clone();
+        engine.call_stack.push(self.addr.clone());
+        // Msg and Json interfaces of this DeBot are bound to its address and ABI
+        for (id, iface) in self.builtin_interfaces.get_interfaces() {
+            if id != msg_interface::ID && id != json_interface::BASE64_ID {
+                engine.add_interface(iface.clone());
+            }
+        }
+        if !restored {
+            engine.fetch_state().await.map_err(Error::execute_failed)?;
+        }
+        engine.add_abi_interfaces();
+        Ok(engine)
+    }
+
+    /// Delivers message to the invoked DeBot, then delivers its answers to this DeBot.
+    /// Answers to DeBots deeper in the call stack are passed to the caller.
+    async fn send_to_invoked(&mut self, addr: String, message: String) -> ClientResult<RunOutput> {
+        let mut engine = match self.invoked.remove(&addr) {
+            Some(engine) => engine,
+            None => self.invoked_engine(addr.clone()).await?,
+        };
+        let result = engine.receive_boxed(message).await;
+        let answers = std::mem::take(&mut engine.answers);
+        self.invoked.insert(addr, engine);
+        result?;
+        let mut output = RunOutput::default();
+        for answer in answers {
+            if message_dst(&answer) == self.addr {
+                output.append(self.send_to_debot(answer).await?);
+            } else {
+                self.answers.push(answer);
+            }
+        }
+        Ok(output)
+    }
+
     async fn run_debot_internal(
         &mut self,
         source: String,
@@ -281,16 +765,27 @@ impl DEngine {
     }
 
     async fn send_to_debot(&mut self, msg: String) -> ClientResult<RunOutput> {
+        let started = self.guard.now();
         let run_result = run_tvm(
             self.ton.clone(),
             ParamsOfRunTvm {
                 account: self.state.clone(),
-                message: msg,
+                message: msg.clone(),
                 abi: Some(self.abi.clone()),
                 return_updated_account: Some(true),
+                execution_options: self.guard.execution_options(),
                 ..Default::default()
             },
-        ).await?;
+        ).await;
+        self.guard.check_run(started, run_result.as_ref().err())?;
+        if self.recorder.is_some() {
+            let (output, error) = match run_result.as_ref() {
+                Ok(result) => (result.decoded.as_ref().and_then(|x| x.output.clone()), None),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            self.record(DebotEvent::InternalMessage { message: msg, output, error });
+        }
+        let run_result = run_result?;
         let mut run_output = RunOutput::new(
             run_result.account,
             self.addr.clone(),
@@ -302,6 +797,7 @@ impl DEngine {
     }
 
     async fn handle_action(&mut self, a: &DAction) -> Result<Option<Vec<DAction>>, String> {
+        self.record(DebotEvent::Action { action: a.clone().into() });
         match a.action_type {
             AcType::Empty => {
                 debug!("empty action: {}", a.name);
@@ -384,6 +880,7 @@ impl DEngine {
                 } else {
                     a.name.clone()
                 };
+                self.guard.count_message().map_err(|e| e.message)?;
                 self.browser.log(label).await;
                 Ok(None)
             }
@@ -436,6 +933,7 @@ impl DEngine {
             state_to = self.prev_state;
         }
         if state_to == STATE_EXIT {
+            self.actions.clear();
             self.browser.switch(STATE_EXIT).await;
             self.browser.switch_completed().await;
         } else if state_to != self.curr_state || force {
@@ -450,6 +948,7 @@ impl DEngine {
                     .find(|ctx| ctx.id == state_to)
                     .map(|ctx| ctx.clone());
                 if let Some(ctx) = jump_to_ctx {
+                    self.actions.clear();
                     self.browser.switch(state_to).await;
                     self.browser.log(ctx.desc.clone()).await;
                     instant_switch = self.enumerate_actions(ctx).await?;
@@ -504,6 +1003,7 @@ impl DEngine {
                 } else if act.is_engine_call() {
                     self.handle_action(&act).await?;
                 } else {
+                    self.actions.push(act.clone());
                     self.browser.show_action(act).await;
                 }
             }
@@ -517,8 +1017,7 @@ impl DEngine {
         args: Option<JsonValue>,
     ) -> Result<RunOutput, String> {
         debug!("run_debot_external {}, args: {}", name, args.as_ref().unwrap_or(&json!({})));
-        let res = Self::run(
-            self.ton.clone(),
+        let res = self.run_limited(
             self.state.clone(),
             self.addr.clone(),
             self.abi.clone(),
@@ -598,8 +1097,8 @@ impl DEngine {
             return Err(format!("target address is undefined"));
         }
         let (addr, abi) = self.get_target()?;
-        let state = Self::load_state(self.ton.clone(), addr.clone()).await?;
-        let result = Self::run(self.ton.clone(), state, addr, abi, getmethod, args).await;
+        let state = self.load_account(addr.clone()).await?;
+        let result = self.run_limited(state, addr, abi, getmethod, args).await;
         let result = match result {
             Ok(r) => Ok(r.return_value),
             Err(e) => Err(self.handle_sdk_err(e).await),
@@ -633,19 +1132,49 @@ impl DEngine {
         Ok(state)
     }
 
+    async fn load_account(&self, addr: String) -> Result<String, String> {
+        let state = match self.node.as_ref() {
+            Some(node) => node.account(&addr).await.ok_or_else(|| format!(
+                "Cannot find smart contract with this address {} in mock node",
+                addr
+            )),
+            None => Self::load_state(self.ton.clone(), addr.clone()).await,
+        }?;
+        if let Some(recorder) = self.recorder.as_ref() {
+            recorder.add_account(&addr, &state);
+        }
+        Ok(state)
+    }
+
     async fn update_options(&mut self) -> Result<(), String> {
         let params = self.run_debot_external("getDebotOptions", None).await?.return_value;
+        let (target_abi, target_addr) = Self::parse_options(params)?;
+        if target_abi.is_some() {
+            self.target_abi = target_abi;
+        }
+        if target_addr.is_some() {
+            self.target_addr = target_addr;
+        }
+        Ok(())
+    }
+
+    /// Returns target ABI and target address from `getDebotOptions` output.
+    fn parse_options(
+        params: Option<JsonValue>,
+    ) -> Result<(Option<String>, Option<String>), String> {
         let params = params.ok_or(format!("no return value"))?;
         let opt_str = params["options"].as_str().unwrap();
         let options = decode_abi_number::<u8>(opt_str).unwrap();
+        let mut target_abi = None;
+        let mut target_addr = None;
         if options & OPTION_TARGET_ABI != 0 {
-            self.target_abi = str_hex_to_utf8(params["targetAbi"].as_str().unwrap());
+            target_abi = str_hex_to_utf8(params["targetAbi"].as_str().unwrap());
         }
         if (options & OPTION_TARGET_ADDR) != 0 {
             let addr = params["targetAddr"].as_str().unwrap();
-            self.target_addr = Some(load_ton_address(addr)?);
+            target_addr = Some(load_ton_address(addr)?);
         }
-        Ok(())
+        Ok((target_abi, target_addr))
     }
 
     async fn query_action_args(&self, act: &DAction) -> Result<Option<JsonValue>, String> {
@@ -692,6 +1221,34 @@ impl DEngine {
         Ok((addr, abi_obj))
     }
 
+    async fn run_limited(
+        &self,
+        state: String,
+        addr: String,
+        abi: Abi,
+        func: &str,
+        args: Option<JsonValue>,
+    ) -> Result<RunOutput, ClientError> {
+        let started = self.guard.now();
+        let options = self.guard.execution_options();
+        let input = args.clone();
+        let result = Self::run(self.ton.clone(), state, addr.clone(), abi, func, args, options).await;
+        self.guard.check_run(started, result.as_ref().err())?;
+        if self.recorder.is_some() {
+            let (output, error) = match result.as_ref() {
+                Ok(res) => (res.return_value.clone(), None),
+                Err(e) => (None, Some(e.to_string())),
+            };
+            let function = func.to_owned();
+            self.record(if addr == self.addr {
+                DebotEvent::RunTvm { function, input, output, error }
+            } else {
+                DebotEvent::GetMethod { address: addr, function, input, output, error }
+            });
+        }
+        result
+    }
+
     async fn run(
         ton: TonClient,
         state: String,
@@ -699,6 +1256,7 @@ impl DEngine {
         abi: Abi,
         func: &str,
         args: Option<JsonValue>,
+        options: Option<ExecutionOptions>,
     ) -> Result<RunOutput, ClientError> {
         debug!("running {}, addr {}", func, &addr);
 
@@ -724,6 +1282,7 @@ impl DEngine {
                 message: result.message,
                 abi: Some(abi),
                 return_updated_account: Some(true),
+                execution_options: options,
                 ..Default::default()
             },
         ).await;
@@ -768,6 +1327,17 @@ impl DEngine {
             processing_try_index: None,
         };
 
+        if let Some(node) = self.node.as_ref() {
+            let result = match encode_message(self.ton.clone(), call_params).await {
+                Ok(encoded) => node.process_message(self.ton.clone(), encoded.message, Some(abi)).await,
+                Err(e) => Err(e),
+            };
+            return match result {
+                Ok(res) => Ok(res.decoded.and_then(|x| x.output)),
+                Err(e) => Err(self.handle_sdk_err(e).await),
+            };
+        }
+
         let browser = self.browser.clone();
         let callback = move |event| {
             debug!("{:?}", event);
@@ -814,10 +1384,35 @@ impl DEngine {
             match call {
                 DebotCallType::Interface{msg, id} => {
                     debug!("Interface call");
-                    match self.builtin_interfaces.try_execute(&msg, &id).await {
-                        None => self.browser.send(msg).await,
+                    self.guard.count_interface_call()?;
+                    let interfaces = self.builtin_interfaces.get_interfaces();
+                    let result = match interfaces.get(&id) {
+                        Some(iface) => {
+                            let result =
+                                BuiltinInterfaces::execute(self.ton.clone(), &msg, &id, interfaces).await;
+                            if let Some(recorder) = self.recorder.as_ref() {
+                                recorder
+                                    .record_interface_call(self.ton.clone(), &msg, iface.as_ref(), &result)
+                                    .await;
+                            }
+                            Some(result)
+                        },
+                        None => None,
+                    };
+                    match result {
+                        None => {
+                            self.guard.count_message()?;
+                            self.pending_messages.push(msg.clone());
+                            self.browser.send(msg).await;
+                        },
                         Some(result) => {
-                            let (fname, args) = result.map_err(|e| Error::execute_failed(e))?;
+                            let (fname, args) = result.map_err(|e| {
+                                self.guard.violation().unwrap_or_else(|| Error::execute_failed(e))
+                            })?;
+                            // answer id 0 means that DeBot does not expect an answer
+                            if fname == 0 {
+                                continue;
+                            }
                             let new_outputs = self.run_debot_internal(
                                 format!("{}:{}", DEBOT_WC, id),
                                 fname,
@@ -829,7 +1424,7 @@ impl DEngine {
                 },
                 DebotCallType::GetMethod{msg, dest} => {
                     debug!("GetMethod call");
-                    let target_state = Self::load_state(self.ton.clone(), dest.clone()).await
+                    let target_state = self.load_account(dest.clone()).await
                         .map_err(|e| Error::execute_failed(e))?;
                     let callobj = ContractCall::new(
                         self.browser.clone(),
@@ -839,14 +1434,20 @@ impl DEngine {
                         target_state,
                         self.addr.clone(),
                         true,
+                        self.node.clone(),
+                        self.known_abis(),
                     ).await?;
                     let answer_msg = callobj.execute().await?;
                     output.append(self.send_to_debot(answer_msg).await?);
                 },
                 DebotCallType::External{msg, dest} => {
                     debug!("External call");
-                    let target_state = Self::load_state(self.ton.clone(), dest.clone()).await
-                        .map_err(|e| Error::execute_failed(e))?;
+                    let target_state = match self.load_account(dest.clone()).await {
+                        Ok(state) => state,
+                        // contract is not deployed yet
+                        Err(_) if has_state_init(&msg) => String::new(),
+                        Err(e) => return Err(Error::execute_failed(e)),
+                    };
                     let callobj = ContractCall::new(
                         self.browser.clone(),
                         self.ton.clone(),
@@ -855,12 +1456,35 @@ impl DEngine {
                         target_state,
                         self.addr.clone(),
                         false,
+                        self.node.clone(),
+                        self.known_abis(),
                     ).await?;
                     let answer_msg = callobj.execute().await?;
                     output.append(self.send_to_debot(answer_msg).await?);
                 },
                 DebotCallType::Invoke{msg} => {
                     debug!("Invoke call");
+                    self.guard.count_message()?;
+                    let dst = message_dst(&msg);
+                    if self.call_stack.contains(&dst) {
+                        // answer to the calling DeBot
+                        self.answers.push(msg);
+                        continue;
+                    }
+                    let call = decode_call(self.ton.clone(), &self.known_abis(), &msg).await;
+                    let activity = DebotActivity::InvokeDebot {
+                        debot: dst.clone(),
+                        msg: msg.clone(),
+                        call,
+                    };
+                    if !self.browser.approve(activity).await? {
+                        return Err(Error::operation_rejected());
+                    }
+                    if self.manage_invoked {
+                        output.append(self.send_to_invoked(dst, msg).await?);
+                        continue;
+                    }
+                    self.pending_messages.push(msg.clone());
                     self.browser.send(msg).await;
                 },
             }
@@ -883,7 +1507,8 @@ impl DEngine {
                     self.addr.clone(),
                     self.abi.clone(),
                     "getErrorDescription",
-                    Some(json!({ "error": e }))
+                    Some(json!({ "error": e })),
+                    self.guard.execution_options(),
                 ).await.ok().and_then(|res| {
                     res.return_value.and_then(|v|
                         v["desc"].as_str().and_then(|hex| {
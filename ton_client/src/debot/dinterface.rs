@@ -3,6 +3,7 @@ use super::hex_interface::HexInterface;
 use super::sdk_interface::SdkInterface;
 use super::network_interface::NetworkInterface;
 use super::query_interface::QueryInterface;
+use super::BrowserCallbacks;
 use crate::abi::{decode_message_body, Abi, ParamsOfDecodeMessageBody};
 use crate::boc::{parse_message, ParamsOfParse};
 use crate::debot::TonClient;
@@ -33,6 +34,23 @@ async fn decode_msg(
     Ok((func, args))
 }
 
+/// Decodes message sent by DeBot to the interface. Returns function name and arguments.
+pub(super) async fn decode_interface_call(
+    client: TonClient,
+    msg: &str,
+    abi: Abi,
+) -> Result<(String, Value), String> {
+    let parsed = parse_message(client.clone(), ParamsOfParse { boc: msg.to_owned() })
+        .await
+        .map_err(|e| format!("{}", e))?;
+
+    let body = parsed.parsed["body"]
+        .as_str()
+        .ok_or(format!("parsed message has no body"))?
+        .to_owned();
+    decode_msg(client, body, abi).await
+}
+
 #[async_trait::async_trait]
 pub trait DebotInterface {
     fn get_id(&self) -> String;
@@ -106,7 +124,7 @@ impl DebotInterfaceExecutor for BuiltinInterfaces {
 }
 
 impl BuiltinInterfaces {
-    pub fn new(client: TonClient) -> Self {
+    pub fn new(client: TonClient, browser: Arc<dyn BrowserCallbacks + Send + Sync>) -> Self {
         let mut interfaces = HashMap::new();
 
         let iface: Arc<dyn DebotInterface + Send + Sync> = Arc::new(Base64Interface::new());
@@ -122,7 +140,7 @@ impl BuiltinInterfaces {
         interfaces.insert(iface.get_id(), iface);
 
         let iface: Arc<dyn DebotInterface + Send + Sync> =
-            Arc::new(SdkInterface::new(client.clone()));
+            Arc::new(SdkInterface::new(client.clone(), browser));
         interfaces.insert(iface.get_id(), iface);
 
         Self { client, interfaces }
@@ -28,6 +28,15 @@ pub enum ErrorCode {
     DebotExternalCallFailed = 810,
     DebotBrowserCallbackFailed = 811,
     DebotOperationRejected = 812,
+    DebotScriptFailed = 813,
+    DebotInvalidSession = 814,
+    DebotGasLimitExceeded = 815,
+    DebotTimeLimitExceeded = 816,
+    DebotInterfaceCallsLimitExceeded = 817,
+    DebotUrlNotAllowed = 818,
+    DebotRateLimitExceeded = 819,
+    DebotInvalidInterface = 820,
+    DebotInvalidEventLog = 821,
 }
 pub struct Error;
 
@@ -119,4 +128,67 @@ impl Error {
             format!("Debot browser callback failed: {}", err),
         )
     }
+
+    pub fn script_failed(err: impl Display) -> ClientError {
+        error(
+            ErrorCode::DebotScriptFailed,
+            format!("Debot script failed: {}", err),
+        )
+    }
+
+    pub fn invalid_session(err: impl Display) -> ClientError {
+        error(
+            ErrorCode::DebotInvalidSession,
+            format!("Invalid debot session: {}", err),
+        )
+    }
+
+    pub fn gas_limit_exceeded(limit: u64) -> ClientError {
+        error(
+            ErrorCode::DebotGasLimitExceeded,
+            format!("Debot function exceeded gas limit {}", limit),
+        )
+    }
+
+    pub fn time_limit_exceeded(limit: u32) -> ClientError {
+        error(
+            ErrorCode::DebotTimeLimitExceeded,
+            format!("Debot function exceeded time limit {} ms", limit),
+        )
+    }
+
+    pub fn interface_calls_limit_exceeded(limit: u32) -> ClientError {
+        error(
+            ErrorCode::DebotInterfaceCallsLimitExceeded,
+            format!("Debot made more than {} interface calls in one action", limit),
+        )
+    }
+
+    pub fn url_not_allowed(url: &str) -> ClientError {
+        error(
+            ErrorCode::DebotUrlNotAllowed,
+            format!("Debot is not allowed to access {}", url),
+        )
+    }
+
+    pub fn rate_limit_exceeded(err: impl Display) -> ClientError {
+        error(
+            ErrorCode::DebotRateLimitExceeded,
+            format!("Debot rate limit exceeded: {}", err),
+        )
+    }
+
+    pub fn invalid_interface(err: impl Display) -> ClientError {
+        error(
+            ErrorCode::DebotInvalidInterface,
+            format!("Invalid debot interface: {}", err),
+        )
+    }
+
+    pub fn invalid_event_log(err: impl Display) -> ClientError {
+        error(
+            ErrorCode::DebotInvalidEventLog,
+            format!("Invalid debot event log: {}", err),
+        )
+    }
 }
@@ -0,0 +1,539 @@
+/*
+* Copyright 2018-2021 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use super::calltype::message_dst;
+use super::dinterface::{decode_interface_call, DebotInterface, InterfaceResult};
+use super::{
+    BrowserCallbacks, DAction, DEngine, DebotAction, DebotActivity, DebotPolicy, MockNode,
+    TonClient,
+};
+use crate::abi::Abi;
+use crate::boc::internal::{deserialize_object_from_base64, serialize_object_to_base64};
+use crate::crypto::SigningBoxHandle;
+use crate::error::ClientResult;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use ton_block::Message;
+
+pub const EVENT_LOG_VERSION: u8 = 1;
+
+/// Event of the DeBot execution recorded by the engine.
+///
+/// `Init`, `Start`, `Execute` and `Receive` are the engine operations requested
+/// by the application, they are repeated on replay. Other events are results
+/// of these operations.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type")]
+pub enum DebotEvent {
+    Init,
+    Start,
+    /// Action chosen by the user.
+    Execute { action: DebotAction },
+    /// Message sent to DeBot by the application: answer of the interface
+    /// implemented by the application or of the invoked DeBot.
+    Receive { message: String },
+    /// Operation requested by the application failed.
+    Failed { error: String },
+    /// DeBot function called by the engine.
+    RunTvm {
+        function: String,
+        input: Option<Value>,
+        output: Option<Value>,
+        error: Option<String>,
+    },
+    /// Get-method of another contract called by the engine.
+    GetMethod {
+        address: String,
+        function: String,
+        input: Option<Value>,
+        output: Option<Value>,
+        error: Option<String>,
+    },
+    /// Internal message delivered to DeBot: answer of the interface or the contract.
+    InternalMessage {
+        message: String,
+        output: Option<Value>,
+        error: Option<String>,
+    },
+    /// Call of the interface implemented in the engine.
+    InterfaceCall {
+        interface_id: String,
+        function: String,
+        input: Value,
+        answer_id: Option<u32>,
+        output: Option<Value>,
+        error: Option<String>,
+    },
+    /// Action handled by the engine.
+    Action { action: DebotAction },
+    Switch { context_id: u8 },
+    ShowAction { action: DebotAction },
+    Log { text: String },
+    Input { prompt: String, value: String },
+    GetSigningBox { handle: Option<u32>, error: Option<String> },
+    Approve { activity: DebotActivity, approved: bool },
+    InvokeDebot { debot: String, action: DebotAction },
+    /// Message sent to the application: call of the interface implemented
+    /// by the application or invocation of another DeBot.
+    Send { dst: String, message: String },
+}
+
+/// Account state loaded by the engine for the first time.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct RecordedAccount {
+    pub address: String,
+    pub boc: String,
+}
+
+/// ABI of the interface called by DeBot.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct RecordedInterface {
+    pub id: String,
+    pub abi: Abi,
+}
+
+/// Detailed log of the DeBot execution.
+///
+/// Contains everything needed to replay the execution offline: states of
+/// accounts used by DeBot, interfaces answers and user input.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct DebotEventLog {
+    pub version: u8,
+    /// DeBot address.
+    pub address: String,
+    pub locale: Option<String>,
+    /// Invoked DeBots were run by the engine.
+    #[serde(default)]
+    pub manage_invoked_debots: bool,
+    /// Policy applied to DeBot execution.
+    #[serde(default)]
+    pub policy: DebotPolicy,
+    /// ABIs of contracts DeBot works with, used to decode activities.
+    #[serde(default)]
+    pub abis: Vec<Abi>,
+    pub accounts: Vec<RecordedAccount>,
+    pub interfaces: Vec<RecordedInterface>,
+    pub events: Vec<DebotEvent>,
+}
+
+impl DebotEventLog {
+    /// Returns index of the first event which differs in two logs.
+    ///
+    /// Creation time of messages, signatures and time headers of external
+    /// messages, fees and signing box handles are ignored since they depend
+    /// on time and the environment.
+    pub fn first_difference(&self, other: &DebotEventLog) -> Option<usize> {
+        let len = std::cmp::max(self.events.len(), other.events.len());
+        (0..len).find(|&i| {
+            self.events.get(i).map(comparable) != other.events.get(i).map(comparable)
+        })
+    }
+
+    /// Repeats the recorded operations against the same DeBot offline and
+    /// returns the new log.
+    ///
+    /// Accounts are emulated by `MockNode` starting from the recorded states.
+    /// The recorded policy and ABIs are applied to DeBot execution. Interface
+    /// calls are answered with the recorded answers, browser callbacks with
+    /// the recorded user input and approvals. DeBot requests of a signing box
+    /// get `signing_box` if the recorded request succeeded.
+    pub async fn replay(
+        &self,
+        ton: TonClient,
+        signing_box: Option<SigningBoxHandle>,
+    ) -> ClientResult<DebotEventLog> {
+        let node = Arc::new(MockNode::new());
+        for account in &self.accounts {
+            node.set_account(account.address.clone(), account.boc.clone()).await;
+        }
+        let browser = Arc::new(ReplayBrowser::new(&self.events, signing_box));
+        let mut engine = DEngine::new_with_client(self.address.clone(), None, ton, browser)
+            .with_mock_node(node)
+            .with_policy(self.policy.clone())
+            .with_locale(self.locale.clone())
+            .with_event_log();
+        if self.manage_invoked_debots {
+            engine = engine.with_invoked_debots();
+        }
+        for abi in &self.abis {
+            engine.add_abi(abi.clone());
+        }
+        for iface in &self.interfaces {
+            engine.add_interface(Arc::new(ReplayInterface::new(iface, &self.events)));
+        }
+        // failures are recorded in the log
+        for event in &self.events {
+            match event {
+                DebotEvent::Init => {
+                    let _ = engine.init().await;
+                }
+                DebotEvent::Start => {
+                    let _ = engine.start().await;
+                }
+                DebotEvent::Execute { action } => {
+                    let action: DAction = action.clone().into();
+                    let _ = engine.execute_action(&action).await;
+                }
+                DebotEvent::Receive { message } => {
+                    let _ = engine.send(message.clone()).await;
+                }
+                _ => {}
+            }
+        }
+        Ok(engine.event_log().unwrap_or_default())
+    }
+}
+
+fn comparable(event: &DebotEvent) -> Value {
+    let mut value = serde_json::to_value(event).unwrap_or_default();
+    remove_volatile_fields(&mut value);
+    value
+}
+
+fn remove_volatile_fields(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for key in &["fee", "signing_box_handle", "handle"] {
+                map.remove(*key);
+            }
+            for key in &["message", "msg"] {
+                let message = map
+                    .get(*key)
+                    .and_then(|msg| msg.as_str())
+                    .and_then(comparable_message);
+                if let Some(message) = message {
+                    map.insert(key.to_string(), message);
+                }
+            }
+            map.values_mut().for_each(remove_volatile_fields);
+        }
+        Value::Array(items) => items.iter_mut().for_each(remove_volatile_fields),
+        _ => {}
+    }
+}
+
+/// Returns message without creation time. `None` if `boc` is not a message.
+///
+/// Body of the external inbound message starts with the signature and time headers,
+/// so only its destination and state init are kept. The call itself is compared
+/// in the decoded form reported with the activity.
+fn comparable_message(boc: &str) -> Option<Value> {
+    let mut message: Message = deserialize_object_from_base64(boc, "message").ok()?.object;
+    if let Some(header) = message.ext_in_header() {
+        let state_init = match message.state_init() {
+            Some(init) => Some(serialize_object_to_base64(init, "state init").ok()?),
+            None => None,
+        };
+        return Some(json!({
+            "dst": header.dst.to_string(),
+            "state_init": state_init,
+        }));
+    }
+    message.set_at_and_lt(0, 0);
+    serialize_object_to_base64(&message, "message").ok().map(Value::String)
+}
+
+/// Collects events of the engine. Shared by the engine and the browser wrapper.
+pub(super) struct EventRecorder {
+    log: Mutex<DebotEventLog>,
+}
+
+impl EventRecorder {
+    pub fn new(address: String, locale: Option<String>) -> Self {
+        Self {
+            log: Mutex::new(DebotEventLog {
+                version: EVENT_LOG_VERSION,
+                address,
+                locale,
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// Continues recording of the log saved in the session.
+    pub fn from_log(log: DebotEventLog) -> Self {
+        Self { log: Mutex::new(log) }
+    }
+
+    pub fn record(&self, event: DebotEvent) {
+        self.log.lock().unwrap().events.push(event);
+    }
+
+    pub fn set_locale(&self, locale: Option<String>) {
+        self.log.lock().unwrap().locale = locale;
+    }
+
+    pub fn set_manage_invoked(&self) {
+        self.log.lock().unwrap().manage_invoked_debots = true;
+    }
+
+    pub fn set_policy(&self, policy: DebotPolicy) {
+        self.log.lock().unwrap().policy = policy;
+    }
+
+    /// Saves ABI if it is not saved yet, e.g. when ABIs are added again
+    /// to the restored engine.
+    pub fn add_abi(&self, abi: &Abi) {
+        let mut log = self.log.lock().unwrap();
+        let value = serde_json::to_value(abi).unwrap_or_default();
+        if log.abis.iter().all(|saved| serde_json::to_value(saved).unwrap_or_default() != value) {
+            log.abis.push(abi.clone());
+        }
+    }
+
+    /// Saves account state if the account is loaded for the first time.
+    pub fn add_account(&self, address: &str, boc: &str) {
+        let mut log = self.log.lock().unwrap();
+        if log.accounts.iter().all(|account| account.address != address) {
+            log.accounts.push(RecordedAccount {
+                address: address.to_owned(),
+                boc: boc.to_owned(),
+            });
+        }
+    }
+
+    pub async fn record_interface_call(
+        &self,
+        ton: TonClient,
+        msg: &str,
+        iface: &(dyn DebotInterface + Send + Sync),
+        result: &InterfaceResult,
+    ) {
+        let id = iface.get_id();
+        let abi = iface.get_abi();
+        let (function, input) = decode_interface_call(ton, msg, abi.clone())
+            .await
+            .unwrap_or_else(|e| (String::new(), json!({ "error": e })));
+        let mut log = self.log.lock().unwrap();
+        if log.interfaces.iter().all(|recorded| recorded.id != id) {
+            log.interfaces.push(RecordedInterface { id: id.clone(), abi });
+        }
+        let (answer_id, output, error) = match result {
+            Ok((answer_id, output)) => (Some(*answer_id), Some(output.clone()), None),
+            Err(e) => (None, None, Some(e.clone())),
+        };
+        log.events.push(DebotEvent::InterfaceCall {
+            interface_id: id,
+            function,
+            input,
+            answer_id,
+            output,
+            error,
+        });
+    }
+
+    pub fn snapshot(&self) -> DebotEventLog {
+        self.log.lock().unwrap().clone()
+    }
+}
+
+/// Records browser callbacks and passes them to the application browser.
+pub(super) struct RecordingBrowser {
+    browser: Arc<dyn BrowserCallbacks + Send + Sync>,
+    recorder: Arc<EventRecorder>,
+}
+
+impl RecordingBrowser {
+    pub fn new(
+        browser: Arc<dyn BrowserCallbacks + Send + Sync>,
+        recorder: Arc<EventRecorder>,
+    ) -> Self {
+        Self { browser, recorder }
+    }
+}
+
+#[async_trait::async_trait]
+impl BrowserCallbacks for RecordingBrowser {
+    async fn log(&self, msg: String) {
+        self.recorder.record(DebotEvent::Log { text: msg.clone() });
+        self.browser.log(msg).await
+    }
+
+    async fn switch(&self, ctx_id: u8) {
+        self.recorder.record(DebotEvent::Switch { context_id: ctx_id });
+        self.browser.switch(ctx_id).await
+    }
+
+    async fn switch_completed(&self) {
+        self.browser.switch_completed().await
+    }
+
+    async fn show_action(&self, act: DAction) {
+        self.recorder.record(DebotEvent::ShowAction { action: act.clone().into() });
+        self.browser.show_action(act).await
+    }
+
+    async fn input(&self, prompt: &str, value: &mut String) {
+        self.browser.input(prompt, value).await;
+        self.recorder.record(DebotEvent::Input {
+            prompt: prompt.to_owned(),
+            value: value.clone(),
+        });
+    }
+
+    async fn get_signing_box(&self) -> Result<SigningBoxHandle, String> {
+        let result = self.browser.get_signing_box().await;
+        self.recorder.record(DebotEvent::GetSigningBox {
+            handle: result.as_ref().ok().map(|handle| handle.0),
+            error: result.as_ref().err().cloned(),
+        });
+        result
+    }
+
+    async fn invoke_debot(&self, debot: String, action: DAction) -> Result<(), String> {
+        self.recorder.record(DebotEvent::InvokeDebot {
+            debot: debot.clone(),
+            action: action.clone().into(),
+        });
+        self.browser.invoke_debot(debot, action).await
+    }
+
+    async fn send(&self, message: String) {
+        self.recorder.record(DebotEvent::Send {
+            dst: message_dst(&message),
+            message: message.clone(),
+        });
+        self.browser.send(message).await
+    }
+
+    async fn approve(&self, activity: DebotActivity) -> ClientResult<bool> {
+        let approved = self.browser.approve(activity.clone()).await?;
+        self.recorder.record(DebotEvent::Approve { activity, approved });
+        Ok(approved)
+    }
+}
+
+/// Answers DeBot requests with the values from the recorded log.
+struct ReplayBrowser {
+    inputs: Mutex<VecDeque<String>>,
+    approvals: Mutex<VecDeque<bool>>,
+    signing_boxes: Mutex<VecDeque<Result<(), String>>>,
+    signing_box: Option<SigningBoxHandle>,
+}
+
+impl ReplayBrowser {
+    fn new(events: &[DebotEvent], signing_box: Option<SigningBoxHandle>) -> Self {
+        let mut inputs = VecDeque::new();
+        let mut approvals = VecDeque::new();
+        let mut signing_boxes = VecDeque::new();
+        for event in events {
+            match event {
+                DebotEvent::Input { value, .. } => inputs.push_back(value.clone()),
+                DebotEvent::Approve { approved, .. } => approvals.push_back(*approved),
+                DebotEvent::GetSigningBox { error, .. } => {
+                    signing_boxes.push_back(error.clone().map_or(Ok(()), Err))
+                }
+                _ => {}
+            }
+        }
+        Self {
+            inputs: Mutex::new(inputs),
+            approvals: Mutex::new(approvals),
+            signing_boxes: Mutex::new(signing_boxes),
+            signing_box,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BrowserCallbacks for ReplayBrowser {
+    async fn log(&self, _msg: String) {}
+
+    async fn switch(&self, _ctx_id: u8) {}
+
+    async fn switch_completed(&self) {}
+
+    async fn show_action(&self, _act: DAction) {}
+
+    async fn input(&self, _prompt: &str, value: &mut String) {
+        *value = self.inputs.lock().unwrap().pop_front().unwrap_or_default();
+    }
+
+    async fn get_signing_box(&self) -> Result<SigningBoxHandle, String> {
+        let recorded = self
+            .signing_boxes
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| Err("signing box was not requested in recorded session".to_owned()));
+        recorded?;
+        self.signing_box
+            .clone()
+            .ok_or_else(|| "signing box is not provided for replay".to_owned())
+    }
+
+    async fn invoke_debot(&self, _debot: String, _action: DAction) -> Result<(), String> {
+        Ok(())
+    }
+
+    async fn send(&self, _message: String) {}
+
+    async fn approve(&self, _activity: DebotActivity) -> ClientResult<bool> {
+        Ok(self.approvals.lock().unwrap().pop_front().unwrap_or(false))
+    }
+}
+
+/// Answers interface calls with the recorded answers.
+struct ReplayInterface {
+    id: String,
+    abi: Abi,
+    answers: Mutex<VecDeque<(String, InterfaceResult)>>,
+}
+
+impl ReplayInterface {
+    fn new(iface: &RecordedInterface, events: &[DebotEvent]) -> Self {
+        let answers = events
+            .iter()
+            .filter_map(|event| match event {
+                DebotEvent::InterfaceCall { interface_id, function, answer_id, output, error, .. }
+                    if *interface_id == iface.id =>
+                {
+                    let result = match error {
+                        Some(e) => Err(e.clone()),
+                        None => Ok((answer_id.unwrap_or(0), output.clone().unwrap_or(json!({})))),
+                    };
+                    Some((function.clone(), result))
+                }
+                _ => None,
+            })
+            .collect();
+        Self {
+            id: iface.id.clone(),
+            abi: iface.abi.clone(),
+            answers: Mutex::new(answers),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DebotInterface for ReplayInterface {
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn get_abi(&self) -> Abi {
+        self.abi.clone()
+    }
+
+    async fn call(&self, func: &str, _args: &Value) -> InterfaceResult {
+        match self.answers.lock().unwrap().pop_front() {
+            Some((function, result)) if function == func => result,
+            Some((function, _)) => Err(format!(
+                "function \"{}\" is called instead of recorded \"{}\"",
+                func, function
+            )),
+            None => Err(format!("function \"{}\" is not called in recorded session", func)),
+        }
+    }
+}
@@ -0,0 +1,368 @@
+/*
+* Copyright 2018-2021 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use super::action::DAction;
+use super::{BrowserCallbacks, DEngine, DebotActivity, Error, STATE_EXIT};
+use crate::crypto::SigningBoxHandle;
+use crate::error::ClientResult;
+use std::collections::VecDeque;
+use tokio::sync::Mutex;
+
+/// Step of the headless browser script.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum ScriptStep {
+    /// Expects DeBot to print the message. `*` in the text matches any substring.
+    ///
+    /// Messages that do not match the pending `Log` step are only recorded in the transcript.
+    Log { text: String },
+    /// Executes the action with the description. The action must be shown by DeBot.
+    Choose { action: String },
+    /// Expects DeBot to request input and answers with the value.
+    Input {
+        value: String,
+        /// Expected prompt. `*` matches any substring.
+        #[serde(default)]
+        prompt: Option<String>,
+    },
+    /// Expects DeBot to request approval of the activity and answers with `approved`.
+    Approve {
+        approved: bool,
+        /// Expected destination address of the transaction, deployed contract
+        /// or invoked DeBot.
+        #[serde(default)]
+        dst: Option<String>,
+    },
+}
+
+/// Declarative script of expected DeBot prompts and browser answers.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct DebotScript {
+    pub steps: Vec<ScriptStep>,
+}
+
+impl DebotScript {
+    pub fn new(steps: Vec<ScriptStep>) -> Self {
+        Self { steps }
+    }
+
+    /// Builds a script which replays the recorded session and checks all DeBot messages.
+    pub fn from_transcript(transcript: &DebotTranscript) -> Self {
+        let steps = transcript.entries.iter().filter_map(|entry| match entry {
+            TranscriptEntry::Log { text } => Some(ScriptStep::Log { text: text.clone() }),
+            TranscriptEntry::Choose { action } => Some(ScriptStep::Choose { action: action.clone() }),
+            TranscriptEntry::Input { prompt, value } => Some(ScriptStep::Input {
+                value: value.clone(),
+                prompt: Some(prompt.clone()),
+            }),
+            TranscriptEntry::Approve { activity, approved } => Some(ScriptStep::Approve {
+                approved: *approved,
+                dst: activity_dst(activity).map(str::to_owned),
+            }),
+            _ => None,
+        });
+        Self { steps: steps.collect() }
+    }
+}
+
+/// Returns the address the activity is addressed to, if any.
+fn activity_dst(activity: &DebotActivity) -> Option<&str> {
+    match activity {
+        DebotActivity::Transaction { dst, .. } | DebotActivity::Deploy { dst, .. } => Some(dst),
+        DebotActivity::InvokeDebot { debot, .. } => Some(debot),
+        _ => None,
+    }
+}
+
+/// Event of the DeBot session recorded by the headless browser.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(tag = "type")]
+pub enum TranscriptEntry {
+    Log { text: String },
+    Switch { context_id: u8 },
+    ShowAction { description: String },
+    Choose { action: String },
+    Input { prompt: String, value: String },
+    GetSigningBox { handle: Option<u32> },
+    Approve { activity: DebotActivity, approved: bool },
+    InvokeDebot { debot: String, action: String },
+    Send { message: String },
+}
+
+/// Record of the DeBot session.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct DebotTranscript {
+    pub entries: Vec<TranscriptEntry>,
+}
+
+impl DebotTranscript {
+    /// Returns all messages printed by DeBot.
+    pub fn logs(&self) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter_map(|entry| match entry {
+                TranscriptEntry::Log { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[derive(Default)]
+struct SessionState {
+    steps: VecDeque<ScriptStep>,
+    actions: Vec<DAction>,
+    transcript: DebotTranscript,
+    failure: Option<String>,
+    finished: bool,
+}
+
+impl SessionState {
+    fn fail(&mut self, message: String) {
+        if self.failure.is_none() {
+            self.failure = Some(message);
+        }
+    }
+}
+
+/// DeBot browser without user interface, driven by `DebotScript`.
+///
+/// Answers DeBot prompts with the script values, checks that DeBot behaves as expected
+/// and records the session transcript. Interfaces which are not built into the engine must be
+/// added to the engine with `DEngine::add_interface`. Combined with `MockNode`, it runs DeBots
+/// without network access.
+pub struct HeadlessBrowser {
+    state: Mutex<SessionState>,
+    signing_box: Option<SigningBoxHandle>,
+}
+
+impl HeadlessBrowser {
+    pub fn new(script: DebotScript) -> Self {
+        Self {
+            state: Mutex::new(SessionState {
+                steps: script.steps.into(),
+                ..Default::default()
+            }),
+            signing_box: None,
+        }
+    }
+
+    /// Sets signing box returned to DeBot on `get_signing_box` request.
+    pub fn with_signing_box(mut self, signing_box: SigningBoxHandle) -> Self {
+        self.signing_box = Some(signing_box);
+        self
+    }
+
+    /// Starts DeBot and executes the script.
+    ///
+    /// `engine` must be created with this browser. Fails if DeBot does not behave as expected
+    /// or the script is not completed. Returns the session transcript.
+    pub async fn run(&self, engine: &mut DEngine) -> ClientResult<DebotTranscript> {
+        engine.start().await.map_err(Error::start_failed)?;
+        self.execute(engine).await
+    }
+
+    /// Resumes DeBot restored from the saved session and executes the script.
+    ///
+    /// `engine` must be restored with this browser.
+    pub async fn resume(&self, engine: &mut DEngine) -> ClientResult<DebotTranscript> {
+        engine.resume().await;
+        self.execute(engine).await
+    }
+
+    async fn execute(&self, engine: &mut DEngine) -> ClientResult<DebotTranscript> {
+        self.check().await?;
+        loop {
+            let action = {
+                let mut state = self.state.lock().await;
+                let description = match state.steps.front() {
+                    None => break,
+                    Some(ScriptStep::Choose { action }) => action.clone(),
+                    Some(step) => {
+                        return Err(Error::script_failed(format!(
+                            "DeBot is waiting for an action, but script expects {:?}",
+                            step
+                        )))
+                    }
+                };
+                if state.finished {
+                    return Err(Error::script_failed(format!(
+                        "DeBot exited, but script expects action \"{}\"",
+                        description
+                    )));
+                }
+                let action = state
+                    .actions
+                    .iter()
+                    .find(|act| act.desc == description)
+                    .cloned()
+                    .ok_or_else(|| {
+                        Error::script_failed(format!("action \"{}\" is not shown", description))
+                    })?;
+                state.steps.pop_front();
+                state.transcript.entries.push(TranscriptEntry::Choose { action: description });
+                action
+            };
+            engine.execute_action(&action).await.map_err(Error::execute_failed)?;
+            self.check().await?;
+        }
+        Ok(std::mem::take(&mut self.state.lock().await.transcript))
+    }
+
+    /// Returns the transcript recorded so far.
+    pub async fn transcript(&self) -> DebotTranscript {
+        self.state.lock().await.transcript.clone()
+    }
+
+    async fn check(&self) -> ClientResult<()> {
+        match self.state.lock().await.failure.take() {
+            Some(failure) => Err(Error::script_failed(failure)),
+            None => Ok(()),
+        }
+    }
+
+    fn matches(pattern: &str, text: &str) -> bool {
+        let mut parts = pattern.split('*');
+        let first = parts.next().unwrap_or_default();
+        if !text.starts_with(first) {
+            return false;
+        }
+        let mut rest = &text[first.len()..];
+        let parts: Vec<&str> = parts.collect();
+        if let Some((last, middle)) = parts.split_last() {
+            for part in middle {
+                match rest.find(part) {
+                    Some(pos) => rest = &rest[pos + part.len()..],
+                    None => return false,
+                }
+            }
+            rest.ends_with(last)
+        } else {
+            rest.is_empty()
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl BrowserCallbacks for HeadlessBrowser {
+    async fn log(&self, msg: String) {
+        let mut state = self.state.lock().await;
+        let matched = match state.steps.front() {
+            Some(ScriptStep::Log { text }) => Self::matches(text, &msg),
+            _ => false,
+        };
+        if matched {
+            state.steps.pop_front();
+        }
+        state.transcript.entries.push(TranscriptEntry::Log { text: msg });
+    }
+
+    async fn switch(&self, ctx_id: u8) {
+        let mut state = self.state.lock().await;
+        state.actions.clear();
+        state.finished = ctx_id == STATE_EXIT;
+        state.transcript.entries.push(TranscriptEntry::Switch { context_id: ctx_id });
+    }
+
+    async fn switch_completed(&self) {}
+
+    async fn show_action(&self, act: DAction) {
+        let mut state = self.state.lock().await;
+        state.transcript.entries.push(TranscriptEntry::ShowAction { description: act.desc.clone() });
+        state.actions.push(act);
+    }
+
+    async fn input(&self, prompt: &str, value: &mut String) {
+        let mut state = self.state.lock().await;
+        match state.steps.front().cloned() {
+            Some(ScriptStep::Input { value: answer, prompt: expected }) => {
+                if let Some(expected) = expected {
+                    if !Self::matches(&expected, prompt) {
+                        state.fail(format!(
+                            "expected input prompt \"{}\", but DeBot requested \"{}\"",
+                            expected, prompt
+                        ));
+                    }
+                }
+                state.steps.pop_front();
+                *value = answer;
+            }
+            step => state.fail(format!(
+                "DeBot requested input \"{}\", but script expects {:?}",
+                prompt, step
+            )),
+        }
+        state.transcript.entries.push(TranscriptEntry::Input {
+            prompt: prompt.to_owned(),
+            value: value.clone(),
+        });
+    }
+
+    async fn get_signing_box(&self) -> Result<SigningBoxHandle, String> {
+        let mut state = self.state.lock().await;
+        state.transcript.entries.push(TranscriptEntry::GetSigningBox {
+            handle: self.signing_box.as_ref().map(|handle| handle.0),
+        });
+        self.signing_box
+            .clone()
+            .ok_or_else(|| "signing box is not set in headless browser".to_owned())
+    }
+
+    async fn invoke_debot(&self, debot: String, action: DAction) -> Result<(), String> {
+        let mut state = self.state.lock().await;
+        state.transcript.entries.push(TranscriptEntry::InvokeDebot {
+            debot,
+            action: action.name,
+        });
+        let error = "invoking DeBots is not supported by headless browser".to_owned();
+        state.fail(error.clone());
+        Err(error)
+    }
+
+    async fn send(&self, message: String) {
+        let mut state = self.state.lock().await;
+        state.transcript.entries.push(TranscriptEntry::Send { message });
+        state.fail(
+            "DeBot sent a message to an interface or DeBot which is not added to the engine"
+                .to_owned(),
+        );
+    }
+
+    async fn approve(&self, activity: DebotActivity) -> ClientResult<bool> {
+        let mut state = self.state.lock().await;
+        let approved = match state.steps.front().cloned() {
+            Some(ScriptStep::Approve { approved, dst: expected }) => {
+                if let Some(expected) = expected {
+                    let dst = activity_dst(&activity).unwrap_or_default();
+                    if expected != dst {
+                        state.fail(format!(
+                            "expected activity with {}, but DeBot requested {:?}",
+                            expected, activity
+                        ));
+                    }
+                }
+                state.steps.pop_front();
+                approved
+            }
+            step => {
+                state.fail(format!(
+                    "DeBot requested approval, but script expects {:?}",
+                    step
+                ));
+                false
+            }
+        };
+        state.transcript.entries.push(TranscriptEntry::Approve { activity, approved });
+        Ok(approved)
+    }
+}
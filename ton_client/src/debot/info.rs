@@ -1,4 +1,5 @@
 use super::context::{str_hex_to_utf8};
+use super::locale::Locale;
 use serde::{Deserialize, Deserializer};
 use crate::encoding::account_decode;
 
@@ -26,12 +27,53 @@ pub struct DInfo {
     #[serde(deserialize_with = "from_opt_hex_to_str")]
     pub icon: Option<String>,
     pub interfaces: Vec<String>,
+    pub translations: Vec<DInfoTranslation>,
+}
+
+/// DeBot metadata in one of the languages. Returned by `getDebotInfo`
+/// in `translations` array if DeBot supports several languages.
+#[derive(Deserialize, Default, Debug, Clone)]
+#[serde(default)]
+pub struct DInfoTranslation {
+    #[serde(deserialize_with = "from_opt_hex_to_str")]
+    pub language: Option<String>,
+    #[serde(deserialize_with = "from_opt_hex_to_str")]
+    pub name: Option<String>,
+    #[serde(deserialize_with = "from_opt_hex_to_str")]
+    pub publisher: Option<String>,
+    #[serde(deserialize_with = "from_opt_hex_to_str")]
+    pub caption: Option<String>,
+    #[serde(deserialize_with = "from_opt_hex_to_str")]
+    pub author: Option<String>,
+    #[serde(deserialize_with = "from_opt_hex_to_str")]
+    pub hello: Option<String>,
 }
 
 impl DInfo {
     pub fn validate(&self) -> Result<(), String> {
         Ok(())
     }
+
+    /// Replaces metadata with the translation best matching the locale.
+    /// Fields missing in the translation keep default values, if there is
+    /// no suitable translation the metadata is not changed.
+    pub(super) fn localize(&mut self, locale: &Locale) {
+        let languages = std::iter::once(&self.language)
+            .chain(self.translations.iter().map(|t| &t.language))
+            .map(|lang| lang.as_deref().unwrap_or_default());
+        // index 0 is the default language of DeBot
+        let index = match locale.best_match(languages) {
+            Some(index) if index > 0 => index - 1,
+            _ => return,
+        };
+        let translation = self.translations[index].clone();
+        self.language = translation.language.or(self.language.take());
+        self.name = translation.name.or(self.name.take());
+        self.publisher = translation.publisher.or(self.publisher.take());
+        self.caption = translation.caption.or(self.caption.take());
+        self.author = translation.author.or(self.author.take());
+        self.hello = translation.hello.or(self.hello.take());
+    }
 }
 
 fn validate_ton_address<'de, D>(des: D) -> Result<Option<String>, D::Error>
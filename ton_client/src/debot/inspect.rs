@@ -0,0 +1,336 @@
+/*
+* Copyright 2018-2021 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use super::action::DAction;
+use super::browser::BrowserCallbacks;
+use super::dinterface::DebotInterface;
+use super::ui_browser::{MenuItem, UiCallbacks};
+use super::{DebotActivity, DEBOT_WC};
+use crate::crypto::SigningBoxHandle;
+use crate::encoding::decode_abi_bigint;
+use crate::error::ClientResult;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use ton_abi::Contract;
+use ton_types::{Cell, UInt256};
+
+const SDK_ID: &str = "8fc6454f90072c9f1f6d3313ae1608f64f4a0660c6ae9f42c68b6a79e2a1bc4b";
+const NETWORK_ID: &str = "e38aed5884dc3e4426a87c083faaf4fa08109189fbc0c79281112f52e062d8ee";
+const ENCRYPTION_BOX_INPUT_ID: &str =
+    "5b5f76b54d976d72f1ada3063d1af2e5352edaf1ba86b3b311170d4d81056d61";
+
+const KNOWN_INTERFACES: &[(&str, &str)] = &[
+    ("8913b27b45267aad3ee08437e64029ac38fb59274f19adca0b23c4f957c8cfa1", "Base64"),
+    ("edfbb00d6ebd16d57a1636774845af9499b400ba417da8552f40b1250256ff8f", "Hex"),
+    ("442288826041d564ccedc579674f17c1b0a3452df799656a9167a41ab270ec19", "Json"),
+    ("475a5d1729acee4601c2a8cb67240e4da5316cc90a116e1b181d905e79401c51", "Msg"),
+    (NETWORK_ID, "Network"),
+    ("5c6fd81616cdfb963632109c42144a3a885c8d0f2e8deb5d8e15872fb92f2811", "Query"),
+    (SDK_ID, "Sdk"),
+    ("8796536366ee21852db56dccb60bc564598b618c865fc50c8b1ab740bba128e3", "Terminal"),
+    ("ac1a4d3ecea232e49783df4a23a81823cdca3205dc58cd20c4db259c25605b48", "Menu"),
+    ("d7ed1bd8e6230871116f4522e58df0a93c5520c56f4ade23ef3d8919a984653b", "AddressInput"),
+    ("a1d347099e29c1624c8890619daf207bde18e92df5220a54bcc6d858309ece84", "AmountInput"),
+    ("16653eaf34c921467120f2685d425ff963db5cbb5aa676a62a2e33bfc3f6828a", "ConfirmInput"),
+    ("c5a9558b2664aed7dc3e6123436d544f13ffe69ab0e259412f48c6d1c8588401", "NumberInput"),
+    ("c13024e101c95e71afb1f5fa6d72f633d51e721de0320d73dfd6121a54e4d40a", "SigningBoxInput"),
+    (ENCRYPTION_BOX_INPUT_ID, "EncryptionBoxInput"),
+];
+
+/// [UNSTABLE](UNSTABLE.md) DeBot interface used by DeBot.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct InterfaceUsage {
+    /// Interface identifier.
+    pub id: String,
+    /// Interface name if the interface is a standard one.
+    pub name: Option<String>,
+    /// Indicates if the interface is listed in `getRequiredInterfaces`.
+    pub declared: bool,
+    /// Names of interface functions called by DeBot.
+    /// Empty if the interface ABI is unknown to the engine.
+    pub functions: Vec<String>,
+}
+
+/// [UNSTABLE](UNSTABLE.md) Result of the static DeBot analysis.
+///
+/// Analysis is based on constants found in DeBot code, so it can miss
+/// values calculated at runtime.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct ResultOfInspect {
+    /// Interfaces used by DeBot.
+    pub interfaces: Vec<InterfaceUsage>,
+    /// Addresses of contracts found in DeBot code constants.
+    pub contracts: Vec<String>,
+    /// Indicates if DeBot signs arbitrary hashes with Sdk `signHash`.
+    pub sign_hash: bool,
+    /// Indicates if DeBot uses encryption boxes.
+    pub encryption_boxes: bool,
+    /// Indicates if DeBot uses Network interface.
+    pub network: bool,
+    /// URLs found in DeBot code constants.
+    pub urls: Vec<String>,
+    /// Headers of target contract ABI, e.g. `pubkey`, `time` and `expire`.
+    /// `None` if DeBot has no target ABI.
+    pub target_abi_headers: Option<Vec<String>>,
+}
+
+/// Bits and bytes of all cells of the code.
+struct CodeCells {
+    bits: Vec<Vec<bool>>,
+    bytes: Vec<Vec<u8>>,
+}
+
+impl CodeCells {
+    fn new(code: &Cell) -> Self {
+        let mut cells = Self { bits: vec![], bytes: vec![] };
+        let mut visited = HashSet::new();
+        let mut queue = vec![code.clone()];
+        while let Some(cell) = queue.pop() {
+            if !visited.insert(cell.repr_hash()) {
+                continue;
+            }
+            let data = cell.data();
+            let len = cell.bit_length();
+            cells.bits.push((0..len).map(|i| (data[i / 8] >> (7 - i % 8)) & 1 == 1).collect());
+            cells.bytes.push(data[..len / 8].to_vec());
+            for i in 0..cell.references_count() {
+                if let Ok(child) = cell.reference(i) {
+                    queue.push(child);
+                }
+            }
+        }
+        cells
+    }
+
+    fn contains(&self, pattern: &[bool]) -> bool {
+        self.bits.iter().any(|bits| find_bits(bits, pattern, 0).is_some())
+    }
+
+    /// Finds standard addresses stored as slice constants: either a whole cell
+    /// or inline `PUSHSLICE` operand (`8D r:3 xx:7` with xx = 33).
+    fn addresses(&self) -> Vec<(i8, UInt256)> {
+        let mut result = vec![];
+        let opcode = to_bits(&[0x8D], 8);
+        let len = to_bits(&[33 << 1], 7);
+        for bits in &self.bits {
+            if bits.len() == 267 {
+                result.extend(read_address(bits, 0));
+            }
+            let mut start = 0;
+            while let Some(pos) = find_bits(bits, &opcode, start) {
+                start = pos + 1;
+                if bits.len() >= pos + 18 && bits[pos + 11..pos + 18] == len[..] {
+                    result.extend(read_address(bits, pos + 18));
+                }
+            }
+        }
+        result
+    }
+
+    fn urls(&self) -> Vec<String> {
+        let mut urls = vec![];
+        for bytes in &self.bytes {
+            for prefix in &["https://", "http://"] {
+                let mut start = 0;
+                while let Some(pos) = find_bytes(&bytes[start..], prefix.as_bytes()) {
+                    let url: Vec<u8> = bytes[start + pos..]
+                        .iter()
+                        .cloned()
+                        .take_while(|b| b.is_ascii_graphic())
+                        .collect();
+                    start += pos + url.len();
+                    urls.push(String::from_utf8_lossy(&url).to_string());
+                }
+            }
+        }
+        urls
+    }
+}
+
+fn to_bits(bytes: &[u8], len: usize) -> Vec<bool> {
+    (0..len).map(|i| (bytes[i / 8] >> (7 - i % 8)) & 1 == 1).collect()
+}
+
+fn find_bits(bits: &[bool], pattern: &[bool], start: usize) -> Option<usize> {
+    if bits.len() < pattern.len() {
+        return None;
+    }
+    (start..=bits.len() - pattern.len()).find(|&pos| bits[pos..pos + pattern.len()] == pattern[..])
+}
+
+fn find_bytes(bytes: &[u8], pattern: &[u8]) -> Option<usize> {
+    bytes.windows(pattern.len()).position(|window| window == pattern)
+}
+
+/// Reads `addr_std$10 anycast:0 workchain_id:int8 address:bits256`.
+fn read_address(bits: &[bool], pos: usize) -> Option<(i8, UInt256)> {
+    if bits.len() < pos + 267 || bits[pos..pos + 3] != [true, false, false] {
+        return None;
+    }
+    let byte = |start: usize| {
+        bits[start..start + 8]
+            .iter()
+            .fold(0u8, |byte, bit| (byte << 1) | *bit as u8)
+    };
+    let wc = byte(pos + 3) as i8;
+    let address: Vec<u8> = (0..32).map(|i| byte(pos + 11 + i * 8)).collect();
+    Some((wc, UInt256::from(address)))
+}
+
+fn normalize_id(id: &str) -> String {
+    decode_abi_bigint(id)
+        .map(|id| format!("{:064x}", id))
+        .unwrap_or_else(|_| id.to_lowercase())
+}
+
+/// Analyzes DeBot code. `interfaces` are interfaces known to the engine,
+/// they are used to find called functions.
+pub(super) fn inspect(
+    code: &Cell,
+    declared_interfaces: &[String],
+    interfaces: &HashMap<String, Arc<dyn DebotInterface + Send + Sync>>,
+    target_abi: Option<&str>,
+) -> ResultOfInspect {
+    let cells = CodeCells::new(code);
+    let declared: HashSet<String> = declared_interfaces.iter().map(|id| normalize_id(id)).collect();
+    let mut used: Vec<String> = declared.iter().cloned().collect();
+    let mut contracts = vec![];
+    for (wc, address) in cells.addresses() {
+        if wc == DEBOT_WC {
+            used.push(address.as_hex_string());
+        } else if wc == 0 || wc == -1 {
+            contracts.push(format!("{}:{}", wc, address.as_hex_string()));
+        }
+    }
+    for (id, _) in KNOWN_INTERFACES {
+        let bits = to_bits(&hex::decode(id).unwrap_or_default(), 256);
+        if cells.contains(&bits) {
+            used.push(id.to_string());
+        }
+    }
+    used.sort();
+    used.dedup();
+    contracts.sort();
+    contracts.dedup();
+
+    let mut usages = vec![];
+    for id in used {
+        let functions = interfaces
+            .get(&id)
+            .and_then(|iface| iface.get_abi().json_string().ok())
+            .and_then(|abi| Contract::load(abi.as_bytes()).ok())
+            .map(|abi| {
+                let mut functions: Vec<String> = abi
+                    .functions()
+                    .values()
+                    .filter(|func| cells.contains(&to_bits(&func.get_input_id().to_be_bytes(), 32)))
+                    .map(|func| func.name.clone())
+                    .collect();
+                functions.sort();
+                functions
+            })
+            .unwrap_or_default();
+        usages.push(InterfaceUsage {
+            name: KNOWN_INTERFACES
+                .iter()
+                .find(|(known, _)| *known == id)
+                .map(|(_, name)| name.to_string()),
+            declared: declared.contains(&id),
+            id,
+            functions,
+        });
+    }
+
+    let calls = |id: &str, names: &[&str]| {
+        usages
+            .iter()
+            .any(|usage| usage.id == id && usage.functions.iter().any(|f| names.contains(&f.as_str())))
+    };
+    let uses = |id: &str| usages.iter().any(|usage| usage.id == id);
+    let mut urls = cells.urls();
+    urls.sort();
+    urls.dedup();
+
+    ResultOfInspect {
+        sign_hash: calls(SDK_ID, &["signHash"]),
+        encryption_boxes: calls(SDK_ID, &["encrypt", "decrypt", "getEncryptionBoxInfo"])
+            || uses(ENCRYPTION_BOX_INPUT_ID),
+        network: uses(NETWORK_ID),
+        target_abi_headers: target_abi
+            .and_then(|abi| serde_json::from_str::<serde_json::Value>(abi).ok())
+            .map(|abi| {
+                abi["header"]
+                    .as_array()
+                    .map(|header| {
+                        header.iter().filter_map(|h| h.as_str().map(str::to_owned)).collect()
+                    })
+                    .unwrap_or_default()
+            }),
+        interfaces: usages,
+        contracts,
+        urls,
+    }
+}
+
+/// Browser which is never called. Standard interfaces are created with it
+/// only to make their ABIs known to the inspector.
+pub(super) struct NoBrowser;
+
+#[async_trait::async_trait]
+impl BrowserCallbacks for NoBrowser {
+    async fn log(&self, _msg: String) {}
+    async fn switch(&self, _ctx_id: u8) {}
+    async fn switch_completed(&self) {}
+    async fn show_action(&self, _act: DAction) {}
+    async fn input(&self, _prompt: &str, _value: &mut String) {}
+    async fn get_signing_box(&self) -> Result<SigningBoxHandle, String> {
+        Err("browser is not available".to_owned())
+    }
+    async fn invoke_debot(&self, _debot: String, _action: DAction) -> Result<(), String> {
+        Err("browser is not available".to_owned())
+    }
+    async fn send(&self, _message: String) {}
+    async fn approve(&self, _activity: DebotActivity) -> ClientResult<bool> {
+        Ok(false)
+    }
+}
+
+/// User interface which is never called. Standard UI interfaces are added with it
+/// only to make their ABIs known to the inspector.
+pub(super) struct NoUi;
+
+#[async_trait::async_trait]
+impl UiCallbacks for NoUi {
+    async fn print(&self, _message: &str) {}
+    async fn input(&self, _prompt: &str, _multiline: bool) -> Result<String, String> {
+        Err("user interface is not available".to_owned())
+    }
+    async fn confirm(&self, _prompt: &str) -> Result<bool, String> {
+        Err("user interface is not available".to_owned())
+    }
+    async fn select(
+        &self,
+        _title: &str,
+        _description: &str,
+        _items: &[MenuItem],
+    ) -> Result<usize, String> {
+        Err("user interface is not available".to_owned())
+    }
+    async fn get_signing_box(
+        &self,
+        _prompt: &str,
+        _possible_keys: &[String],
+    ) -> Result<SigningBoxHandle, String> {
+        Err("user interface is not available".to_owned())
+    }
+}
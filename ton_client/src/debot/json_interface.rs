@@ -38,7 +38,7 @@ const ABI: &str = r#"
 }
 "#;
 
-const BASE64_ID: &str = "442288826041d564ccedc579674f17c1b0a3452df799656a9167a41ab270ec19";
+pub(crate) const BASE64_ID: &str = "442288826041d564ccedc579674f17c1b0a3452df799656a9167a41ab270ec19";
 
 pub struct JsonInterface {
     debot_abi: String,
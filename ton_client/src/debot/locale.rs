@@ -0,0 +1,130 @@
+use super::ui_browser::{format_amount, parse_amount};
+use chrono::{TimeZone, Utc};
+use num_bigint::BigInt;
+
+const NO_BREAK_SPACE: char = '\u{a0}';
+
+/// Preferred user locale, e.g. `en-US` or `ru`. Used to translate DeBot metadata
+/// and to format amounts and dates shown to the user.
+///
+/// Empty locale keeps locale-independent formats: `.` as decimal separator,
+/// no digit grouping and `YYYY-MM-DD hh:mm:ss` dates.
+#[derive(Clone, Debug, Default)]
+pub(super) struct Locale {
+    tag: String,
+}
+
+impl Locale {
+    pub fn new(tag: Option<String>) -> Self {
+        Self { tag: tag.unwrap_or_default().trim().replace('_', "-") }
+    }
+
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Primary language subtag in lowercase, e.g. `en` for `en-US`.
+    fn language(&self) -> String {
+        primary_language(&self.tag)
+    }
+
+    fn decimal_separator(&self) -> char {
+        match self.language().as_str() {
+            "cs" | "de" | "es" | "fi" | "fr" | "it" | "nb" | "nl" | "pl" | "pt" | "ru" | "sv"
+            | "tr" | "uk" => ',',
+            _ => '.',
+        }
+    }
+
+    fn group_separator(&self) -> Option<char> {
+        match self.language().as_str() {
+            "" => None,
+            "de" | "es" | "it" | "nl" | "pt" | "tr" => Some('.'),
+            "cs" | "fi" | "fr" | "nb" | "pl" | "ru" | "sv" | "uk" => Some(NO_BREAK_SPACE),
+            _ => Some(','),
+        }
+    }
+
+    /// Formats integer number of minimal units as decimal amount, e.g. `1,234.5`.
+    pub fn format_amount(&self, value: &BigInt, decimals: u8) -> String {
+        let amount = format_amount(value, decimals);
+        let (int, frac) = match amount.find('.') {
+            Some(pos) => (&amount[..pos], Some(&amount[pos + 1..])),
+            None => (amount.as_str(), None),
+        };
+        let mut result = String::new();
+        for (i, digit) in int.chars().enumerate() {
+            if i > 0 && (int.len() - i) % 3 == 0 {
+                if let Some(separator) = self.group_separator() {
+                    result.push(separator);
+                }
+            }
+            result.push(digit);
+        }
+        if let Some(frac) = frac {
+            result.push(self.decimal_separator());
+            result.push_str(frac);
+        }
+        result
+    }
+
+    /// Parses decimal amount entered by user. Besides `.` accepts decimal separator
+    /// of the locale and space digit grouping.
+    pub fn parse_amount(&self, value: &str, decimals: u8) -> Result<BigInt, String> {
+        let mut normalized: String = value
+            .chars()
+            .filter(|c| *c != ' ' && *c != NO_BREAK_SPACE)
+            .collect();
+        if self.decimal_separator() == ',' {
+            normalized = normalized.replace(',', ".");
+        }
+        parse_amount(&normalized, decimals)
+    }
+
+    /// Formats unix time as UTC date and time.
+    pub fn format_date(&self, time: u32) -> String {
+        let format = match self.language().as_str() {
+            "" => "%Y-%m-%d %H:%M:%S",
+            "en" if self.tag.eq_ignore_ascii_case("en") || region(&self.tag) == "US" => {
+                "%m/%d/%Y %H:%M"
+            }
+            "cs" | "de" | "fi" | "nb" | "pl" | "ru" | "tr" | "uk" => "%d.%m.%Y %H:%M",
+            "ja" | "ko" | "zh" => "%Y/%m/%d %H:%M",
+            "nl" => "%d-%m-%Y %H:%M",
+            "sv" => "%Y-%m-%d %H:%M",
+            _ => "%d/%m/%Y %H:%M",
+        };
+        Utc.timestamp(time as i64, 0).format(format).to_string()
+    }
+
+    /// Selects the language best matching the locale: exact tag first,
+    /// then the same primary language. Returns index in `languages`.
+    pub fn best_match<'a>(&self, languages: impl Iterator<Item = &'a str>) -> Option<usize> {
+        if self.tag.is_empty() {
+            return None;
+        }
+        let languages: Vec<&str> = languages.collect();
+        languages
+            .iter()
+            .position(|lang| lang.replace('_', "-").eq_ignore_ascii_case(&self.tag))
+            .or_else(|| {
+                languages
+                    .iter()
+                    .position(|lang| primary_language(lang) == self.language())
+            })
+    }
+}
+
+fn primary_language(tag: &str) -> String {
+    tag.split(|c| c == '-' || c == '_')
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+fn region(tag: &str) -> String {
+    tag.split(|c| c == '-' || c == '_')
+        .nth(1)
+        .unwrap_or_default()
+        .to_uppercase()
+}
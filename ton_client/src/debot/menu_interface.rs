@@ -0,0 +1,87 @@
+use super::dinterface::{get_string_arg, DebotInterface, InterfaceResult};
+use super::ui_browser::{MenuItem, UiCallbacks};
+use crate::abi::Abi;
+use crate::encoding::decode_abi_number;
+use serde_json::Value;
+use std::sync::Arc;
+
+const ABI: &str = r#"
+{
+	"ABI version": 2,
+	"header": ["time"],
+	"functions": [
+		{
+			"name": "select",
+			"inputs": [
+				{"name":"title","type":"bytes"},
+				{"name":"description","type":"bytes"},
+				{"components":[{"name":"title","type":"bytes"},{"name":"description","type":"bytes"},{"name":"handlerId","type":"uint32"}],"name":"items","type":"tuple[]"}
+			],
+			"outputs": [
+				{"name":"index","type":"uint32"}
+			]
+		}
+	],
+	"data": [
+	],
+	"events": [
+	]
+}
+"#;
+
+const MENU_ID: &str = "ac1a4d3ecea232e49783df4a23a81823cdca3205dc58cd20c4db259c25605b48";
+
+pub struct MenuInterface {
+    ui: Arc<dyn UiCallbacks + Send + Sync>,
+}
+
+impl MenuInterface {
+    pub fn new(ui: Arc<dyn UiCallbacks + Send + Sync>) -> Self {
+        Self { ui }
+    }
+
+    async fn select(&self, args: &Value) -> InterfaceResult {
+        let title = get_string_arg(args, "title")?;
+        let description = get_string_arg(args, "description")?;
+        let items = args["items"]
+            .as_array()
+            .ok_or(format!("\"items\" is invalid: must be array"))?
+            .iter()
+            .map(|item| {
+                Ok(MenuItem {
+                    title: get_string_arg(item, "title")?,
+                    description: get_string_arg(item, "description")?,
+                    handler_id: decode_abi_number(item["handlerId"].as_str().unwrap_or_default())
+                        .map_err(|e| format!("invalid handler id: {}", e))?,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        if items.is_empty() {
+            return Err(format!("menu has no items"));
+        }
+        let index = self.ui.select(&title, &description, &items).await?;
+        let item = items
+            .get(index)
+            .ok_or(format!("menu item {} does not exist", index))?;
+        // Menu calls the handler of the selected item instead of the answer function.
+        Ok((item.handler_id, json!({ "index": index })))
+    }
+}
+
+#[async_trait::async_trait]
+impl DebotInterface for MenuInterface {
+    fn get_id(&self) -> String {
+        MENU_ID.to_string()
+    }
+
+    fn get_abi(&self) -> Abi {
+        Abi::Json(ABI.to_owned())
+    }
+
+    async fn call(&self, func: &str, args: &Value) -> InterfaceResult {
+        match func {
+            "select" => self.select(args).await,
+            _ => Err(format!("function \"{}\" is not implemented", func)),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+/*
+* Copyright 2018-2021 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use super::TonClient;
+use crate::abi::Abi;
+use crate::boc::{parse_message, ParamsOfParse};
+use crate::error::ClientResult;
+use crate::tvm::{run_executor, AccountForExecutor, ParamsOfRunExecutor, ResultOfRunExecutor};
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+
+/// Local blockchain emulation to run DeBots without network access.
+///
+/// Keeps account states in memory. Messages are executed on these states with
+/// `tvm.run_executor` and updated states are stored back. Outbound internal messages
+/// are not delivered to other accounts.
+#[derive(Default)]
+pub struct MockNode {
+    accounts: Mutex<HashMap<String, String>>,
+}
+
+impl MockNode {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets account state. `boc` is encoded with `base64`.
+    pub async fn set_account(&self, address: String, boc: String) {
+        self.accounts.lock().await.insert(address, boc);
+    }
+
+    /// Returns account state BOC encoded with `base64`.
+    pub async fn account(&self, address: &str) -> Option<String> {
+        self.accounts.lock().await.get(address).cloned()
+    }
+
+    /// Executes the message on its destination account and stores the updated account state.
+    ///
+    /// Messages to unknown accounts (e.g. deploy messages) are executed on an uninitialized
+    /// account with unlimited balance.
+    pub async fn process_message(
+        &self,
+        ton: TonClient,
+        message: String,
+        abi: Option<Abi>,
+    ) -> ClientResult<ResultOfRunExecutor> {
+        let parsed = parse_message(ton.clone(), ParamsOfParse { boc: message.clone() })
+            .await?
+            .parsed;
+        let dst = parsed["dst"].as_str().unwrap_or_default().to_owned();
+        let account = match self.account(&dst).await {
+            Some(boc) => AccountForExecutor::Account { boc, unlimited_balance: None },
+            None => AccountForExecutor::Uninit,
+        };
+        let result = run_executor(
+            ton,
+            ParamsOfRunExecutor {
+                message,
+                account,
+                abi,
+                return_updated_account: Some(true),
+                ..Default::default()
+            },
+        )
+        .await?;
+        self.set_account(dst, result.account.clone()).await;
+        Ok(result)
+    }
+}
@@ -13,41 +13,74 @@
 
 mod action;
 mod activity;
+mod address_input_interface;
+mod amount_input_interface;
 mod base64_interface;
 mod hex_interface;
 mod json_interface;
 mod json_lib_utils;
+mod locale;
 mod browser;
 pub mod calltype;
+mod confirm_input_interface;
 mod context;
 mod debot_abi;
 mod dengine;
 mod dinterface;
 mod errors;
+mod event_log;
+mod headless;
 mod helpers;
 mod info;
+mod inspect;
+mod menu_interface;
+mod mock_node;
 mod msg_interface;
 mod network_interface;
+mod number_input_interface;
+mod policy;
 mod query_interface;
 mod routines;
 mod run_output;
 mod sdk_interface;
+mod session;
+mod signing_box_input_interface;
+mod terminal_interface;
 #[cfg(test)]
 mod tests;
 #[cfg(test)]
 mod tests_interfaces;
+mod ui_browser;
 
 #[cfg(not(feature = "wasm"))]
 pub use calltype::prepare_ext_in_message;
 
 pub use action::DAction;
-pub use activity::{DebotActivity, Spending};
+pub use activity::{DebotActivity, DecodedCall, Spending};
+pub use address_input_interface::AddressInputInterface;
+pub use amount_input_interface::AmountInputInterface;
 pub use browser::BrowserCallbacks;
+pub use confirm_input_interface::ConfirmInputInterface;
 pub use context::{DContext, STATE_EXIT, STATE_ZERO};
 pub use dengine::DEngine;
 pub use dinterface::{DebotInterface, DebotInterfaceExecutor, InterfaceResult};
+pub(crate) use dinterface::decode_answer_id;
 pub use errors::{Error, ErrorCode};
-use info::DInfo;
+pub use event_log::{DebotEvent, DebotEventLog, RecordedAccount, RecordedInterface};
+pub use inspect::{InterfaceUsage, ResultOfInspect};
+pub use headless::{DebotScript, DebotTranscript, HeadlessBrowser, ScriptStep, TranscriptEntry};
+pub use menu_interface::MenuInterface;
+pub use mock_node::MockNode;
+pub use number_input_interface::NumberInputInterface;
+pub use policy::DebotPolicy;
+pub use session::{DebotSession, SessionContext};
+pub use signing_box_input_interface::SigningBoxInputInterface;
+pub use terminal_interface::TerminalInterface;
+pub use ui_browser::{MenuItem, UiCallbacks};
+use info::{DInfo, DInfoTranslation};
+use locale::Locale;
+use crate::abi::Abi;
+use crate::crypto::SigningBoxHandle;
 use crate::error::ClientResult;
 use crate::ClientContext;
 use std::sync::Arc;
@@ -133,6 +166,53 @@ pub struct DebotInfo {
     pub icon: Option<String>,
     /// Vector with IDs of DInterfaces used by DeBot.
     pub interfaces: Vec<String>,
+    /// Translations of DeBot metadata to other languages.
+    /// Fields above are translated to the locale preferred by the user if possible.
+    #[serde(default)]
+    pub translations: Vec<DebotInfoTranslation>,
+}
+
+/// [UNSTABLE](UNSTABLE.md) DeBot metadata in one of the languages.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct DebotInfoTranslation {
+    /// Language tag (BCP 47), e.g. `en` or `pt-BR`.
+    pub language: Option<String>,
+    /// DeBot short name.
+    pub name: Option<String>,
+    /// The name of DeBot deployer.
+    pub publisher: Option<String>,
+    /// Short info about DeBot.
+    pub caption: Option<String>,
+    /// The name of DeBot developer.
+    pub author: Option<String>,
+    /// String with the first messsage from DeBot.
+    pub hello: Option<String>,
+}
+
+impl From<DInfoTranslation> for DebotInfoTranslation {
+    fn from(t: DInfoTranslation) -> Self {
+        Self {
+            language: t.language,
+            name: t.name,
+            publisher: t.publisher,
+            caption: t.caption,
+            author: t.author,
+            hello: t.hello,
+        }
+    }
+}
+
+impl From<DebotInfoTranslation> for DInfoTranslation {
+    fn from(t: DebotInfoTranslation) -> Self {
+        Self {
+            language: t.language,
+            name: t.name,
+            publisher: t.publisher,
+            caption: t.caption,
+            author: t.author,
+            hello: t.hello,
+        }
+    }
 }
 
 impl From<DInfo> for DebotInfo {
@@ -149,6 +229,26 @@ impl From<DInfo> for DebotInfo {
             dabi: info.dabi,
             icon : info.icon,
             interfaces: info.interfaces,
+            translations: info.translations.into_iter().map(|t| t.into()).collect(),
+        }
+    }
+}
+
+impl From<DebotInfo> for DInfo {
+    fn from(info: DebotInfo) -> Self {
+        Self {
+            name: info.name,
+            version: info.version,
+            publisher: info.publisher,
+            caption: info.caption,
+            author: info.author,
+            support: info.support,
+            hello: info.hello,
+            language: info.language,
+            dabi: info.dabi,
+            icon: info.icon,
+            interfaces: info.interfaces,
+            translations: info.translations.into_iter().map(|t| t.into()).collect(),
         }
     }
 }
@@ -183,7 +283,8 @@ pub async fn start(
         .get(&params.debot_handle.0)
         .ok_or(Error::invalid_handle(params.debot_handle.0))?;
     let mut dengine = mutex.1.lock().await;
-    dengine.start().await.map_err(Error::start_failed)
+    let result = dengine.start().await.map_err(Error::start_failed);
+    check_policy(&dengine, result)
 }
 
 /// [UNSTABLE](UNSTABLE.md) Parameters to fetch DeBot metadata.
@@ -191,6 +292,9 @@ pub async fn start(
 pub struct ParamsOfFetch {
     /// Debot smart contract address.
     pub address: String,
+    /// Preferred locale of the user, e.g. `en-US`. Metadata is translated
+    /// to it if DeBot provides translations.
+    pub locale: Option<String>,
 }
 
 /// [UNSTABLE](UNSTABLE.md)
@@ -208,9 +312,38 @@ pub async fn fetch(
     context: Arc<ClientContext>,
     params: ParamsOfFetch,
 ) -> ClientResult<ResultOfFetch> {
-    Ok(ResultOfFetch {
-        info : DEngine::fetch(context, params.address).await.map_err(Error::fetch_failed)?.into()
-    })
+    let mut info = DEngine::fetch(context, params.address).await.map_err(Error::fetch_failed)?;
+    info.localize(&Locale::new(params.locale));
+    Ok(ResultOfFetch { info: info.into() })
+}
+
+/// [UNSTABLE](UNSTABLE.md) Parameters to inspect DeBot.
+#[derive(Serialize, Deserialize, Default, ApiType)]
+pub struct ParamsOfInspect {
+    /// Debot smart contract address.
+    pub address: String,
+}
+
+/// [UNSTABLE](UNSTABLE.md) Inspects DeBot without running it.
+///
+/// Downloads DeBot from blockchain and analyzes its code and ABI: interfaces and
+/// their functions called by DeBot, contract addresses and URLs found in code
+/// constants, usage of `signHash`, encryption boxes and Network interface,
+/// headers of the target contract ABI.
+/// DeBot is not initialized: only get-methods returning its metadata and options
+/// are called.
+/// Can be used by Debot Browser to show permissions summary before `start`.
+#[api_function]
+pub async fn inspect(
+    context: Arc<ClientContext>,
+    params: ParamsOfInspect,
+) -> ClientResult<ResultOfInspect> {
+    let state = DEngine::load_state(context.clone(), params.address.clone())
+        .await
+        .map_err(Error::fetch_failed)?;
+    DEngine::inspect_state(context, params.address, state)
+        .await
+        .map_err(Error::fetch_failed)
 }
 
 /// [UNSTABLE](UNSTABLE.md) Parameters to init DeBot.
@@ -218,6 +351,23 @@ pub async fn fetch(
 pub struct ParamsOfInit {
     /// Debot smart contract address
     pub address: String,
+    /// Restrictions applied to DeBot execution. By default DeBot is not restricted.
+    pub policy: Option<DebotPolicy>,
+    /// ABIs of contracts DeBot works with. Used to decode messages in
+    /// activities passed to `approve` callback.
+    pub abis: Option<Vec<Abi>>,
+    /// Preferred locale of the user, e.g. `en-US`. DeBot metadata is translated
+    /// to it if DeBot provides translations. DeBot can read it with Sdk interface
+    /// `getLocale` function.
+    pub locale: Option<String>,
+    /// Enables recording of the detailed event log of DeBot execution.
+    /// The log can be exported with `get_event_log`. Disabled by default.
+    pub event_log: Option<bool>,
+    /// Makes the engine run DeBots invoked by this DeBot with internal messages
+    /// instead of passing the messages to the `send` callback. Invoked DeBots
+    /// share the browser callbacks and the policy, their answers are delivered
+    /// back to the calling DeBot. Disabled by default.
+    pub manage_invoked_debots: Option<bool>,
 }
 
 /// [UNSTABLE](UNSTABLE.md) Structure for storing debot handle returned from `init` function.
@@ -245,8 +395,20 @@ pub async fn init(
     callbacks: impl BrowserCallbacks + Send + Sync + 'static,
 ) -> ClientResult<RegisteredDebot> {
     let mut dengine =
-        DEngine::new_with_client(params.address, None, context.clone(), Arc::new(callbacks));
-    let info: DebotInfo = dengine.init().await.map_err(Error::fetch_failed)?.into();
+        DEngine::new_with_client(params.address, None, context.clone(), Arc::new(callbacks))
+            .with_policy(params.policy.unwrap_or_default())
+            .with_locale(params.locale);
+    if params.manage_invoked_debots.unwrap_or(false) {
+        dengine = dengine.with_invoked_debots();
+    }
+    if params.event_log.unwrap_or(false) {
+        dengine = dengine.with_event_log();
+    }
+    for abi in params.abis.unwrap_or_default() {
+        dengine.add_abi(abi);
+    }
+    let result = dengine.init().await.map_err(Error::fetch_failed);
+    let info: DebotInfo = check_policy(&dengine, result)?.into();
 
     let handle = context.get_next_id();
     context.debots.insert(handle, Mutex::new(dengine));
@@ -277,10 +439,11 @@ pub async fn execute(context: Arc<ClientContext>, params: ParamsOfExecute) -> Cl
         .get(&params.debot_handle.0)
         .ok_or(Error::invalid_handle(params.debot_handle.0))?;
     let mut dengine = mutex.1.lock().await;
-    dengine
+    let result = dengine
         .execute_action(&params.action.into())
         .await
-        .map_err(Error::execute_failed)
+        .map_err(Error::execute_failed);
+    check_policy(&dengine, result)
 }
 
 /// [UNSTABLE](UNSTABLE.md)
@@ -311,6 +474,8 @@ pub struct ParamsOfSend {
 /// [UNSTABLE](UNSTABLE.md) Sends message to Debot.
 ///
 /// Used by Debot Browser to send response on Dinterface call or from other Debots.
+/// Responses to DeBots invoked by the engine (see `manage_invoked_debots`) are sent
+/// with the handle of the DeBot which invoked them.
 #[api_function]
 pub async fn send(context: Arc<ClientContext>, params: ParamsOfSend) -> ClientResult<()> {
     let mutex = context
@@ -318,7 +483,206 @@ pub async fn send(context: Arc<ClientContext>, params: ParamsOfSend) -> ClientRe
         .get(&params.debot_handle.0)
         .ok_or(Error::invalid_handle(params.debot_handle.0))?;
     let mut dengine = mutex.1.lock().await;
-    dengine
+    let result = dengine
         .send(params.message)
-        .await
-}
\ No newline at end of file
+        .await;
+    check_policy(&dengine, result)
+}
+
+/// [UNSTABLE](UNSTABLE.md) Parameters of `register_interface` function.
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfRegisterInterface {
+    /// Debot handle which references an instance of debot engine.
+    pub debot_handle: DebotHandle,
+    /// Interface identifier: 256-bit hash in hex. DeBot calls the interface
+    /// by sending messages to `-31:<interface_id>` address.
+    pub interface_id: String,
+    /// Interface ABI. Used to decode interface calls.
+    pub abi: Abi,
+}
+
+/// Adds interface implemented by the application to the debot engine.
+///
+/// Replaces the interface with the same id, including built-in ones.
+pub async fn register_interface(
+    context: Arc<ClientContext>,
+    debot_handle: DebotHandle,
+    iface: Arc<dyn DebotInterface + Send + Sync>,
+) -> ClientResult<()> {
+    let id = iface.get_id();
+    if hex::decode(&id).map(|bytes| bytes.len() != 32).unwrap_or(true) {
+        return Err(Error::invalid_interface(format!(
+            "interface id must be 256-bit hash in hex, got \"{}\"",
+            id
+        )));
+    }
+    let mutex = context
+        .debots
+        .get(&debot_handle.0)
+        .ok_or(Error::invalid_handle(debot_handle.0))?;
+    mutex.1.lock().await.add_interface(iface);
+    Ok(())
+}
+
+/// Replaces the result of the engine operation with the policy violation error.
+fn check_policy<T>(dengine: &DEngine, result: ClientResult<T>) -> ClientResult<T> {
+    match dengine.take_policy_violation() {
+        Some(err) => Err(err),
+        None => result,
+    }
+}
+
+/// [UNSTABLE](UNSTABLE.md) Parameters of `save_session` function.
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfSaveSession {
+    /// Debot handle which references an instance of debot engine.
+    pub debot_handle: DebotHandle,
+}
+
+/// [UNSTABLE](UNSTABLE.md)
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfSaveSession {
+    /// Serialized debot session. JSON string.
+    pub session: String,
+}
+
+/// [UNSTABLE](UNSTABLE.md) Saves debot session.
+///
+/// Serializes debot engine state: debot address, ABI, account state, contexts,
+/// shown actions, interface calls which are not answered yet, the recorded
+/// event log and states of DeBots run by the engine (see `manage_invoked_debots`).
+/// The session can be restored later with `restore_session`, e.g. after the application restart.
+#[api_function]
+pub async fn save_session(
+    context: Arc<ClientContext>,
+    params: ParamsOfSaveSession,
+) -> ClientResult<ResultOfSaveSession> {
+    let mutex = context
+        .debots
+        .get(&params.debot_handle.0)
+        .ok_or(Error::invalid_handle(params.debot_handle.0))?;
+    let dengine = mutex.1.lock().await;
+    let session = serde_json::to_string(&dengine.save())
+        .map_err(|e| Error::invalid_session(e))?;
+    Ok(ResultOfSaveSession { session })
+}
+
+/// [UNSTABLE](UNSTABLE.md) Parameters of `get_event_log` function.
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfGetEventLog {
+    /// Debot handle which references an instance of debot engine.
+    pub debot_handle: DebotHandle,
+}
+
+/// [UNSTABLE](UNSTABLE.md)
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfGetEventLog {
+    /// Serialized event log. JSON string which can be saved to a file.
+    pub event_log: String,
+}
+
+/// [UNSTABLE](UNSTABLE.md) Returns detailed event log of DeBot execution.
+///
+/// The log contains DeBot functions and get-methods calls with inputs and outputs,
+/// interface calls with decoded arguments and answers, actions and context switches,
+/// outgoing messages with their activities, and all browser callbacks.
+/// Recording must be enabled with `event_log` parameter of `init`.
+#[api_function]
+pub async fn get_event_log(
+    context: Arc<ClientContext>,
+    params: ParamsOfGetEventLog,
+) -> ClientResult<ResultOfGetEventLog> {
+    let mutex = context
+        .debots
+        .get(&params.debot_handle.0)
+        .ok_or(Error::invalid_handle(params.debot_handle.0))?;
+    let log = mutex.1.lock().await.event_log()
+        .ok_or_else(|| Error::invalid_event_log("recording is not enabled for this debot"))?;
+    let event_log = serde_json::to_string(&log)
+        .map_err(|e| Error::invalid_event_log(e))?;
+    Ok(ResultOfGetEventLog { event_log })
+}
+
+/// [UNSTABLE](UNSTABLE.md) Parameters of `replay_event_log` function.
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfReplayEventLog {
+    /// Event log returned from `get_event_log`.
+    pub event_log: String,
+    /// Signing box given to DeBot when it requests one. If not specified,
+    /// requests fail.
+    pub signing_box: Option<SigningBoxHandle>,
+}
+
+/// [UNSTABLE](UNSTABLE.md)
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ResultOfReplayEventLog {
+    /// Event log of the replay. JSON string.
+    pub event_log: String,
+    /// Index of the first event which differs from the recorded log.
+    /// `None` if DeBot behaves the same way.
+    pub first_difference: Option<u32>,
+}
+
+/// [UNSTABLE](UNSTABLE.md) Replays recorded DeBot execution offline.
+///
+/// Runs the same DeBot from the recorded account states without network access:
+/// operations requested by the application are repeated, interface calls and browser
+/// callbacks are answered with the recorded values, the recorded policy and ABIs are applied.
+/// Creation time of messages, signatures and time headers of external messages, fees
+/// and handles are ignored when the logs are compared.
+#[api_function]
+pub async fn replay_event_log(
+    context: Arc<ClientContext>,
+    params: ParamsOfReplayEventLog,
+) -> ClientResult<ResultOfReplayEventLog> {
+    let log: DebotEventLog = serde_json::from_str(&params.event_log)
+        .map_err(|e| Error::invalid_event_log(e))?;
+    if log.version != event_log::EVENT_LOG_VERSION {
+        return Err(Error::invalid_event_log(format!("unsupported version {}", log.version)));
+    }
+    let replayed = log.replay(context, params.signing_box).await?;
+    let first_difference = log.first_difference(&replayed).map(|i| i as u32);
+    let event_log = serde_json::to_string(&replayed)
+        .map_err(|e| Error::invalid_event_log(e))?;
+    Ok(ResultOfReplayEventLog { event_log, first_difference })
+}
+
+/// [UNSTABLE](UNSTABLE.md) Parameters of `restore_session` function.
+#[derive(Serialize, Deserialize, ApiType, Default)]
+pub struct ParamsOfRestoreSession {
+    /// Debot session returned from `save_session`.
+    pub session: String,
+    /// Restrictions applied to DeBot execution. Policy is not saved in the session.
+    pub policy: Option<DebotPolicy>,
+    /// ABIs of contracts DeBot works with. ABIs are not saved in the session.
+    pub abis: Option<Vec<Abi>>,
+}
+
+/// [UNSTABLE](UNSTABLE.md) Restores debot session.
+///
+/// Creates an instance of Debot Engine from the saved session and continues
+/// the dialog: the actions of the current context are shown again and the
+/// interface calls which were not answered before saving are sent again.
+/// Blockchain is not queried, debot state is taken from the session.
+/// Returns a debot handle which can be used later in `execute` or `send` functions.
+pub async fn restore_session(
+    context: Arc<ClientContext>,
+    params: ParamsOfRestoreSession,
+    callbacks: impl BrowserCallbacks + Send + Sync + 'static,
+) -> ClientResult<RegisteredDebot> {
+    let session: DebotSession = serde_json::from_str(&params.session)
+        .map_err(|e| Error::invalid_session(e))?;
+    let info = session.info.clone();
+    let mut dengine = DEngine::restore(session, context.clone(), Arc::new(callbacks))
+        .map_err(Error::invalid_session)?
+        .with_policy(params.policy.unwrap_or_default());
+    for abi in params.abis.unwrap_or_default() {
+        dengine.add_abi(abi);
+    }
+    dengine.resume().await;
+
+    let handle = context.get_next_id();
+    context.debots.insert(handle, Mutex::new(dengine));
+    let debot_abi = info.dabi.clone().unwrap_or(String::new());
+    Ok(RegisteredDebot { debot_handle: DebotHandle(handle), info, debot_abi })
+}
@@ -11,6 +11,7 @@ use ton_abi::Contract;
 use crate::abi::Signer;
 use crate::boc::{parse_message, ParamsOfParse};
 use crate::debot::DEngine;
+use super::mock_node::MockNode;
 
 const ABI: &str = r#"
 {
@@ -36,13 +37,14 @@ const ABI: &str = r#"
 }
 "#;
 
-const ID: &str = "475a5d1729acee4601c2a8cb67240e4da5316cc90a116e1b181d905e79401c51";
+pub(crate) const ID: &str = "475a5d1729acee4601c2a8cb67240e4da5316cc90a116e1b181d905e79401c51";
 
 pub struct MsgInterface {
     ton: TonClient,
     debot_addr: String,
     debot_abi: Abi,
     browser: Arc<dyn BrowserCallbacks + Send + Sync>,
+    node: Option<Arc<MockNode>>,
 }
 
 impl MsgInterface {
@@ -51,12 +53,14 @@ impl MsgInterface {
         debot_addr: String,
         debot_abi: Abi,
         browser: Arc<dyn BrowserCallbacks + Send + Sync>,
+        node: Option<Arc<MockNode>>,
     ) -> Self {
         Self {
             ton,
             debot_addr,
             debot_abi,
             browser,
+            node,
         }
     }
 
@@ -76,9 +80,14 @@ impl MsgInterface {
             .map_err(|e| format!("{}", e))?
             .parsed;
         let dest = parsed_msg["dst"].as_str().ok_or(format!("failed to parse dst address"))?.to_owned();
-        let target_state = DEngine::load_state(self.ton.clone(), dest)
-            .await
-            .map_err(|e| format!("{}", e))?;
+        let target_state = match self.node.as_ref() {
+            Some(node) => node.account(&dest)
+                .await
+                .ok_or(format!("account {} not found in mock node", dest))?,
+            None => DEngine::load_state(self.ton.clone(), dest)
+                .await
+                .map_err(|e| format!("{}", e))?,
+        };
         let callobj = ContractCall::new(
             self.browser.clone(),
             self.ton.clone(),
@@ -87,6 +96,7 @@ impl MsgInterface {
             target_state,
             self.debot_addr.clone(),
             false,
+            self.node.clone(),
         ).await.map_err(|e| format!("{}", e))?;
         let answer_msg = callobj.execute()
             .await
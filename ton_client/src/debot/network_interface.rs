@@ -1,11 +1,13 @@
 use super::dinterface::{
     decode_answer_id, get_array_strings, get_string_arg, DebotInterface, InterfaceResult,
 };
+use super::policy::PolicyGuard;
 use super::TonClient;
 use crate::abi::Abi;
 use crate::client::FetchMethod;
 use serde_json::Value;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 const ABI: &str = r#"
 {
@@ -51,11 +53,16 @@ const ID: &str = "e38aed5884dc3e4426a87c083faaf4fa08109189fbc0c79281112f52e062d8
 
 pub struct NetworkInterface {
     client: TonClient,
+    guard: Option<Arc<PolicyGuard>>,
 }
 
 impl NetworkInterface {
     pub fn new(client: TonClient) -> Self {
-        Self { client }
+        Self { client, guard: None }
+    }
+
+    pub(super) fn with_guard(client: TonClient, guard: Arc<PolicyGuard>) -> Self {
+        Self { client, guard: Some(guard) }
     }
 
     async fn post(&self, args: &Value) -> InterfaceResult {
@@ -81,6 +88,9 @@ impl NetworkInterface {
         headers: Vec<String>,
         body: Option<String>,
     ) -> Result<Value, String> {
+        if let Some(guard) = self.guard.as_ref() {
+            guard.check_request(&url).map_err(|e| e.message)?;
+        }
         let mut header_map = HashMap::new();
         for h in headers {
             let mut iter = h.split(':');
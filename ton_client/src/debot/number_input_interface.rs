@@ -0,0 +1,83 @@
+use super::dinterface::{decode_answer_id, get_arg, get_string_arg, DebotInterface, InterfaceResult};
+use super::ui_browser::{input_valid, UiCallbacks};
+use crate::abi::Abi;
+use crate::encoding::decode_abi_bigint;
+use num_bigint::BigInt;
+use serde_json::Value;
+use std::str::FromStr;
+use std::sync::Arc;
+
+const ABI: &str = r#"
+{
+	"ABI version": 2,
+	"header": ["time"],
+	"functions": [
+		{
+			"name": "get",
+			"inputs": [
+				{"name":"answerId","type":"uint32"},
+				{"name":"prompt","type":"bytes"},
+				{"name":"min","type":"int256"},
+				{"name":"max","type":"int256"}
+			],
+			"outputs": [
+				{"name":"value","type":"int256"}
+			]
+		}
+	],
+	"data": [
+	],
+	"events": [
+	]
+}
+"#;
+
+const NUMBER_INPUT_ID: &str = "c5a9558b2664aed7dc3e6123436d544f13ffe69ab0e259412f48c6d1c8588401";
+
+pub struct NumberInputInterface {
+    ui: Arc<dyn UiCallbacks + Send + Sync>,
+}
+
+impl NumberInputInterface {
+    pub fn new(ui: Arc<dyn UiCallbacks + Send + Sync>) -> Self {
+        Self { ui }
+    }
+
+    async fn get(&self, args: &Value) -> InterfaceResult {
+        let answer_id = decode_answer_id(args)?;
+        let prompt = get_string_arg(args, "prompt")?;
+        let min = decode_abi_bigint(&get_arg(args, "min")?).map_err(|e| e.to_string())?;
+        let max = decode_abi_bigint(&get_arg(args, "max")?).map_err(|e| e.to_string())?;
+        if min > max {
+            return Err(format!("min number is greater than max number"));
+        }
+        let value = input_valid(self.ui.as_ref(), &prompt, |value| {
+            let number = BigInt::from_str(value)
+                .map_err(|_| format!("invalid number \"{}\"", value))?;
+            if number < min || number > max {
+                return Err(format!("number must be between {} and {}", min, max));
+            }
+            Ok(number)
+        })
+        .await?;
+        Ok((answer_id, json!({ "value": value.to_string() })))
+    }
+}
+
+#[async_trait::async_trait]
+impl DebotInterface for NumberInputInterface {
+    fn get_id(&self) -> String {
+        NUMBER_INPUT_ID.to_string()
+    }
+
+    fn get_abi(&self) -> Abi {
+        Abi::Json(ABI.to_owned())
+    }
+
+    async fn call(&self, func: &str, args: &Value) -> InterfaceResult {
+        match func {
+            "get" => self.get(args).await,
+            _ => Err(format!("function \"{}\" is not implemented", func)),
+        }
+    }
+}
@@ -0,0 +1,224 @@
+/*
+* Copyright 2018-2021 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use super::{Error, TonClient};
+use crate::error::{ClientError, ClientResult};
+use crate::tvm::ExecutionOptions;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use url::Url;
+
+const MINUTE_MS: u64 = 60_000;
+const SECOND_MS: u64 = 1_000;
+
+/// [UNSTABLE](UNSTABLE.md) Restrictions applied to DeBot execution.
+///
+/// All limits are optional. Violations fail the DeBot operation with
+/// the corresponding `debot` error code.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType, Default, PartialEq)]
+pub struct DebotPolicy {
+    /// Maximum gas consumed by one DeBot function call.
+    pub gas_limit: Option<u64>,
+    /// Maximum duration of one DeBot function call in milliseconds.
+    /// The limit is checked after the call, results of a longer call are discarded.
+    /// TVM execution can not be interrupted, use `gas_limit` to stop looping DeBots.
+    pub time_limit: Option<u32>,
+    /// Maximum number of interface calls made by DeBot while executing one
+    /// action or processing one message from the browser.
+    pub max_interface_calls: Option<u32>,
+    /// URLs allowed for Network interface requests. If specified, requests to other
+    /// URLs are rejected. A request matches the URL if it has the same scheme, host
+    /// and port, and its path starts with the path segments of the URL.
+    pub url_allow_list: Option<Vec<String>>,
+    /// URLs denied for Network interface requests. Matched the same way as `url_allow_list`.
+    pub url_deny_list: Option<Vec<String>>,
+    /// Maximum number of messages DeBot can print or send to the browser per second.
+    pub max_messages_per_second: Option<u32>,
+    /// Maximum number of Network interface requests per minute.
+    pub max_requests_per_minute: Option<u32>,
+}
+
+#[derive(Default)]
+struct GuardState {
+    interface_calls: u32,
+    messages: VecDeque<u64>,
+    requests: VecDeque<u64>,
+    violation: Option<ClientError>,
+}
+
+/// Enforces `DebotPolicy` for one DeBot engine and the interfaces it uses.
+///
+/// Violations detected inside interfaces are reported to DeBot as interface
+/// errors and also kept here, so the engine can fail with the right error code.
+pub(super) struct PolicyGuard {
+    policy: DebotPolicy,
+    client: TonClient,
+    state: Mutex<GuardState>,
+}
+
+impl PolicyGuard {
+    pub fn new(client: TonClient, policy: DebotPolicy) -> Self {
+        Self {
+            policy,
+            client,
+            state: Default::default(),
+        }
+    }
+
+    pub fn policy(&self) -> &DebotPolicy {
+        &self.policy
+    }
+
+    pub fn execution_options(&self) -> Option<ExecutionOptions> {
+        self.policy.gas_limit.map(|gas_limit| ExecutionOptions {
+            gas_limit: Some(gas_limit),
+            ..Default::default()
+        })
+    }
+
+    pub fn now(&self) -> u64 {
+        self.client.env.now_ms()
+    }
+
+    /// Records the violation and returns it. The first violation is kept
+    /// until it is taken by the engine.
+    pub fn violate(&self, err: ClientError) -> ClientError {
+        let mut state = self.state.lock().unwrap();
+        if state.violation.is_none() {
+            state.violation = Some(err.clone());
+        }
+        err
+    }
+
+    pub fn violation(&self) -> Option<ClientError> {
+        self.state.lock().unwrap().violation.clone()
+    }
+
+    pub fn take_violation(&self) -> Option<ClientError> {
+        self.state.lock().unwrap().violation.take()
+    }
+
+    /// Starts a new action: resets the interface calls counter.
+    pub fn begin_action(&self) {
+        self.state.lock().unwrap().interface_calls = 0;
+    }
+
+    /// Checks the result of DeBot function call started at `started` time.
+    pub fn check_run(&self, started: u64, error: Option<&ClientError>) -> ClientResult<()> {
+        if let (Some(gas_limit), Some(err)) = (self.policy.gas_limit, error) {
+            if is_out_of_gas(err) {
+                return Err(self.violate(Error::gas_limit_exceeded(gas_limit)));
+            }
+        }
+        if let Some(time_limit) = self.policy.time_limit {
+            if self.now().saturating_sub(started) > time_limit as u64 {
+                return Err(self.violate(Error::time_limit_exceeded(time_limit)));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn count_interface_call(&self) -> ClientResult<()> {
+        let mut state = self.state.lock().unwrap();
+        state.interface_calls += 1;
+        if let Some(max) = self.policy.max_interface_calls {
+            if state.interface_calls > max {
+                drop(state);
+                return Err(self.violate(Error::interface_calls_limit_exceeded(max)));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn count_message(&self) -> ClientResult<()> {
+        let max = match self.policy.max_messages_per_second {
+            Some(max) => max,
+            None => return Ok(()),
+        };
+        let exceeded = {
+            let mut state = self.state.lock().unwrap();
+            !Self::count(&mut state.messages, self.now(), SECOND_MS, max)
+        };
+        if exceeded {
+            return Err(self.violate(Error::rate_limit_exceeded(format!(
+                "more than {} messages per second",
+                max
+            ))));
+        }
+        Ok(())
+    }
+
+    pub fn check_request(&self, url: &str) -> ClientResult<()> {
+        let parsed = match Url::parse(url) {
+            Ok(parsed) => parsed,
+            Err(_) => return Err(self.violate(Error::url_not_allowed(url))),
+        };
+        let matches = |list: &Vec<String>| list.iter().any(|pattern| url_matches(&parsed, pattern));
+        let allowed = self.policy.url_allow_list.as_ref().map(matches).unwrap_or(true);
+        let denied = self.policy.url_deny_list.as_ref().map(matches).unwrap_or(false);
+        if !allowed || denied {
+            return Err(self.violate(Error::url_not_allowed(url)));
+        }
+        if let Some(max) = self.policy.max_requests_per_minute {
+            let exceeded = {
+                let mut state = self.state.lock().unwrap();
+                !Self::count(&mut state.requests, self.now(), MINUTE_MS, max)
+            };
+            if exceeded {
+                return Err(self.violate(Error::rate_limit_exceeded(format!(
+                    "more than {} network requests per minute",
+                    max
+                ))));
+            }
+        }
+        Ok(())
+    }
+
+    fn count(events: &mut VecDeque<u64>, now: u64, period: u64, max: u32) -> bool {
+        while events.front().map(|time| now.saturating_sub(*time) >= period).unwrap_or(false) {
+            events.pop_front();
+        }
+        if events.len() >= max as usize {
+            return false;
+        }
+        events.push_back(now);
+        true
+    }
+}
+
+/// Checks if `url` is covered by `pattern` URL. Both URLs are normalized by the parser,
+/// so hosts are compared case-insensitively and `.` and `..` path segments are resolved.
+fn url_matches(url: &Url, pattern: &str) -> bool {
+    let pattern = match Url::parse(pattern) {
+        Ok(pattern) => pattern,
+        Err(_) => return false,
+    };
+    let segments = |url: &Url| -> Vec<String> {
+        url.path_segments()
+            .map(|segments| segments.filter(|s| !s.is_empty()).map(str::to_owned).collect())
+            .unwrap_or_default()
+    };
+    url.scheme() == pattern.scheme()
+        && url.host_str() == pattern.host_str()
+        && url.port_or_known_default() == pattern.port_or_known_default()
+        && segments(url).starts_with(&segments(&pattern))
+}
+
+fn is_out_of_gas(err: &ClientError) -> bool {
+    let code = ton_types::ExceptionCode::OutOfGas as i64;
+    err.code == crate::tvm::ErrorCode::ContractExecutionError as u32
+        && err.data["exit_code"]
+            .as_i64()
+            .map(|exit_code| exit_code == code || exit_code == !code)
+            .unwrap_or(false)
+}
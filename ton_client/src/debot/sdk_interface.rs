@@ -2,8 +2,9 @@ use super::dinterface::{
     decode_answer_id, get_arg, get_bool_arg, get_num_arg, get_string_arg, DebotInterface,
     InterfaceResult,
 };
+use super::locale::Locale;
 use super::routines;
-use super::TonClient;
+use super::{BrowserCallbacks, DebotActivity, Error, TonClient};
 use crate::abi::Abi;
 use crate::crypto::{
     chacha20, encryption_box_decrypt, encryption_box_encrypt, encryption_box_get_info,
@@ -21,6 +22,7 @@ use crate::crypto::{
 use crate::encoding::decode_abi_bigint;
 use crate::net::{query_collection, OrderBy, ParamsOfQueryCollection, SortDirection};
 use serde_json::Value;
+use std::sync::Arc;
 
 const ABI: &str = r#"
 {
@@ -147,6 +149,36 @@ const ABI: &str = r#"
 				{"name":"substr","type":"bytes"}
 			]
 		},
+		{
+			"name": "getLocale",
+			"inputs": [
+				{"name":"answerId","type":"uint32"}
+			],
+			"outputs": [
+				{"name":"locale","type":"bytes"}
+			]
+		},
+		{
+			"name": "formatAmount",
+			"inputs": [
+				{"name":"answerId","type":"uint32"},
+				{"name":"amount","type":"uint128"},
+				{"name":"decimals","type":"uint8"}
+			],
+			"outputs": [
+				{"name":"str","type":"bytes"}
+			]
+		},
+		{
+			"name": "formatDate",
+			"inputs": [
+				{"name":"answerId","type":"uint32"},
+				{"name":"time","type":"uint32"}
+			],
+			"outputs": [
+				{"name":"str","type":"bytes"}
+			]
+		},
 		{
 			"name": "mnemonicFromRandom",
 			"inputs": [
@@ -305,6 +337,8 @@ const SDK_ID: &str = "8fc6454f90072c9f1f6d3313ae1608f64f4a0660c6ae9f42c68b6a79e2
 
 pub struct SdkInterface {
     ton: TonClient,
+    browser: Arc<dyn BrowserCallbacks + Send + Sync>,
+    locale: Locale,
 }
 
 #[derive(Default, Serialize)]
@@ -335,8 +369,15 @@ impl From<EncryptionBoxInfo> for EncryptionBoxInfoResult {
 }
 
 impl SdkInterface {
-    pub fn new(ton: TonClient) -> Self {
-        Self { ton }
+    pub fn new(ton: TonClient, browser: Arc<dyn BrowserCallbacks + Send + Sync>) -> Self {
+        Self { ton, browser, locale: Locale::default() }
+    }
+
+    /// Sets preferred locale of the user returned by `getLocale`
+    /// and used by `formatAmount` and `formatDate`.
+    pub fn with_locale(mut self, locale: Option<String>) -> Self {
+        self.locale = Locale::new(locale);
+        self
     }
 
     async fn get_balance(&self, args: &Value) -> InterfaceResult {
@@ -555,6 +596,26 @@ impl SdkInterface {
         ))
     }
 
+    fn get_locale(&self, args: &Value) -> InterfaceResult {
+        let answer_id = decode_answer_id(args)?;
+        Ok((answer_id, json!({ "locale": hex::encode(self.locale.tag()) })))
+    }
+
+    fn format_amount(&self, args: &Value) -> InterfaceResult {
+        let answer_id = decode_answer_id(args)?;
+        let amount = decode_abi_bigint(&get_arg(args, "amount")?).map_err(|e| e.to_string())?;
+        let decimals = get_num_arg::<u8>(args, "decimals")?;
+        let result = self.locale.format_amount(&amount, decimals);
+        Ok((answer_id, json!({ "str": hex::encode(result) })))
+    }
+
+    fn format_date(&self, args: &Value) -> InterfaceResult {
+        let answer_id = decode_answer_id(args)?;
+        let time = get_num_arg::<u32>(args, "time")?;
+        let result = self.locale.format_date(time);
+        Ok((answer_id, json!({ "str": hex::encode(result) })))
+    }
+
     fn nacl_box(&self, args: &Value) -> InterfaceResult {
         let answer_id = decode_answer_id(args)?;
         let decrypted = base64::encode(
@@ -654,9 +715,25 @@ impl SdkInterface {
     async fn encrypt_or_decrypt(&self, args: &Value, encrypt: bool) -> InterfaceResult {
         let answer_id = decode_answer_id(args)?;
         let encryption_box = EncryptionBoxHandle(get_num_arg::<u32>(args, "boxHandle")?);
-        let data =
-            base64::encode(&hex::decode(&get_arg(args, "data")?).map_err(|e| format!("{}", e))?);
-        let result = if encrypt {
+        let data = hex::decode(&get_arg(args, "data")?).map_err(|e| format!("{}", e))?;
+        let algorithm = encryption_box_get_info(
+            self.ton.clone(),
+            ParamsOfEncryptionBoxGetInfo { encryption_box: encryption_box.clone() },
+        )
+        .await
+        .ok()
+        .and_then(|x| x.info.algorithm);
+        let activity = DebotActivity::EncryptionBoxUse {
+            encryption_box_handle: encryption_box.0,
+            encrypt,
+            algorithm,
+            data_size: data.len() as u32,
+        };
+        let data = base64::encode(&data);
+        let approved = self.browser.approve(activity).await.map_err(|e| e.to_string())?;
+        let result = if !approved {
+            Err(Error::operation_rejected().code)
+        } else if encrypt {
             encryption_box_encrypt(
                 self.ton.clone(),
                 ParamsOfEncryptionBoxEncrypt {
@@ -759,6 +836,22 @@ impl SdkInterface {
         let hash_to_sign =
             decode_abi_bigint(&get_arg(&args, "hash")?).map_err(|e| e.to_string())?;
 
+        let signkey = signing_box_get_public_key(
+            self.ton.clone(),
+            RegisteredSigningBox { handle: box_handle.into() },
+        )
+        .await
+        .map(|x| x.pubkey)
+        .unwrap_or_default();
+        let activity = DebotActivity::SignHash {
+            signing_box_handle: box_handle,
+            signkey,
+            hash: format!("{:064x}", hash_to_sign),
+        };
+        if !self.browser.approve(activity).await.map_err(|e| e.to_string())? {
+            return Err(Error::operation_rejected().to_string());
+        }
+
         let signature = signing_box_sign(
             self.ton.clone(),
             ParamsOfSigningBoxSign {
@@ -802,6 +895,9 @@ impl DebotInterface for SdkInterface {
             "naclSignKeypairFromSecretKey" => self.nacl_sign_keypair_from_secret_key(args),
 
             "substring" => self.substring(args),
+            "getLocale" => self.get_locale(args),
+            "formatAmount" => self.format_amount(args),
+            "formatDate" => self.format_date(args),
 
             "genRandom" => self.get_random(args),
             "signHash" => self.sign_hash(args).await,
@@ -0,0 +1,123 @@
+/*
+* Copyright 2018-2021 TON DEV SOLUTIONS LTD.
+*
+* Licensed under the SOFTWARE EVALUATION License (the "License"); you may not use
+* this file except in compliance with the License.
+*
+* Unless required by applicable law or agreed to in writing, software
+* distributed under the License is distributed on an "AS IS" BASIS,
+* WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+* See the License for the specific TON DEV software governing permissions and
+* limitations under the License.
+*/
+
+use super::context::DContext;
+use super::event_log::DebotEventLog;
+use super::{DebotAction, DebotInfo};
+use crate::boc::internal::deserialize_object_from_base64;
+use ton_block::Message;
+
+pub(super) const SESSION_VERSION: u8 = 1;
+
+/// Snapshot of the DeBot engine state.
+///
+/// Contains everything needed to continue the dialog in a new engine instance:
+/// DeBot account state, its contexts, displayed actions, interface calls
+/// which are not answered by the browser yet and sessions of DeBots run by
+/// the engine on behalf of this one.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct DebotSession {
+    pub version: u8,
+    /// DeBot address.
+    pub address: String,
+    /// DeBot ABI. Empty if DeBot does not provide it.
+    pub abi: String,
+    /// DeBot account state BOC encoded in `base64`.
+    pub state: String,
+    pub info: DebotInfo,
+    pub contexts: Vec<SessionContext>,
+    pub curr_context: u8,
+    pub prev_context: u8,
+    pub target_addr: Option<String>,
+    pub target_abi: Option<String>,
+    /// Actions of the current context shown to the user.
+    pub actions: Vec<DebotAction>,
+    /// Messages sent to the browser (interface calls or other DeBots invocations)
+    /// which are not answered yet.
+    pub pending_messages: Vec<String>,
+    /// Preferred locale of the user.
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// Event log recorded so far. `None` if recording is not enabled.
+    /// Invoked DeBots share the log of the DeBot which started the dialog.
+    #[serde(default)]
+    pub event_log: Option<DebotEventLog>,
+    /// DeBots invoked with internal messages are run by the engine.
+    #[serde(default)]
+    pub manage_invoked_debots: bool,
+    /// Sessions of DeBots invoked by this one.
+    #[serde(default)]
+    pub invoked: Vec<DebotSession>,
+    /// Addresses of DeBots which invoked this one, the caller is the last.
+    #[serde(default)]
+    pub call_stack: Vec<String>,
+    /// Messages to DeBots from `call_stack` waiting for delivery.
+    #[serde(default)]
+    pub answers: Vec<String>,
+}
+
+impl DebotSession {
+    /// Returns true if `addr` is the address of this DeBot or of DeBot invoked by it.
+    pub(super) fn runs(&self, addr: &str) -> bool {
+        self.address == addr || self.invoked.iter().any(|session| session.runs(addr))
+    }
+
+    /// Collects unanswered messages of this DeBot and of DeBots invoked by it.
+    pub(super) fn collect_pending_messages(&self, messages: &mut Vec<String>) {
+        messages.extend(self.pending_messages.iter().cloned());
+        for session in &self.invoked {
+            session.collect_pending_messages(messages);
+        }
+    }
+}
+
+/// Serializable DeBot context.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct SessionContext {
+    pub id: u8,
+    pub desc: String,
+    pub actions: Vec<DebotAction>,
+}
+
+impl From<DContext> for SessionContext {
+    fn from(ctx: DContext) -> Self {
+        Self {
+            id: ctx.id,
+            desc: ctx.desc,
+            actions: ctx.actions.into_iter().map(|act| act.into()).collect(),
+        }
+    }
+}
+
+impl Into<DContext> for SessionContext {
+    fn into(self) -> DContext {
+        DContext::new(
+            self.desc,
+            self.actions.into_iter().map(|act| act.into()).collect(),
+            self.id,
+        )
+    }
+}
+
+/// Checks if `answer` message is sent back from the destination of `request` message.
+pub(super) fn is_answer(answer: &str, request: &str) -> bool {
+    let parse = |msg: &str| -> Option<Message> {
+        deserialize_object_from_base64(msg, "message").ok().map(|msg| msg.object)
+    };
+    match (parse(answer), parse(request)) {
+        (Some(answer), Some(request)) => {
+            answer.src_ref().is_some() && answer.src_ref() == request.dst_ref()
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,92 @@
+use super::dinterface::{decode_answer_id, get_string_arg, DebotInterface, InterfaceResult};
+use super::ui_browser::UiCallbacks;
+use super::TonClient;
+use crate::abi::Abi;
+use crate::crypto::{signing_box_get_public_key, RegisteredSigningBox};
+use crate::encoding::decode_abi_bigint;
+use serde_json::Value;
+use std::sync::Arc;
+
+const ABI: &str = r#"
+{
+	"ABI version": 2,
+	"header": ["time"],
+	"functions": [
+		{
+			"name": "get",
+			"inputs": [
+				{"name":"answerId","type":"uint32"},
+				{"name":"prompt","type":"bytes"},
+				{"name":"possiblePublicKeys","type":"uint256[]"}
+			],
+			"outputs": [
+				{"name":"handle","type":"uint32"}
+			]
+		}
+	],
+	"data": [
+	],
+	"events": [
+	]
+}
+"#;
+
+const SIGNING_BOX_INPUT_ID: &str = "c13024e101c95e71afb1f5fa6d72f633d51e721de0320d73dfd6121a54e4d40a";
+
+pub struct SigningBoxInputInterface {
+    ton: TonClient,
+    ui: Arc<dyn UiCallbacks + Send + Sync>,
+}
+
+impl SigningBoxInputInterface {
+    pub fn new(ton: TonClient, ui: Arc<dyn UiCallbacks + Send + Sync>) -> Self {
+        Self { ton, ui }
+    }
+
+    async fn get(&self, args: &Value) -> InterfaceResult {
+        let answer_id = decode_answer_id(args)?;
+        let prompt = get_string_arg(args, "prompt")?;
+        let possible_keys = args["possiblePublicKeys"]
+            .as_array()
+            .ok_or(format!("\"possiblePublicKeys\" is invalid: must be array"))?
+            .iter()
+            .map(|key| {
+                let key = decode_abi_bigint(key.as_str().unwrap_or_default())
+                    .map_err(|e| format!("invalid public key: {}", e))?;
+                Ok(format!("{:064x}", key))
+            })
+            .collect::<Result<Vec<String>, String>>()?;
+        let signing_box = self.ui.get_signing_box(&prompt, &possible_keys).await?;
+        if !possible_keys.is_empty() {
+            let pubkey = signing_box_get_public_key(
+                self.ton.clone(),
+                RegisteredSigningBox { handle: signing_box.clone() },
+            )
+            .await
+            .map_err(|e| format!("failed to get signing box public key: {}", e))?
+            .pubkey;
+            if !possible_keys.contains(&pubkey) {
+                return Err(format!("signing box public key {} is not allowed by DeBot", pubkey));
+            }
+        }
+        Ok((answer_id, json!({ "handle": signing_box.0 })))
+    }
+}
+
+#[async_trait::async_trait]
+impl DebotInterface for SigningBoxInputInterface {
+    fn get_id(&self) -> String {
+        SIGNING_BOX_INPUT_ID.to_string()
+    }
+
+    fn get_abi(&self) -> Abi {
+        Abi::Json(ABI.to_owned())
+    }
+
+    async fn call(&self, func: &str, args: &Value) -> InterfaceResult {
+        match func {
+            "get" => self.get(args).await,
+            _ => Err(format!("function \"{}\" is not implemented", func)),
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use super::dinterface::{
+    decode_answer_id, get_bool_arg, get_string_arg, DebotInterface, InterfaceResult,
+};
+use super::ui_browser::UiCallbacks;
+use crate::abi::Abi;
+use serde_json::Value;
+use std::sync::Arc;
+
+const ABI: &str = r#"
+{
+	"ABI version": 2,
+	"header": ["time"],
+	"functions": [
+		{
+			"name": "print",
+			"inputs": [
+				{"name":"answerId","type":"uint32"},
+				{"name":"message","type":"bytes"}
+			],
+			"outputs": [
+			]
+		},
+		{
+			"name": "input",
+			"inputs": [
+				{"name":"answerId","type":"uint32"},
+				{"name":"prompt","type":"bytes"},
+				{"name":"multiline","type":"bool"}
+			],
+			"outputs": [
+				{"name":"value","type":"bytes"}
+			]
+		}
+	],
+	"data": [
+	],
+	"events": [
+	]
+}
+"#;
+
+const TERMINAL_ID: &str = "8796536366ee21852db56dccb60bc564598b618c865fc50c8b1ab740bba128e3";
+
+pub struct TerminalInterface {
+    ui: Arc<dyn UiCallbacks + Send + Sync>,
+}
+
+impl TerminalInterface {
+    pub fn new(ui: Arc<dyn UiCallbacks + Send + Sync>) -> Self {
+        Self { ui }
+    }
+
+    async fn print(&self, args: &Value) -> InterfaceResult {
+        let answer_id = decode_answer_id(args)?;
+        let message = get_string_arg(args, "message")?;
+        self.ui.print(&message).await;
+        Ok((answer_id, json!({})))
+    }
+
+    async fn input(&self, args: &Value) -> InterfaceResult {
+        let answer_id = decode_answer_id(args)?;
+        let prompt = get_string_arg(args, "prompt")?;
+        let multiline = get_bool_arg(args, "multiline")?;
+        let value = self.ui.input(&prompt, multiline).await?;
+        Ok((answer_id, json!({ "value": hex::encode(value.as_bytes()) })))
+    }
+}
+
+#[async_trait::async_trait]
+impl DebotInterface for TerminalInterface {
+    fn get_id(&self) -> String {
+        TERMINAL_ID.to_string()
+    }
+
+    fn get_abi(&self) -> Abi {
+        Abi::Json(ABI.to_owned())
+    }
+
+    async fn call(&self, func: &str, args: &Value) -> InterfaceResult {
+        match func {
+            "print" => self.print(args).await,
+            "input" => self.input(args).await,
+            _ => Err(format!("function \"{}\" is not implemented", func)),
+        }
+    }
+}
@@ -121,7 +121,7 @@ impl TestBrowser {
 
         let handle: RegisteredDebot = client.request_async_callback(
             "debot.init",
-            ParamsOfInit { address: address.clone() },
+            ParamsOfInit { address: address.clone(), ..Default::default() },
             callback
         ).await.unwrap();
 
@@ -138,7 +138,7 @@ impl TestBrowser {
         if call_start {
             let res: ResultOfFetch = client.request_async(
                 "debot.fetch",
-                ParamsOfFetch { address: state.address.clone() },
+                ParamsOfFetch { address: state.address.clone(), locale: None },
             ).await.unwrap();
             assert_eq!(res.info, state.info);
         }
@@ -331,17 +331,15 @@ impl TestBrowser {
             },
             ParamsOfAppDebotBrowser::Approve {activity} => {
                 let mut approved = true;
-                if let Some(expected) = state.activity.lock().await.pop() {
-                    approved = expected.approved;
-                    match activity {
-                        DebotActivity::Transaction{msg: _, dst, out, fee, setcode, signkey, signing_box_handle} => {
-                            assert_eq!(expected.dst, dst);
-                            assert_eq!(expected.out, out);
-                            assert_eq!(expected.setcode, setcode);
-                            assert_eq!(expected.signkey, signkey);
-                            assert!(signing_box_handle != 0);
-                            assert!(fee > 0);
-                        },
+                if let DebotActivity::Transaction{msg: _, dst, out, fee, setcode, signkey, signing_box_handle, ..} = activity {
+                    if let Some(expected) = state.activity.lock().await.pop() {
+                        approved = expected.approved;
+                        assert_eq!(expected.dst, dst);
+                        assert_eq!(expected.out, out);
+                        assert_eq!(expected.setcode, setcode);
+                        assert_eq!(expected.signkey, signkey);
+                        assert!(signing_box_handle != 0);
+                        assert!(fee > 0);
                     }
                 }
                 ResultOfAppDebotBrowser::Approve{ approved }
@@ -1033,6 +1031,7 @@ async fn test_debot_inner_interfaces() {
             interfaces: vec![
                 "0x8796536366ee21852db56dccb60bc564598b618c865fc50c8b1ab740bba128e3".to_owned(),
             ],
+            translations: vec![],
         },
         vec![],
     ).await;
@@ -1199,6 +1198,7 @@ async fn test_debot_getinfo() {
             dabi: Some(abi),
             icon: Some(icon),
             interfaces: vec!["0x8796536366ee21852db56dccb60bc564598b618c865fc50c8b1ab740bba128e3".to_owned()],
+            translations: vec![],
         },
         vec![],
     ).await;
@@ -1223,6 +1223,7 @@ async fn test_debot_approve() {
             "0x8796536366ee21852db56dccb60bc564598b618c865fc50c8b1ab740bba128e3".to_owned(),
             "0xc13024e101c95e71afb1f5fa6d72f633d51e721de0320d73dfd6121a54e4d40a".to_owned(),
         ],
+        translations: vec![],
     };
     let steps = serde_json::from_value(json!([])).unwrap();
     TestBrowser::execute_with_details(
@@ -1292,6 +1293,7 @@ async fn test_debot_json_interface() {
                 "0x8796536366ee21852db56dccb60bc564598b618c865fc50c8b1ab740bba128e3".to_owned(),
                 "0x442288826041d564ccedc579674f17c1b0a3452df799656a9167a41ab270ec19".to_owned(),
             ],
+            translations: vec![],
         },
         vec![],
     ).await;
@@ -1436,6 +1438,858 @@ async fn test_debot_json_parse() {
     .await;
 }
 
+async fn init_debot_on_mock_node(ton: TonClient, node: &MockNode) -> DebotData {
+    let keys = crate::crypto::generate_random_sign_keys(ton.clone()).unwrap();
+    let target_abi = TestClient::abi(TEST_DEBOT_TARGET, Some(2));
+    let debot_abi = TestClient::abi(TEST_DEBOT, Some(2));
+
+    let deploy = |abi: &Abi, name: &str, input: Option<Value>| ParamsOfEncodeMessage {
+        abi: abi.clone(),
+        deploy_set: Some(DeploySet {
+            tvc: TestClient::tvc(name, Some(2)),
+            ..Default::default()
+        }),
+        signer: Signer::Keys { keys: keys.clone() },
+        processing_try_index: None,
+        address: None,
+        call_set: CallSet::some_with_function_and_input("constructor", input.unwrap_or(json!({}))),
+    };
+
+    let target = crate::abi::encode_message(ton.clone(), deploy(&target_abi, TEST_DEBOT_TARGET, None))
+        .await
+        .unwrap();
+    node.process_message(ton.clone(), target.message, Some(target_abi.clone())).await.unwrap();
+
+    let debot = crate::abi::encode_message(
+        ton.clone(),
+        deploy(&debot_abi, TEST_DEBOT, Some(json!({
+            "targetAbi": hex::encode(&target_abi.json_string().unwrap().as_bytes()),
+            "targetAddr": target.address,
+        }))),
+    ).await.unwrap();
+    node.process_message(ton.clone(), debot.message, Some(debot_abi.clone())).await.unwrap();
+
+    let set_abi = crate::abi::encode_message(ton.clone(), ParamsOfEncodeMessage {
+        abi: debot_abi.clone(),
+        deploy_set: None,
+        signer: Signer::None,
+        processing_try_index: None,
+        address: Some(debot.address.clone()),
+        call_set: CallSet::some_with_function_and_input("setAbi", json!({
+            "debotAbi": hex::encode(&debot_abi.json_string().unwrap().as_bytes())
+        })),
+    }).await.unwrap();
+    node.process_message(ton, set_abi.message, Some(debot_abi.clone())).await.unwrap();
+
+    DebotData {
+        debot_addr: debot.address,
+        target_addr: target.address,
+        keys,
+        abi: debot_abi.json_string().unwrap(),
+    }
+}
+
+async fn run_headless(
+    ton: TonClient,
+    node: Arc<MockNode>,
+    data: &DebotData,
+    steps: Value,
+) -> ClientResult<DebotTranscript> {
+    let script: DebotScript = serde_json::from_value(json!({ "steps": steps })).unwrap();
+    let browser = Arc::new(HeadlessBrowser::new(script));
+    let mut engine = DEngine::new_with_client(
+        data.debot_addr.clone(),
+        Some(data.abi.clone()),
+        ton,
+        browser.clone(),
+    ).with_mock_node(node);
+    browser.run(&mut engine).await
+}
+
+#[tokio::test(core_threads = 2)]
+async fn test_debot_headless_browser() {
+    let ton = Arc::new(ClientContext::new(crate::ClientConfig::default()).unwrap());
+    let node = Arc::new(MockNode::new());
+    let data = init_debot_on_mock_node(ton.clone(), &node).await;
+
+    let transcript = run_headless(ton.clone(), node.clone(), &data, json!([
+        { "type": "Log", "text": "Debot Tests" },
+    ])).await.unwrap();
+    let goto = transcript.entries.iter().find_map(|entry| match entry {
+        TranscriptEntry::ShowAction { description } => Some(description.clone()),
+        _ => None,
+    }).expect("DeBot must show actions");
+
+    let transcript = run_headless(ton.clone(), node.clone(), &data, json!([
+        { "type": "Log", "text": "Debot*" },
+        { "type": "Choose", "action": goto },
+        { "type": "Log", "text": "Test Goto Action" },
+    ])).await.unwrap();
+    assert!(transcript.logs().contains(&"Test Goto Action"));
+
+    let replayed = run_headless(
+        ton.clone(),
+        node.clone(),
+        &data,
+        serde_json::to_value(DebotScript::from_transcript(&transcript).steps).unwrap(),
+    ).await.unwrap();
+    assert_eq!(transcript.logs(), replayed.logs());
+
+    let err = run_headless(ton.clone(), node.clone(), &data, json!([
+        { "type": "Input", "value": "1" },
+    ])).await.unwrap_err();
+    assert_eq!(err.code, ErrorCode::DebotScriptFailed as u32);
+
+    let err = run_headless(ton, node, &data, json!([
+        { "type": "Choose", "action": "Unknown action" },
+    ])).await.unwrap_err();
+    assert_eq!(err.code, ErrorCode::DebotScriptFailed as u32);
+}
+
+#[tokio::test(core_threads = 2)]
+async fn test_debot_session() {
+    let ton = Arc::new(ClientContext::new(crate::ClientConfig::default()).unwrap());
+    let node = Arc::new(MockNode::new());
+    let data = init_debot_on_mock_node(ton.clone(), &node).await;
+
+    let script: DebotScript = serde_json::from_value(json!({
+        "steps": [{ "type": "Log", "text": "Debot Tests" }]
+    })).unwrap();
+    let browser = Arc::new(HeadlessBrowser::new(script));
+    let mut engine = DEngine::new_with_client(
+        data.debot_addr.clone(),
+        Some(data.abi.clone()),
+        ton.clone(),
+        browser.clone(),
+    ).with_mock_node(node.clone());
+    let transcript = browser.run(&mut engine).await.unwrap();
+    let shown: Vec<String> = transcript.entries.iter().filter_map(|entry| match entry {
+        TranscriptEntry::ShowAction { description } => Some(description.clone()),
+        _ => None,
+    }).collect();
+    assert!(!shown.is_empty());
+
+    let session = serde_json::to_string(&engine.save()).unwrap();
+    drop(engine);
+
+    let script: DebotScript = serde_json::from_value(json!({
+        "steps": [
+            { "type": "Choose", "action": shown[0] },
+            { "type": "Log", "text": "Test Goto Action" },
+        ]
+    })).unwrap();
+    let browser = Arc::new(HeadlessBrowser::new(script));
+    let mut engine = DEngine::restore(
+        serde_json::from_str(&session).unwrap(),
+        ton.clone(),
+        browser.clone(),
+    ).unwrap().with_mock_node(node);
+    let transcript = browser.resume(&mut engine).await.unwrap();
+    let resumed: Vec<String> = transcript.entries.iter().filter_map(|entry| match entry {
+        TranscriptEntry::ShowAction { description } => Some(description.clone()),
+        _ => None,
+    }).take(shown.len()).collect();
+    assert_eq!(shown, resumed);
+    assert!(transcript.logs().contains(&"Test Goto Action"));
+
+    let mut session: DebotSession = serde_json::from_str(&session).unwrap();
+    session.version = 0;
+    assert!(DEngine::restore(session, ton, browser).is_err());
+}
+
+#[tokio::test(core_threads = 2)]
+async fn test_debot_policy() {
+    let ton = Arc::new(ClientContext::new(crate::ClientConfig::default()).unwrap());
+    let node = Arc::new(MockNode::new());
+    let data = init_debot_on_mock_node(ton.clone(), &node).await;
+
+    let browser = Arc::new(HeadlessBrowser::new(Default::default()));
+    let mut engine = DEngine::new_with_client(
+        data.debot_addr.clone(),
+        Some(data.abi.clone()),
+        ton.clone(),
+        browser.clone(),
+    ).with_mock_node(node).with_policy(DebotPolicy {
+        gas_limit: Some(100),
+        ..Default::default()
+    });
+    assert!(browser.run(&mut engine).await.is_err());
+    let err = engine.take_policy_violation().expect("gas limit must be violated");
+    assert_eq!(err.code, ErrorCode::DebotGasLimitExceeded as u32);
+    assert!(engine.take_policy_violation().is_none());
+
+    let guard = super::policy::PolicyGuard::new(ton.clone(), DebotPolicy {
+        max_interface_calls: Some(2),
+        url_allow_list: Some(vec!["https://api.example.com/".to_owned()]),
+        url_deny_list: Some(vec!["https://api.example.com/admin".to_owned()]),
+        max_requests_per_minute: Some(2),
+        ..Default::default()
+    });
+    assert_eq!(
+        guard.check_request("https://example.org/").unwrap_err().code,
+        ErrorCode::DebotUrlNotAllowed as u32,
+    );
+    assert_eq!(
+        guard.check_request("https://api.example.com/admin/keys").unwrap_err().code,
+        ErrorCode::DebotUrlNotAllowed as u32,
+    );
+    // URLs are compared by scheme, host, port and path segments
+    for url in &[
+        "http://api.example.com/price",
+        "https://api.example.com.example.org/",
+        "https://api.example.com@example.org/",
+        "https://api.example.com/price/../admin",
+        "api.example.com/price",
+    ] {
+        assert_eq!(
+            guard.check_request(url).unwrap_err().code,
+            ErrorCode::DebotUrlNotAllowed as u32,
+        );
+    }
+    guard.check_request("https://API.example.com:443/price").unwrap();
+    guard.check_request("https://api.example.com/administrator").unwrap();
+    assert_eq!(
+        guard.check_request("https://api.example.com/price").unwrap_err().code,
+        ErrorCode::DebotRateLimitExceeded as u32,
+    );
+
+    guard.count_interface_call().unwrap();
+    guard.count_interface_call().unwrap();
+    assert_eq!(
+        guard.count_interface_call().unwrap_err().code,
+        ErrorCode::DebotInterfaceCallsLimitExceeded as u32,
+    );
+    guard.begin_action();
+    guard.count_interface_call().unwrap();
+
+    // the first violation is reported
+    assert_eq!(guard.take_violation().unwrap().code, ErrorCode::DebotUrlNotAllowed as u32);
+    assert!(guard.take_violation().is_none());
+
+    // time limit is checked after the call of a looping contract
+    // PUSHPOW2 19, REPEATEND: a million iterations of an empty loop
+    let code = ton_types::BuilderData::with_raw(vec![0x83, 0x13, 0xE5], 24)
+        .unwrap()
+        .into_cell()
+        .unwrap();
+    let account = crate::abi::encode_account(ton.clone(), crate::abi::ParamsOfEncodeAccount {
+        state_init: crate::abi::StateInitSource::StateInit {
+            code: crate::boc::internal::serialize_cell_to_base64(&code, "code").unwrap(),
+            data: "te6ccgEBAQEAAgAAAA==".to_owned(),
+            library: None,
+        },
+        balance: Some(1_000_000_000),
+        ..Default::default()
+    }).await.unwrap();
+    let addr = format!("0:{}", account.id);
+    let message = super::helpers::build_internal_message(&addr, &addr, Default::default()).unwrap();
+    let guard = super::policy::PolicyGuard::new(ton.clone(), DebotPolicy {
+        time_limit: Some(1),
+        ..Default::default()
+    });
+    let started = guard.now();
+    let result = crate::tvm::run_tvm(ton.clone(), ParamsOfRunTvm {
+        account: account.account,
+        message,
+        execution_options: guard.execution_options(),
+        ..Default::default()
+    }).await;
+    let error = guard.check_run(started, result.as_ref().err()).unwrap_err();
+    assert_eq!(error.code, ErrorCode::DebotTimeLimitExceeded as u32);
+    assert_eq!(guard.take_violation().unwrap().code, ErrorCode::DebotTimeLimitExceeded as u32);
+
+    let guard = super::policy::PolicyGuard::new(ton.clone(), DebotPolicy {
+        time_limit: Some(60_000),
+        ..Default::default()
+    });
+    guard.check_run(guard.now(), None).unwrap();
+    assert!(guard.take_violation().is_none());
+}
+
+#[tokio::test(core_threads = 2)]
+async fn test_debot_activities() {
+    let ton = Arc::new(ClientContext::new(crate::ClientConfig::default()).unwrap());
+    let keys = crate::crypto::generate_random_sign_keys(ton.clone()).unwrap();
+    let target_abi = TestClient::abi(TEST_DEBOT_TARGET, Some(2));
+
+    let deploy = crate::abi::encode_message(ton.clone(), ParamsOfEncodeMessage {
+        abi: target_abi.clone(),
+        deploy_set: Some(DeploySet {
+            tvc: TestClient::tvc(TEST_DEBOT_TARGET, Some(2)),
+            ..Default::default()
+        }),
+        signer: Signer::Keys { keys: keys.clone() },
+        processing_try_index: None,
+        address: None,
+        call_set: CallSet::some_with_function_and_input("constructor", json!({})),
+    }).await.unwrap();
+    assert!(super::calltype::has_state_init(&deploy.message));
+    assert!(super::calltype::decode_call(ton.clone(), &[], &deploy.message).await.is_none());
+    let call = super::calltype::decode_call(ton.clone(), &[target_abi], &deploy.message)
+        .await
+        .unwrap();
+    assert_eq!(call.function_name, "constructor");
+    assert_eq!(call.dst, deploy.address);
+
+    let script: DebotScript = serde_json::from_value(json!({ "steps": [
+        { "type": "Approve", "approved": false },
+        { "type": "Approve", "approved": true },
+    ]})).unwrap();
+    let browser = Arc::new(HeadlessBrowser::new(script));
+    let signing_box = crate::crypto::get_signing_box(ton.clone(), keys.clone()).await.unwrap();
+    let sdk = super::sdk_interface::SdkInterface::new(ton.clone(), browser.clone());
+    let args = json!({
+        "answerId": "1",
+        "boxHandle": signing_box.handle.0.to_string(),
+        "hash": "0x01",
+    });
+    assert!(sdk.call("signHash", &args).await.is_err());
+    let (answer_id, result) = sdk.call("signHash", &args).await.unwrap();
+    assert_eq!(answer_id, 1);
+    assert!(result["signature"].as_str().is_some());
+
+    let transcript = browser.transcript().await;
+    let activities: Vec<_> = transcript.entries.iter().filter_map(|entry| match entry {
+        TranscriptEntry::Approve { activity, approved } => Some((activity.clone(), *approved)),
+        _ => None,
+    }).collect();
+    assert_eq!(activities.len(), 2);
+    match &activities[1] {
+        (DebotActivity::SignHash { signing_box_handle, signkey, hash }, true) => {
+            assert_eq!(*signing_box_handle, signing_box.handle.0);
+            assert_eq!(signkey, &keys.public);
+            assert_eq!(hash, &format!("{:064x}", 1));
+        },
+        other => panic!("unexpected activity {:?}", other),
+    }
+}
+
+#[tokio::test(core_threads = 2)]
+async fn test_debot_register_interface() {
+    let client = Arc::new(TestClient::new());
+    let params = |interface_id: &str| ParamsOfRegisterInterface {
+        debot_handle: Default::default(),
+        interface_id: interface_id.to_owned(),
+        abi: Abi::Json(ECHO_ABI.to_owned()),
+    };
+
+    let err = client.request_async_callback::<_, (), _, _, _>(
+        "debot.register_interface",
+        params("0x1234"),
+        TestClient::default_callback,
+    ).await.unwrap_err();
+    assert_eq!(err.code, ErrorCode::DebotInvalidInterface as u32);
+
+    let err = client.request_async_callback::<_, (), _, _, _>(
+        "debot.register_interface",
+        params("0xf6927c0d4bdb69e1b52d27f018d156ff04152f00558042ff674f0fec32e4369d"),
+        TestClient::default_callback,
+    ).await.unwrap_err();
+    assert_eq!(err.code, ErrorCode::DebotInvalidHandle as u32);
+
+    // DeBot calls Echo interface implemented by the application
+    let ton = Arc::new(ClientContext::new(crate::ClientConfig::default()).unwrap());
+    let node = Arc::new(MockNode::new());
+    let data = init_debot_on_mock_node(ton.clone(), &node).await;
+    let script: DebotScript = serde_json::from_value(json!({
+        "steps": [{ "type": "Log", "text": "Debot Tests" }]
+    })).unwrap();
+    let browser = Arc::new(HeadlessBrowser::new(script));
+    let mut engine = DEngine::new_with_client(
+        data.debot_addr.clone(),
+        Some(data.abi.clone()),
+        ton.clone(),
+        browser.clone(),
+    ).with_mock_node(node).with_event_log();
+    browser.run(&mut engine).await.unwrap();
+    let session = engine.save();
+    // the same action as in `test_debot_interface_call`
+    let action = session.actions[7].clone();
+
+    let browser_callback = |params: Value, response_type: ResponseType| async move {
+        if let ResponseType::AppRequest = response_type {
+            panic!("unexpected browser request {}", params);
+        }
+    };
+    let handle: RegisteredDebot = client.request_async_callback(
+        "debot.restore_session",
+        ParamsOfRestoreSession {
+            session: serde_json::to_string(&session).unwrap(),
+            ..Default::default()
+        },
+        browser_callback,
+    ).await.unwrap();
+
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let (client_copy, calls_copy) = (client.clone(), calls.clone());
+    let interface_callback = move |params: Value, response_type: ResponseType| {
+        let client = client_copy.clone();
+        let calls = calls_copy.clone();
+        async move {
+            match response_type {
+                ResponseType::AppRequest => {
+                    tokio::spawn(async move {
+                        let request: ParamsOfAppRequest = serde_json::from_value(params).unwrap();
+                        let call: ParamsOfAppDebotInterface =
+                            serde_json::from_value(request.request_data).unwrap();
+                        let ParamsOfAppDebotInterface::Call { input, .. } = &call;
+                        let output = json!({ "response": input["request"] });
+                        calls.lock().await.push(call);
+                        client.resolve_app_request(
+                            request.app_request_id,
+                            ResultOfAppDebotInterface::Call { output },
+                        ).await;
+                    });
+                },
+                _ => panic!("unexpected interface notification {}", params),
+            }
+        }
+    };
+    let _: () = client.request_async_callback(
+        "debot.register_interface",
+        ParamsOfRegisterInterface {
+            debot_handle: handle.debot_handle.clone(),
+            interface_id: SUPPORTED_INTERFACES[0].to_owned(),
+            abi: Abi::Json(ECHO_ABI.to_owned()),
+        },
+        interface_callback,
+    ).await.unwrap();
+
+    let _: () = client.request_async(
+        "debot.execute",
+        ParamsOfExecute { debot_handle: handle.debot_handle.clone(), action },
+    ).await.unwrap();
+
+    let calls = calls.lock().await;
+    assert_eq!(calls.len(), 1);
+    let ParamsOfAppDebotInterface::Call { function_name, input } = &calls[0];
+    assert_eq!(function_name, "echo");
+    assert!(input.get("answerId").is_none());
+    assert!(input["request"].is_string());
+
+    // returned output is delivered to DeBot in the answer message
+    let log: ResultOfGetEventLog = client.request_async(
+        "debot.get_event_log",
+        ParamsOfGetEventLog { debot_handle: handle.debot_handle.clone() },
+    ).await.unwrap();
+    let log: DebotEventLog = serde_json::from_str(&log.event_log).unwrap();
+    let call_index = log.events.iter().position(|event| matches!(
+        event,
+        DebotEvent::InterfaceCall { interface_id, .. } if interface_id == SUPPORTED_INTERFACES[0]
+    )).unwrap();
+    let answer = log.events[call_index..].iter().find_map(|event| match event {
+        DebotEvent::InternalMessage { message, error: None, .. } => Some(message.clone()),
+        _ => None,
+    }).expect("answer must be delivered to DeBot");
+    let decoded = crate::abi::decode_message(ton, crate::abi::ParamsOfDecodeMessage {
+        abi: Abi::Json(data.abi),
+        message: answer,
+    }).await.unwrap();
+    assert_eq!(decoded.name, "echo");
+    assert_eq!(decoded.value.unwrap()["response"], input["request"]);
+}
+
+struct ScriptedUi {
+    inputs: Mutex<VecDeque<String>>,
+    printed: Mutex<Vec<String>>,
+    selected: usize,
+}
+
+#[async_trait::async_trait]
+impl UiCallbacks for ScriptedUi {
+    async fn print(&self, message: &str) {
+        self.printed.lock().await.push(message.to_owned());
+    }
+
+    async fn input(&self, _prompt: &str, _multiline: bool) -> Result<String, String> {
+        self.inputs.lock().await.pop_front().ok_or("no more input".to_owned())
+    }
+
+    async fn confirm(&self, _prompt: &str) -> Result<bool, String> {
+        Ok(true)
+    }
+
+    async fn select(&self, _title: &str, _description: &str, _items: &[MenuItem]) -> Result<usize, String> {
+        Ok(self.selected)
+    }
+
+    async fn get_signing_box(&self, _prompt: &str, _keys: &[String]) -> Result<crate::crypto::SigningBoxHandle, String> {
+        Err("not supported".to_owned())
+    }
+}
+
+#[tokio::test(core_threads = 2)]
+async fn test_debot_ui_interfaces() {
+    assert_eq!(super::ui_browser::parse_amount("1.5", 9).unwrap().to_string(), "1500000000");
+    assert_eq!(super::ui_browser::parse_amount("7", 0).unwrap().to_string(), "7");
+    assert!(super::ui_browser::parse_amount("1.5", 0).is_err());
+    assert!(super::ui_browser::parse_amount("1,5", 9).is_err());
+    assert!(super::ui_browser::parse_amount(".", 9).is_err());
+    let amount = super::ui_browser::parse_amount("0.05", 9).unwrap();
+    assert_eq!(super::ui_browser::format_amount(&amount, 9), "0.05");
+
+    let ui = Arc::new(ScriptedUi {
+        inputs: Mutex::new(vec!["abc".to_owned(), "3".to_owned(), "1.5".to_owned()].into()),
+        printed: Mutex::new(vec![]),
+        selected: 1,
+    });
+    let prompt = hex::encode("Enter amount");
+    let amount_input = AmountInputInterface::new(ui.clone());
+    let (answer_id, result) = amount_input.call("get", &json!({
+        "answerId": "1",
+        "prompt": prompt,
+        "decimals": "9",
+        "min": "0",
+        "max": "2000000000",
+    })).await.unwrap();
+    assert_eq!(answer_id, 1);
+    assert_eq!(result["value"], "1500000000");
+    assert_eq!(ui.printed.lock().await.len(), 2);
+    assert_eq!(ui.printed.lock().await[1], "amount must be between 0 and 2");
+
+    let address = format!("0:{}", "1".repeat(64));
+    *ui.inputs.lock().await = vec!["bad".to_owned(), address.clone()].into();
+    let (_, result) = AddressInputInterface::new(ui.clone()).call("get", &json!({
+        "answerId": "2",
+        "prompt": prompt,
+    })).await.unwrap();
+    assert_eq!(result["value"], address);
+
+    *ui.inputs.lock().await = vec!["a".to_owned(), "b".to_owned(), "c".to_owned(), "1".to_owned()].into();
+    let err = NumberInputInterface::new(ui.clone()).call("get", &json!({
+        "answerId": "3",
+        "prompt": prompt,
+        "min": "-5",
+        "max": "5",
+    })).await.unwrap_err();
+    assert!(err.contains("no valid input"));
+
+    let item = |title: &str, handler_id: u32| json!({
+        "title": hex::encode(title),
+        "description": "",
+        "handlerId": handler_id.to_string(),
+    });
+    let (answer_id, result) = MenuInterface::new(ui.clone()).call("select", &json!({
+        "title": hex::encode("Menu"),
+        "description": "",
+        "items": [item("First", 10), item("Second", 20)],
+    })).await.unwrap();
+    assert_eq!(answer_id, 20);
+    assert_eq!(result["index"], 1);
+
+    let (answer_id, _) = TerminalInterface::new(ui.clone()).call("print", &json!({
+        "answerId": "0",
+        "message": hex::encode("Hello"),
+    })).await.unwrap();
+    assert_eq!(answer_id, 0);
+    assert_eq!(ui.printed.lock().await.last().unwrap(), "Hello");
+}
+
+#[tokio::test(core_threads = 2)]
+async fn test_debot_inspect() {
+    use ton_block::Serializable;
+    use ton_types::BuilderData;
+
+    let contract = ton_block::MsgAddressInt::with_standart(None, 0, [0x11; 32].into()).unwrap();
+    let mut sdk_id = [0u8; 32];
+    sdk_id.copy_from_slice(
+        &hex::decode("8fc6454f90072c9f1f6d3313ae1608f64f4a0660c6ae9f42c68b6a79e2a1bc4b").unwrap()
+    );
+    let sdk = ton_block::MsgAddressInt::with_standart(None, DEBOT_WC, sdk_id.into()).unwrap();
+    let url = b"https://example.com/api ";
+    let mut code = BuilderData::with_raw(url.to_vec(), url.len() * 8).unwrap();
+    code.append_reference_cell(contract.serialize().unwrap());
+    code.append_reference_cell(sdk.serialize().unwrap());
+    let result = super::inspect::inspect(
+        &code.into_cell().unwrap(),
+        &[],
+        &HashMap::new(),
+        Some(r#"{"ABI version": 2, "header": ["pubkey", "time"], "functions": []}"#),
+    );
+    assert_eq!(result.contracts, vec![format!("0:{}", "11".repeat(32))]);
+    assert_eq!(result.urls, vec!["https://example.com/api"]);
+    assert_eq!(result.interfaces.len(), 1);
+    assert_eq!(result.interfaces[0].name.as_deref(), Some("Sdk"));
+    assert!(!result.interfaces[0].declared);
+    assert!(!result.sign_hash);
+    assert_eq!(result.target_abi_headers, Some(vec!["pubkey".to_owned(), "time".to_owned()]));
+
+    let ton = Arc::new(ClientContext::new(crate::ClientConfig::default()).unwrap());
+    let node = Arc::new(MockNode::new());
+    let data = init_debot_on_mock_node(ton.clone(), &node).await;
+    let browser = Arc::new(HeadlessBrowser::new(DebotScript::new(vec![])));
+    let mut engine = DEngine::new_with_client(
+        data.debot_addr.clone(),
+        Some(data.abi.clone()),
+        ton.clone(),
+        browser,
+    ).with_mock_node(node.clone());
+    engine.init().await.unwrap();
+    let result = engine.inspect().unwrap();
+    assert_eq!(result.target_abi_headers, Some(vec!["time".to_owned(), "expire".to_owned()]));
+
+    let state = node.account(&data.debot_addr).await.unwrap();
+    let result = DEngine::inspect_state(ton, data.debot_addr.clone(), state).await.unwrap();
+    assert_eq!(result.target_abi_headers, Some(vec!["time".to_owned(), "expire".to_owned()]));
+}
+
+#[tokio::test(core_threads = 2)]
+async fn test_debot_locale() {
+    let locale = |tag: &str| super::locale::Locale::new(Some(tag.to_owned()));
+    let amount = num_bigint::BigInt::from(1234567500000000u64);
+    assert_eq!(locale("en-US").format_amount(&amount, 9), "1,234,567.5");
+    assert_eq!(locale("ru").format_amount(&amount, 9), "1\u{a0}234\u{a0}567,5");
+    assert_eq!(locale("de-DE").format_amount(&amount, 9), "1.234.567,5");
+    assert_eq!(super::locale::Locale::new(None).format_amount(&amount, 9), "1234567.5");
+    assert_eq!(locale("de").parse_amount("1,5", 9).unwrap().to_string(), "1500000000");
+    assert_eq!(locale("fr").parse_amount("1 000,5", 9).unwrap().to_string(), "1000500000000");
+    assert!(locale("en").parse_amount("1,5", 9).is_err());
+    assert_eq!(locale("en-US").format_date(1634515200), "10/18/2021 00:00");
+    assert_eq!(locale("de").format_date(1634515200), "18.10.2021 00:00");
+    assert_eq!(super::locale::Locale::new(None).format_date(1634515200), "2021-10-18 00:00:00");
+
+    let translation = |language: &str, name: &str| super::info::DInfoTranslation {
+        language: Some(language.to_owned()),
+        name: Some(name.to_owned()),
+        ..Default::default()
+    };
+    let info = super::info::DInfo {
+        name: Some("Wallet".to_owned()),
+        caption: Some("Simple wallet".to_owned()),
+        language: Some("en".to_owned()),
+        translations: vec![translation("fr", "Portefeuille"), translation("ru", "Кошелёк")],
+        ..Default::default()
+    };
+    let mut localized = info.clone();
+    localized.localize(&locale("fr-CA"));
+    assert_eq!(localized.name.as_deref(), Some("Portefeuille"));
+    assert_eq!(localized.caption.as_deref(), Some("Simple wallet"));
+    assert_eq!(localized.language.as_deref(), Some("fr"));
+    for tag in &["de", "en-GB"] {
+        let mut localized = info.clone();
+        localized.localize(&locale(tag));
+        assert_eq!(localized.name.as_deref(), Some("Wallet"));
+    }
+
+    let ton = Arc::new(ClientContext::new(crate::ClientConfig::default()).unwrap());
+    let browser = Arc::new(HeadlessBrowser::new(DebotScript::new(vec![])));
+    let sdk = super::sdk_interface::SdkInterface::new(ton, browser)
+        .with_locale(Some("en-US".to_owned()));
+    let (_, result) = sdk.call("getLocale", &json!({ "answerId": "1" })).await.unwrap();
+    assert_eq!(result["locale"], hex::encode("en-US"));
+    let (_, result) = sdk.call("formatAmount", &json!({
+        "answerId": "1",
+        "amount": "2500000000",
+        "decimals": "9",
+    })).await.unwrap();
+    assert_eq!(result["str"], hex::encode("2.5"));
+
+    let ui = Arc::new(ScriptedUi {
+        inputs: Mutex::new(vec!["5000,5".to_owned(), "1,5".to_owned()].into()),
+        printed: Mutex::new(vec![]),
+        selected: 0,
+    });
+    let (_, result) = AmountInputInterface::new(ui.clone())
+        .with_locale(Some("ru-RU".to_owned()))
+        .call("get", &json!({
+            "answerId": "1",
+            "prompt": hex::encode("Enter amount"),
+            "decimals": "9",
+            "min": "0",
+            "max": "2000000000000",
+        })).await.unwrap();
+    assert_eq!(result["value"], "1500000000");
+    assert_eq!(ui.printed.lock().await[0], "amount must be between 0 and 2\u{a0}000");
+}
+
+#[tokio::test(core_threads = 2)]
+async fn test_debot_event_log() {
+    let ton = Arc::new(ClientContext::new(crate::ClientConfig::default()).unwrap());
+    let node = Arc::new(MockNode::new());
+    let data = init_debot_on_mock_node(ton.clone(), &node).await;
+
+    let script: DebotScript = serde_json::from_value(json!({
+        "steps": [{ "type": "Log", "text": "Debot Tests" }]
+    })).unwrap();
+    let browser = Arc::new(HeadlessBrowser::new(script));
+    let policy = DebotPolicy { max_interface_calls: Some(100), ..Default::default() };
+    let mut engine = DEngine::new_with_client(
+        data.debot_addr.clone(),
+        Some(data.abi.clone()),
+        ton.clone(),
+        browser.clone(),
+    ).with_mock_node(node.clone()).with_policy(policy.clone()).with_event_log();
+    engine.add_abi(TestClient::abi(TEST_DEBOT_TARGET, Some(2)));
+    browser.run(&mut engine).await.unwrap();
+
+    let log = engine.event_log().unwrap();
+    assert_eq!(log.address, data.debot_addr);
+    assert_eq!(log.policy, policy);
+    assert_eq!(log.abis.len(), 1);
+    assert!(log.accounts.iter().any(|account| account.address == data.debot_addr));
+    assert!(matches!(log.events.first(), Some(DebotEvent::Start)));
+    assert!(log.events.iter().any(|event| matches!(event, DebotEvent::RunTvm { .. })));
+    assert!(log.events.iter().any(|event| matches!(event, DebotEvent::Switch { .. })));
+    assert!(log.events.iter().any(|event| matches!(event, DebotEvent::Log { text } if text == "Debot Tests")));
+
+    let exported = serde_json::to_string(&log).unwrap();
+    let imported: DebotEventLog = serde_json::from_str(&exported).unwrap();
+    let replayed = imported.replay(ton.clone(), None).await.unwrap();
+    assert_eq!(replayed.policy, policy);
+    assert_eq!(replayed.abis.len(), 1);
+    assert_eq!(log.first_difference(&replayed), None);
+
+    let mut changed = imported.clone();
+    changed.events.push(DebotEvent::Log { text: "Unexpected".to_owned() });
+    assert_eq!(log.first_difference(&changed), Some(log.events.len()));
+
+    // messages are compared without creation time, but with body
+    let send = |body: u8, at: u32| {
+        let boc = super::helpers::build_internal_message(
+            &data.debot_addr,
+            &data.debot_addr,
+            ton_types::SliceData::from_raw(vec![body], 8),
+        ).unwrap();
+        let mut message: ton_block::Message =
+            crate::boc::internal::deserialize_object_from_base64(&boc, "message").unwrap().object;
+        message.set_at_and_lt(at, at as u64);
+        let message = crate::boc::internal::serialize_object_to_base64(&message, "message").unwrap();
+        DebotEvent::Send { dst: data.debot_addr.clone(), message }
+    };
+    let mut sent = imported.clone();
+    sent.events.push(send(1, 1));
+    let mut resent = imported.clone();
+    resent.events.push(send(1, 2));
+    assert_eq!(sent.first_difference(&resent), None);
+    resent.events.pop();
+    resent.events.push(send(2, 1));
+    assert_eq!(sent.first_difference(&resent), Some(log.events.len()));
+}
+
+async fn deploy_debot_on_mock_node(
+    ton: TonClient,
+    node: &MockNode,
+    keys: &KeyPair,
+    name: &str,
+    input: Value,
+) -> String {
+    let abi = TestClient::abi(name, Some(2));
+    let deploy = crate::abi::encode_message(ton.clone(), ParamsOfEncodeMessage {
+        abi: abi.clone(),
+        deploy_set: DeploySet::some_with_tvc(TestClient::tvc(name, Some(2))),
+        signer: Signer::Keys { keys: keys.clone() },
+        processing_try_index: None,
+        address: None,
+        call_set: CallSet::some_with_function_and_input("constructor", input),
+    }).await.unwrap();
+    node.process_message(ton.clone(), deploy.message, Some(abi.clone())).await.unwrap();
+
+    let set_abi = crate::abi::encode_message(ton.clone(), ParamsOfEncodeMessage {
+        abi: abi.clone(),
+        deploy_set: None,
+        signer: Signer::None,
+        processing_try_index: None,
+        address: Some(deploy.address.clone()),
+        call_set: CallSet::some_with_function_and_input("setAbi", json!({
+            "debotAbi": hex::encode(&abi.json_string().unwrap().as_bytes())
+        })),
+    }).await.unwrap();
+    node.process_message(ton, set_abi.message, Some(abi)).await.unwrap();
+    deploy.address
+}
+
+#[tokio::test(core_threads = 2)]
+async fn test_debot_invoked_by_engine() {
+    let ton = Arc::new(ClientContext::new(crate::ClientConfig::default()).unwrap());
+    let node = Arc::new(MockNode::new());
+    let keys = crate::crypto::generate_random_sign_keys(ton.clone()).unwrap();
+    let debot_b = deploy_debot_on_mock_node(ton.clone(), &node, &keys, TEST_DEBOTB, json!({})).await;
+    let debot_a = deploy_debot_on_mock_node(
+        ton.clone(), &node, &keys, TEST_DEBOTA, json!({ "targetAddr": debot_b }),
+    ).await;
+
+    let run = |approved: bool| {
+        let ton = ton.clone();
+        let node = node.clone();
+        let debot_a = debot_a.clone();
+        let debot_b = debot_b.clone();
+        async move {
+            let ui = Arc::new(ScriptedUi {
+                inputs: Mutex::new(VecDeque::new()),
+                printed: Mutex::new(vec![]),
+                selected: 0,
+            });
+            let script: DebotScript = serde_json::from_value(json!({
+                "steps": [{ "type": "Approve", "approved": approved, "dst": debot_b }]
+            })).unwrap();
+            let browser = Arc::new(HeadlessBrowser::new(script));
+            let mut engine = DEngine::new_with_client(debot_a, None, ton, browser)
+                .with_mock_node(node)
+                .with_ui(ui.clone())
+                .with_invoked_debots();
+            let result = engine.start().await;
+            let printed = ui.printed.lock().await.clone();
+            (result, printed)
+        }
+    };
+
+    let (result, printed) = run(true).await;
+    result.unwrap();
+    assert_eq!(printed, vec![
+        "Invoking Debot B".to_owned(),
+        "DebotB receives question: What is your name?".to_owned(),
+        "DebotA receives answer: My name is DebotB".to_owned(),
+    ]);
+
+    // rejected invocation is not delivered to the invoked DeBot
+    let (_, printed) = run(false).await;
+    assert!(!printed.contains(&"DebotB receives question: What is your name?".to_owned()));
+}
+
+#[tokio::test(core_threads = 2)]
+async fn test_debot_session_invoked() {
+    let ton = Arc::new(ClientContext::new(crate::ClientConfig::default()).unwrap());
+    let node = Arc::new(MockNode::new());
+    let keys = crate::crypto::generate_random_sign_keys(ton.clone()).unwrap();
+    let debot_b = deploy_debot_on_mock_node(ton.clone(), &node, &keys, TEST_DEBOTB, json!({})).await;
+    let debot_a = deploy_debot_on_mock_node(
+        ton.clone(), &node, &keys, TEST_DEBOTA, json!({ "targetAddr": debot_b }),
+    ).await;
+
+    let ui = Arc::new(ScriptedUi {
+        inputs: Mutex::new(VecDeque::new()),
+        printed: Mutex::new(vec![]),
+        selected: 0,
+    });
+    let script: DebotScript = serde_json::from_value(json!({
+        "steps": [{ "type": "Approve", "approved": true, "dst": debot_b }]
+    })).unwrap();
+    let browser = Arc::new(HeadlessBrowser::new(script));
+    let mut engine = DEngine::new_with_client(debot_a.clone(), None, ton.clone(), browser.clone())
+        .with_mock_node(node.clone())
+        .with_ui(ui)
+        .with_invoked_debots()
+        .with_event_log();
+    engine.start().await.unwrap();
+
+    let session = engine.save();
+    assert!(session.manage_invoked_debots);
+    assert_eq!(session.invoked.len(), 1);
+    assert_eq!(session.invoked[0].address, debot_b);
+    assert_eq!(session.invoked[0].call_stack, vec![debot_a.clone()]);
+    assert!(session.invoked[0].event_log.is_none());
+    let events = session.event_log.as_ref().unwrap().events.len();
+    assert!(events > 0);
+
+    let session: DebotSession = serde_json::from_str(&serde_json::to_string(&session).unwrap()).unwrap();
+    let restored = DEngine::restore(session, ton, browser).unwrap().with_mock_node(node);
+    assert_eq!(restored.event_log().unwrap().events.len(), events);
+    let session = restored.save();
+    assert!(session.manage_invoked_debots);
+    assert_eq!(session.invoked.len(), 1);
+    assert_eq!(session.invoked[0].address, debot_b);
+}
+
 fn build_info(abi: String, n: u32, interfaces: Vec<String>) -> DebotInfo {
     let name = format!("TestDeBot{}", n);
     DebotInfo {
@@ -1450,6 +2304,7 @@ fn build_info(abi: String, n: u32, interfaces: Vec<String>) -> DebotInfo {
         dabi: Some(abi),
         icon: Some(format!("")),
         interfaces,
+        translations: vec![],
     }
 }
 
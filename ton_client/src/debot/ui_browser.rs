@@ -0,0 +1,99 @@
+use crate::crypto::SigningBoxHandle;
+use num_bigint::BigInt;
+use std::str::FromStr;
+
+/// Number of attempts given to the user to enter a valid value.
+pub(super) const MAX_INPUT_ATTEMPTS: usize = 3;
+
+/// Item of the menu shown by Menu interface.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct MenuItem {
+    pub title: String,
+    pub description: String,
+    /// DeBot function called when the item is selected.
+    pub handler_id: u32,
+}
+
+/// User interface of Debot Browser used by the standard DeBot interfaces
+/// implemented in SDK: Terminal, Menu, AddressInput, AmountInput,
+/// ConfirmInput, NumberInput and SigningBoxInput.
+///
+/// Interfaces parse and validate DeBot arguments and user input,
+/// the browser only shows prompts and returns what the user entered.
+#[async_trait::async_trait]
+pub trait UiCallbacks {
+    /// Prints text message to user.
+    async fn print(&self, message: &str);
+    /// Requests text input from user.
+    async fn input(&self, prompt: &str, multiline: bool) -> Result<String, String>;
+    /// Asks user a yes/no question.
+    async fn confirm(&self, prompt: &str) -> Result<bool, String>;
+    /// Asks user to select one of the menu items. Returns index of the selected item.
+    async fn select(
+        &self,
+        title: &str,
+        description: &str,
+        items: &[MenuItem],
+    ) -> Result<usize, String>;
+    /// Requests signing box from user. If `possible_keys` is not empty,
+    /// the box public key must be one of them. Keys are encoded in hex.
+    async fn get_signing_box(
+        &self,
+        prompt: &str,
+        possible_keys: &[String],
+    ) -> Result<SigningBoxHandle, String>;
+}
+
+/// Requests input until `parse` accepts it. Parsing errors are printed to user.
+pub(super) async fn input_valid<T>(
+    ui: &(dyn UiCallbacks + Send + Sync),
+    prompt: &str,
+    parse: impl Fn(&str) -> Result<T, String>,
+) -> Result<T, String> {
+    let mut error = String::new();
+    for _ in 0..MAX_INPUT_ATTEMPTS {
+        let value = ui.input(prompt, false).await?;
+        match parse(value.trim()) {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                ui.print(&e).await;
+                error = e;
+            }
+        }
+    }
+    Err(format!("no valid input after {} attempts: {}", MAX_INPUT_ATTEMPTS, error))
+}
+
+/// Parses decimal amount, e.g. `1.5`, into integer number of minimal units.
+pub(super) fn parse_amount(value: &str, decimals: u8) -> Result<BigInt, String> {
+    let (int, frac) = match value.find('.') {
+        Some(pos) => (&value[..pos], &value[pos + 1..]),
+        None => (value, ""),
+    };
+    let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if (int.is_empty() && frac.is_empty()) || !is_digits(int) || !is_digits(frac) {
+        return Err(format!("invalid amount \"{}\"", value));
+    }
+    if frac.len() > decimals as usize {
+        return Err(format!("amount can have at most {} decimal places", decimals));
+    }
+    let digits = format!("{}{}{}", int, frac, "0".repeat(decimals as usize - frac.len()));
+    BigInt::from_str(&digits).map_err(|e| format!("invalid amount \"{}\": {}", value, e))
+}
+
+/// Formats integer number of minimal units as decimal amount.
+pub(super) fn format_amount(value: &BigInt, decimals: u8) -> String {
+    let digits = value.to_string();
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+    let digits = format!("{:0>width$}", digits, width = decimals + 1);
+    let (int, frac) = digits.split_at(digits.len() - decimals);
+    let frac = frac.trim_end_matches('0');
+    if frac.is_empty() {
+        int.to_owned()
+    } else {
+        format!("{}.{}", int, frac)
+    }
+}
@@ -12,9 +12,11 @@
  *
  */
 
-use crate::client::{AppObject, ClientContext, Error};
-use crate::error::ClientResult;
-use crate::crypto::{EncryptionBoxInfo, RegisteredEncryptionBox, RegisteredSigningBox, SigningBox};
+use crate::client::{AppObject, ClientContext, Error, ErrorCode};
+use crate::error::{ClientError, ClientResult};
+use crate::crypto::{
+    EncryptionBoxInfo, RegisteredEncryptionBox, RegisteredSigningBox, SigningBox, SigningBoxContext,
+};
 use crate::crypto::boxes::encryption_box::EncryptionBox;
 
 /// Signing box callbacks.
@@ -28,6 +30,27 @@ pub enum ParamsOfAppSigningBox {
         /// Data to sign encoded as base64
         unsigned: String,
     },
+    /// Sign several data items at once.
+    ///
+    /// If the application does not support this request, i.e. answers it with
+    /// a result which can not be parsed, the items are requested to be signed
+    /// one by one with `Sign`. Errors returned by the application fail the call.
+    SignBatch {
+        /// Data items to sign encoded as base64
+        unsigned: Vec<String>,
+    },
+    /// Sign message data. Used when the signing box is used to sign a message
+    /// with `abi.encode_message` and `processing.process_message`.
+    ///
+    /// If the application does not support this request, i.e. answers it with
+    /// a result which can not be parsed, the data is requested to be signed
+    /// with `Sign`. Errors returned by the application fail the call.
+    SignWithContext {
+        /// Data to sign encoded as base64
+        unsigned: String,
+        /// Description of the message being signed
+        context: SigningBoxContext,
+    },
 }
 
 /// Returning values from signing box callbacks.
@@ -44,12 +67,29 @@ pub enum ResultOfAppSigningBox {
         /// Data signature encoded as hex
         signature: String,
     },
+    /// Result of signing several data items
+    SignBatch {
+        /// Data signatures encoded as hex in the order of items
+        signatures: Vec<String>,
+    },
+    /// Signing is rejected by the signing box. Can be returned to any signing request.
+    Reject {
+        /// Rejection reason
+        reason: String,
+    },
 }
 
 struct ExternalSigningBox {
     app_object: AppObject<ParamsOfAppSigningBox, ResultOfAppSigningBox>,
 }
 
+/// Checks if the application does not support the request. Bindings answer
+/// unknown requests with a result of the same type without fields, which can not be parsed.
+/// Other errors, including errors returned by the application, are not treated so.
+fn is_unsupported_request(err: &ClientError) -> bool {
+    err.code == ErrorCode::CanNotParseRequestResult as u32
+}
+
 impl ExternalSigningBox {
     pub fn new(app_object: AppObject<ParamsOfAppSigningBox, ResultOfAppSigningBox>) -> Self {
         Self { app_object }
@@ -79,10 +119,67 @@ impl SigningBox for ExternalSigningBox {
             ResultOfAppSigningBox::Sign { signature: signed } => {
                crate::encoding::hex_decode(&signed)
             },
+            ResultOfAppSigningBox::Reject { reason } => {
+                Err(crate::crypto::Error::signing_box_rejected(reason))
+            },
             _ => Err(Error::unexpected_callback_response(
                 "SigningBoxSign", &response))
         }
     }
+
+    async fn sign_with_context(
+        &self,
+        unsigned: &[u8],
+        context: &SigningBoxContext,
+    ) -> ClientResult<Vec<u8>> {
+        let response = self.app_object.call(ParamsOfAppSigningBox::SignWithContext {
+            unsigned: base64::encode(unsigned),
+            context: context.clone(),
+        }).await;
+
+        match response {
+            Ok(ResultOfAppSigningBox::Sign { signature }) => {
+                crate::encoding::hex_decode(&signature)
+            },
+            Ok(ResultOfAppSigningBox::Reject { reason }) => {
+                Err(crate::crypto::Error::signing_box_rejected(reason))
+            },
+            Ok(response) => Err(Error::unexpected_callback_response(
+                "SigningBoxSignWithContext", &response)),
+            Err(err) if is_unsupported_request(&err) => self.sign(unsigned).await,
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn sign_batch(&self, unsigned: &[Vec<u8>]) -> ClientResult<Vec<Vec<u8>>> {
+        let response = self.app_object.call(ParamsOfAppSigningBox::SignBatch {
+            unsigned: unsigned.iter().map(|data| base64::encode(data)).collect()
+        }).await;
+
+        match response {
+            Ok(ResultOfAppSigningBox::SignBatch { signatures })
+                if signatures.len() == unsigned.len() =>
+            {
+                signatures
+                    .iter()
+                    .map(|signature| crate::encoding::hex_decode(signature))
+                    .collect()
+            },
+            Ok(ResultOfAppSigningBox::Reject { reason }) => {
+                Err(crate::crypto::Error::signing_box_rejected(reason))
+            },
+            Ok(response) => Err(Error::unexpected_callback_response(
+                "SigningBoxSignBatch", &response)),
+            Err(err) if is_unsupported_request(&err) => {
+                let mut signatures = Vec::with_capacity(unsigned.len());
+                for data in unsigned {
+                    signatures.push(self.sign(data).await?);
+                }
+                Ok(signatures)
+            },
+            Err(err) => Err(err),
+        }
+    }
 }
 
 /// Register an application implemented signing box.
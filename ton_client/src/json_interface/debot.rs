@@ -12,11 +12,16 @@
  *
  */
 
+use crate::abi::Abi;
 use crate::client::{AppObject, ClientContext};
 use crate::error::ClientResult;
 use crate::debot::Error;
-use crate::debot::{DAction, DebotAction, BrowserCallbacks, ParamsOfInit, RegisteredDebot, DebotActivity};
+use crate::debot::{
+    DAction, DebotAction, BrowserCallbacks, ParamsOfInit, ParamsOfRestoreSession, RegisteredDebot,
+    DebotActivity, DebotInterface, InterfaceResult, ParamsOfRegisterInterface,
+};
 use crate::crypto::SigningBoxHandle;
+use serde_json::Value;
 
 /// [UNSTABLE](UNSTABLE.md) Returning values from Debot Browser callbacks.
 #[derive(Serialize, Deserialize, Clone, Debug, ApiType)]
@@ -184,6 +189,76 @@ impl DebotBrowserAdapter {
     }
 }
 
+/// [UNSTABLE](UNSTABLE.md) Calls of DeBot interface implemented by the application.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType)]
+#[serde(tag="type")]
+pub enum ParamsOfAppDebotInterface {
+    /// Call of the interface function.
+    Call {
+        /// Function name.
+        function_name: String,
+        /// Function arguments decoded with the interface ABI. `answerId` is omitted.
+        input: Value,
+    },
+}
+
+/// [UNSTABLE](UNSTABLE.md) Returning values from DeBot interface implemented by the application.
+#[derive(Serialize, Deserialize, Clone, Debug, ApiType)]
+#[serde(tag="type")]
+pub enum ResultOfAppDebotInterface {
+    /// Result of the interface function call.
+    Call {
+        /// Function outputs. Encoded into the answer message according to DeBot ABI.
+        output: Value,
+    },
+}
+
+/// Wrapper for DeBot interface implemented by the application.
+pub(crate) struct DebotInterfaceAdapter {
+    id: String,
+    abi: Abi,
+    app_object: AppObject<ParamsOfAppDebotInterface, ResultOfAppDebotInterface>,
+}
+
+impl DebotInterfaceAdapter {
+    pub fn new(
+        id: String,
+        abi: Abi,
+        app_object: AppObject<ParamsOfAppDebotInterface, ResultOfAppDebotInterface>,
+    ) -> Self {
+        Self { id, abi, app_object }
+    }
+}
+
+#[async_trait::async_trait]
+impl DebotInterface for DebotInterfaceAdapter {
+    fn get_id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn get_abi(&self) -> Abi {
+        self.abi.clone()
+    }
+
+    async fn call(&self, func: &str, args: &Value) -> InterfaceResult {
+        let answer_id = crate::debot::decode_answer_id(args)?;
+        let mut input = args.clone();
+        if let Some(input) = input.as_object_mut() {
+            input.remove("answerId");
+        }
+        let response = self.app_object.call(ParamsOfAppDebotInterface::Call {
+            function_name: func.to_owned(),
+            input,
+        })
+        .await
+        .map_err(|e| format!("application failed to handle interface call: {}", e))?;
+
+        match response {
+            ResultOfAppDebotInterface::Call { output } => Ok((answer_id, output)),
+        }
+    }
+}
+
 /// [UNSTABLE](UNSTABLE.md) Creates and instance of DeBot.
 ///
 /// Downloads debot smart contract (code and data) from blockchain and creates
@@ -199,4 +274,41 @@ pub(crate) async fn init(
 ) -> ClientResult<RegisteredDebot> {
     let browser_callbacks = DebotBrowserAdapter::new(app_object);
     crate::debot::init(context, params, browser_callbacks).await
-}
\ No newline at end of file
+}
+
+/// [UNSTABLE](UNSTABLE.md) Restores debot session.
+///
+/// Creates an instance of Debot Engine from the session saved with `save_session`.
+///
+/// # Remarks
+/// Browser Callbacks are called to show the actions of the current debot context
+/// and to send again the interface calls which were not answered before saving.
+#[api_function]
+pub(crate) async fn restore_session(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfRestoreSession,
+    app_object: AppObject<ParamsOfAppDebotBrowser, ResultOfAppDebotBrowser>,
+) -> ClientResult<RegisteredDebot> {
+    let browser_callbacks = DebotBrowserAdapter::new(app_object);
+    crate::debot::restore_session(context, params, browser_callbacks).await
+}
+
+/// [UNSTABLE](UNSTABLE.md) Registers DeBot interface implemented by the application.
+///
+/// DeBot calls to the interface are decoded with the interface ABI and passed
+/// to the application object. Values returned by the application are encoded
+/// into the answer message and sent back to DeBot, so the browser does not
+/// handle raw interface messages.
+///
+/// # Remarks
+/// Interface must be registered before DeBot calls it, e.g. right after `init`.
+#[api_function]
+pub(crate) async fn register_interface(
+    context: std::sync::Arc<ClientContext>,
+    params: ParamsOfRegisterInterface,
+    app_object: AppObject<ParamsOfAppDebotInterface, ResultOfAppDebotInterface>,
+) -> ClientResult<()> {
+    let id = params.interface_id.trim_start_matches("0x").to_lowercase();
+    let iface = DebotInterfaceAdapter::new(id, params.abi, app_object);
+    crate::debot::register_interface(context, params.debot_handle, std::sync::Arc::new(iface)).await
+}
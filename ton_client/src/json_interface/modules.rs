@@ -57,12 +57,23 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
 
     module.register_error_code::<crate::crypto::ErrorCode>();
     module.register_type::<crate::crypto::SigningBoxHandle>();
+    module.register_type::<crate::crypto::SigningBoxContext>();
+    module.register_type::<crate::crypto::CollectedSignature>();
+    module.register_type::<crate::crypto::SigningBoxFailure>();
+    module.register_type::<crate::crypto::MnemonicWordSuggestions>();
+    module.register_type::<crate::crypto::MnemonicCorrection>();
+    module.register_type::<crate::crypto::DerivedSigningBox>();
+    module.register_type::<crate::crypto::SignatureBatchItem>();
     module.register_type::<crate::crypto::EncryptionBoxHandle>();
     module.register_type::<crate::crypto::EncryptionBoxInfo>();
     module.register_type::<crate::crypto::EncryptionAlgorithm>();
     module.register_type::<crate::crypto::CipherMode>();
     module.register_type::<crate::crypto::AesParams>();
     module.register_type::<crate::crypto::AesInfo>();
+    module.register_type::<crate::crypto::EncryptionSessionHandle>();
+    module.register_type::<crate::crypto::KeystoreSecret>();
+    module.register_type::<crate::crypto::KeystoreKdf>();
+    module.register_type::<crate::crypto::Keystore>();
 
     // Math
 
@@ -93,11 +104,16 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
         crate::crypto::verify_signature,
         crate::crypto::keys::verify_signature_api,
     );
+    module.register_sync_fn(
+        crate::crypto::verify_signatures_batch,
+        crate::crypto::keys::verify_signatures_batch_api,
+    );
 
     // Sha
 
     module.register_sync_fn(crate::crypto::sha256, crate::crypto::hash::sha256_api);
     module.register_sync_fn(crate::crypto::sha512, crate::crypto::hash::sha512_api);
+    module.register_sync_fn(crate::crypto::keccak256, crate::crypto::hash::keccak256_api);
 
     // Scrypt
 
@@ -166,6 +182,10 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
         crate::crypto::mnemonic_verify,
         crate::crypto::mnemonic::mnemonic_verify_api,
     );
+    module.register_sync_fn(
+        crate::crypto::mnemonic_suggest,
+        crate::crypto::mnemonic::mnemonic_suggest_api,
+    );
     module.register_sync_fn(
         crate::crypto::mnemonic_derive_sign_keys,
         crate::crypto::mnemonic::mnemonic_derive_sign_keys_api,
@@ -193,6 +213,64 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
         crate::crypto::hdkey_public_from_xprv,
         crate::crypto::hdkey::hdkey_public_from_xprv_api,
     );
+    module.register_sync_fn(
+        crate::crypto::slip10_xprv_from_seed,
+        crate::crypto::hdkey::slip10_xprv_from_seed_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::slip10_xprv_from_mnemonic,
+        crate::crypto::hdkey::slip10_xprv_from_mnemonic_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::slip10_derive_from_xprv,
+        crate::crypto::hdkey::slip10_derive_from_xprv_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::slip10_derive_from_xprv_path,
+        crate::crypto::hdkey::slip10_derive_from_xprv_path_api,
+    );
+
+    // Secp256k1
+
+    module.register_sync_fn_without_args(
+        crate::crypto::generate_random_secp256k1_keys,
+        crate::crypto::secp256k1::generate_random_secp256k1_keys_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::secp256k1_keypair_from_secret,
+        crate::crypto::secp256k1::secp256k1_keypair_from_secret_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::secp256k1_sign,
+        crate::crypto::secp256k1::secp256k1_sign_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::secp256k1_verify,
+        crate::crypto::secp256k1::secp256k1_verify_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::secp256k1_recover_public_key,
+        crate::crypto::secp256k1::secp256k1_recover_public_key_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::secp256k1_eth_address,
+        crate::crypto::secp256k1::secp256k1_eth_address_api,
+    );
+    module.register_async_fn(
+        crate::crypto::get_secp256k1_signing_box,
+        crate::crypto::secp256k1::get_secp256k1_signing_box_api,
+    );
+
+    // Secret sharing
+
+    module.register_sync_fn(
+        crate::crypto::split_secret,
+        crate::crypto::secret_sharing::split_secret_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::combine_shares,
+        crate::crypto::secret_sharing::combine_shares_api,
+    );
 
     // Encryption
 
@@ -212,6 +290,14 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
         crate::crypto::get_signing_box,
         crate::crypto::boxes::signing_box::get_signing_box_api,
     );
+    module.register_async_fn(
+        crate::crypto::get_signing_box_from_mnemonic,
+        crate::crypto::boxes::signing_box::get_signing_box_from_mnemonic_api,
+    );
+    module.register_async_fn(
+        crate::crypto::derive_signing_boxes,
+        crate::crypto::boxes::signing_box::derive_signing_boxes_api,
+    );
     module.register_async_fn(
         crate::crypto::signing_box_get_public_key,
         crate::crypto::boxes::signing_box::signing_box_get_public_key_api,
@@ -220,6 +306,14 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
         crate::crypto::signing_box_sign,
         crate::crypto::boxes::signing_box::signing_box_sign_api,
     );
+    module.register_async_fn(
+        crate::crypto::signing_box_sign_batch,
+        crate::crypto::boxes::signing_box::signing_box_sign_batch_api,
+    );
+    module.register_async_fn(
+        crate::crypto::collect_signatures,
+        crate::crypto::boxes::signing_box::collect_signatures_api,
+    );
     module.register_sync_fn(
         crate::crypto::remove_signing_box,
         crate::crypto::boxes::signing_box::remove_signing_box_api,
@@ -250,6 +344,53 @@ fn register_crypto(handlers: &mut RuntimeHandlers) {
         crate::crypto::create_encryption_box,
         crate::crypto::boxes::encryption_box::create_encryption_box_api,
     );
+    module.register_async_fn(
+        crate::crypto::encryption_box_encrypt_init,
+        crate::crypto::boxes::encryption_box::encryption_box_encrypt_init_api,
+    );
+    module.register_async_fn(
+        crate::crypto::encryption_box_encrypt_update,
+        crate::crypto::boxes::encryption_box::encryption_box_encrypt_update_api,
+    );
+    module.register_async_fn(
+        crate::crypto::encryption_box_encrypt_finish,
+        crate::crypto::boxes::encryption_box::encryption_box_encrypt_finish_api,
+    );
+    module.register_async_fn(
+        crate::crypto::encryption_box_decrypt_init,
+        crate::crypto::boxes::encryption_box::encryption_box_decrypt_init_api,
+    );
+    module.register_async_fn(
+        crate::crypto::encryption_box_decrypt_update,
+        crate::crypto::boxes::encryption_box::encryption_box_decrypt_update_api,
+    );
+    module.register_async_fn(
+        crate::crypto::encryption_box_decrypt_finish,
+        crate::crypto::boxes::encryption_box::encryption_box_decrypt_finish_api,
+    );
+    module.register_async_fn(
+        crate::crypto::remove_encryption_session,
+        crate::crypto::boxes::encryption_box::remove_encryption_session_api,
+    );
+
+    // Keystore
+
+    module.register_sync_fn(
+        crate::crypto::keystore_import,
+        crate::crypto::keystore::keystore_import_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::keystore_export,
+        crate::crypto::keystore::keystore_export_api,
+    );
+    module.register_sync_fn(
+        crate::crypto::keystore_change_password,
+        crate::crypto::keystore::keystore_change_password_api,
+    );
+    module.register_async_fn(
+        crate::crypto::keystore_get_signing_box,
+        crate::crypto::keystore::keystore_get_signing_box_api,
+    );
 
     module.register();
 }
@@ -269,6 +410,7 @@ fn register_abi(handlers: &mut RuntimeHandlers) {
     module.register_type::<crate::abi::CallSet>();
     module.register_type::<crate::abi::DeploySet>();
     module.register_type::<crate::abi::Signer>();
+    module.register_type::<crate::abi::MultiSignatureLayout>();
     module.register_type::<crate::abi::MessageBodyType>();
     module.register_type::<crate::abi::StateInitSource>();
     module.register_type::<crate::abi::StateInitParams>();
@@ -278,6 +420,10 @@ fn register_abi(handlers: &mut RuntimeHandlers) {
     module.register_type::<crate::abi::AbiData>();
     module.register_type::<crate::abi::AbiFunction>();
     module.register_type::<crate::abi::AbiContract>();
+    module.register_type::<crate::abi::AbiFieldChange>();
+    module.register_type::<crate::abi::AbiValidationError>();
+    module.register_type::<crate::abi::AbiFunctionSchema>();
+    module.register_type::<crate::abi::AbiEventSchema>();
 
     module.register_async_fn(
         crate::abi::encode_message_body,
@@ -315,6 +461,10 @@ fn register_abi(handlers: &mut RuntimeHandlers) {
         crate::abi::decode_account_data,
         crate::abi::decode_data::decode_account_data_api,
     );
+    module.register_async_fn(
+        crate::abi::encode_account_data,
+        crate::abi::encode_data::encode_account_data_api,
+    );
     module.register_async_fn(
         crate::abi::update_initial_data,
         crate::abi::init_data::update_initial_data_api,
@@ -323,6 +473,18 @@ fn register_abi(handlers: &mut RuntimeHandlers) {
         crate::abi::decode_initial_data,
         crate::abi::init_data::decode_initial_data_api,
     );
+    module.register_async_fn(
+        crate::abi::migrate_account_data,
+        crate::abi::migrate_data::migrate_account_data_api,
+    );
+    module.register_async_fn(
+        crate::abi::validate_abi,
+        crate::abi::validate::validate_abi_api,
+    );
+    module.register_async_fn(
+        crate::abi::get_json_schema,
+        crate::abi::json_schema::get_json_schema_api,
+    );
     module.register();
 }
 
@@ -552,17 +714,36 @@ fn register_debot(handlers: &mut RuntimeHandlers) {
     module.register_type::<crate::debot::DebotAction>();
     module.register_type::<crate::debot::DebotHandle>();
     module.register_type::<crate::debot::DebotInfo>();
+    module.register_type::<crate::debot::DebotInfoTranslation>();
     module.register_type::<crate::debot::DebotActivity>();
     module.register_type::<crate::debot::Spending>();
+    module.register_type::<crate::debot::DecodedCall>();
+    module.register_type::<crate::debot::DebotPolicy>();
+    module.register_type::<crate::debot::InterfaceUsage>();
     module.register_async_fn_with_app_object(
         crate::json_interface::debot::init,
         crate::json_interface::debot::init_api,
     );
     module.register_async_fn(crate::debot::start, crate::debot::start_api);
     module.register_async_fn(crate::debot::fetch, crate::debot::fetch_api);
+    module.register_async_fn(crate::debot::inspect, crate::debot::inspect_api);
     module.register_async_fn(crate::debot::execute, crate::debot::execute_api);
     module.register_async_fn(crate::debot::send, crate::debot::send_api);
     module.register_sync_fn(crate::debot::remove, crate::debot::remove_api);
+    module.register_async_fn(crate::debot::save_session, crate::debot::save_session_api);
+    module.register_async_fn(crate::debot::get_event_log, crate::debot::get_event_log_api);
+    module.register_async_fn(
+        crate::debot::replay_event_log,
+        crate::debot::replay_event_log_api,
+    );
+    module.register_async_fn_with_app_object(
+        crate::json_interface::debot::restore_session,
+        crate::json_interface::debot::restore_session_api,
+    );
+    module.register_async_fn_with_app_object(
+        crate::json_interface::debot::register_interface,
+        crate::json_interface::debot::register_interface_api,
+    );
     module.register();
 }
 
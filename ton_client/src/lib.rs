@@ -39,5 +39,9 @@ pub mod utils;
 #[cfg(test)]
 mod tests;
 
+// Allows `abigen!` generated code referring to `::ton_client` in the crate tests
+#[cfg(test)]
+extern crate self as ton_client;
+
 pub use self::json_interface::interop::*;
 pub use client::{ClientConfig, ClientContext};
@@ -31,6 +31,7 @@ pub enum ErrorCode {
     BlockNotFound = 511,
     InvalidData = 512,
     ExternalSignerMustNotBeUsed = 513,
+    MessageSigningRejected = 514,
 }
 
 pub struct Error;
@@ -51,6 +52,14 @@ impl Error {
         )
     }
 
+    pub fn message_signing_rejected(err: ClientError) -> ClientError {
+        error_with_data(
+            ErrorCode::MessageSigningRejected,
+            format!("Message can't be sent because its signing is rejected: {}", err.message),
+            err.data,
+        )
+    }
+
     pub fn message_already_expired() -> ClientError {
         error(
             ErrorCode::MessageAlreadyExpired,
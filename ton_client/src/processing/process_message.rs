@@ -3,7 +3,7 @@ use crate::client::ClientContext;
 use crate::error::{AddNetworkUrl, ClientResult};
 use crate::processing::internal::can_retry_expired_message;
 use crate::processing::{
-    send_message, wait_for_transaction, ErrorCode, ParamsOfSendMessage, ParamsOfWaitForTransaction,
+    send_message, wait_for_transaction, Error, ErrorCode, ParamsOfSendMessage, ParamsOfWaitForTransaction,
     ProcessingEvent, ResultOfProcessMessage, ResultOfSendMessage,
 };
 use crate::tvm::StdContractError;
@@ -23,6 +23,9 @@ pub async fn process_message<F: futures::Future<Output = ()> + Send>(
     params: ParamsOfProcessMessage,
     callback: impl Fn(ProcessingEvent) -> F + Send + Sync + 'static,
 ) -> ClientResult<ResultOfProcessMessage> {
+    if params.message_encode_params.signer.is_unsigned_multi() {
+        return Err(Error::external_signer_must_not_be_used());
+    }
     let abi = params.message_encode_params.abi.clone();
 
     let mut try_index = 0;
@@ -31,7 +34,14 @@ pub async fn process_message<F: futures::Future<Output = ()> + Send>(
         let mut encode_params = params.message_encode_params.clone();
         encode_params.processing_try_index = Some(try_index);
         let message = crate::abi::encode_message(context.clone(), encode_params)
-            .await?
+            .await
+            .map_err(|err| {
+                if err.code == crate::crypto::ErrorCode::SigningBoxRejected as u32 {
+                    Error::message_signing_rejected(err)
+                } else {
+                    err
+                }
+            })?
             .message;
 
         // Send
@@ -15,6 +15,7 @@
 use super::types::ResolvedExecutionOptions;
 use crate::error::ClientResult;
 use crate::tvm::Error;
+use std::convert::TryFrom;
 use std::sync::Arc;
 use ton_block::{
     Account, ConfigParams, CurrencyCollection,
@@ -57,7 +58,12 @@ pub(crate) fn call_tvm(
         .put(7, &mut sci.into_temp_data())
         .map_err(|err| Error::internal_error(format!("can not put SCI to registers: {}", err)))?;
 
-    let gas_limit = 1_000_000_000;
+    let gas_limit = i64::try_from(options.gas_limit).map_err(|_| {
+        crate::client::Error::invalid_params(
+            &json!({ "gas_limit": options.gas_limit }).to_string(),
+            format!("gas limit must not exceed {}", i64::MAX),
+        )
+    })?;
     let gas = Gas::new(gas_limit, 0, gas_limit, 10);
 
     let mut engine = ton_vm::executor::Engine::new().setup(
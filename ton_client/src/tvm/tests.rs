@@ -127,6 +127,20 @@ async fn test_execute_get() {
         .output;
 
     assert_eq!(result[0][0][0], "1588268660");
+
+    let error = run_get
+        .call(ParamsOfRunGet {
+            account: elector.clone(),
+            function_name: "past_elections".into(),
+            execution_options: Some(ExecutionOptions {
+                gas_limit: Some(u64::MAX),
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(error.code, crate::client::ErrorCode::InvalidParams as u32);
 }
 
 #[tokio::test(core_threads = 2)]
@@ -35,6 +35,8 @@ pub struct ExecutionOptions {
     pub block_lt: Option<u64>,
     /// transaction logical time
     pub transaction_lt: Option<u64>,
+    /// Overrides standard TVM gas limit. Used by `run_tvm` and `run_get` only.
+    pub gas_limit: Option<u64>,
 }
 
 pub(crate) struct ResolvedExecutionOptions {
@@ -42,8 +44,11 @@ pub(crate) struct ResolvedExecutionOptions {
     pub block_time: u32,
     pub block_lt: u64,
     pub transaction_lt: u64,
+    pub gas_limit: u64,
 }
 
+pub(crate) const DEFAULT_GAS_LIMIT: u64 = 1_000_000_000;
+
 pub(crate) async fn blockchain_config_from_boc(context: &ClientContext, b64: &str) -> ClientResult<BlockchainConfig> {
     let config_params = deserialize_object_from_boc(context, b64, "blockchain config").await?;
     BlockchainConfig::with_config(config_params.object)
@@ -72,6 +77,7 @@ impl ResolvedExecutionOptions {
             block_time,
             blockchain_config: config,
             transaction_lt,
+            gas_limit: options.gas_limit.unwrap_or(DEFAULT_GAS_LIMIT),
         })
     }
 }